@@ -0,0 +1,77 @@
+//! Compares parsing to the owned AST through the CST with the fast path.
+//!
+//! Run with `cargo bench`. Each iteration parses all the samples of
+//! `tests/as2`, as a batch analysis would.
+
+use as2_parser::parser::{parse, parse_script, parse_script_fast, ParseOptions};
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+const ITERATIONS: u32 = 200;
+
+fn main() {
+  let texts = read_samples(Path::new("../tests/as2"));
+  let bytes: usize = texts.iter().map(String::len).sum();
+  println!("{} samples, {} bytes, {} iterations", texts.len(), bytes, ITERATIONS);
+
+  let cst = bench("parse (CST only)", &texts, |text| {
+    parse(text, &ParseOptions::default()).syntax().descendants().count()
+  });
+  let lowered = bench("parse_script (CST + lowering)", &texts, |text| {
+    parse_script(text, &ParseOptions::default()).stmts.len()
+  });
+  let fast = bench("parse_script_fast", &texts, |text| {
+    parse_script_fast(text, &ParseOptions::default()).stmts.len()
+  });
+
+  println!(
+    "parse_script_fast speedup: {:.2}x over parse_script, {:.2}x over parse",
+    lowered.as_secs_f64() / fast.as_secs_f64(),
+    cst.as_secs_f64() / fast.as_secs_f64(),
+  );
+}
+
+/// Reads the `main.as2` file of each sample directory, recursively.
+fn read_samples(dir: &Path) -> Vec<String> {
+  let mut texts: Vec<String> = Vec::new();
+  let mut entries: Vec<_> = fs::read_dir(dir)
+    .expect("Failed to read samples")
+    .map(|entry| entry.expect("Failed to read sample entry").path())
+    .collect();
+  entries.sort();
+  for path in entries {
+    if path.is_dir() {
+      texts.extend(read_samples(&path));
+    } else if path.file_name().map_or(false, |name| name == "main.as2") {
+      texts.push(fs::read_to_string(&path).expect("Failed to read sample"));
+    }
+  }
+  texts
+}
+
+/// Runs `f` on every text for `ITERATIONS` iterations and prints the mean time.
+///
+/// The sum of the results is printed so the work can't be optimized away.
+fn bench<F: Fn(&str) -> usize>(name: &str, texts: &[String], f: F) -> Duration {
+  let mut checksum: usize = 0;
+  // Warm up
+  for text in texts {
+    checksum += f(text);
+  }
+  let start = Instant::now();
+  for _ in 0..ITERATIONS {
+    for text in texts {
+      checksum += f(text);
+    }
+  }
+  let elapsed = start.elapsed();
+  let mean = elapsed / ITERATIONS;
+  println!(
+    "{:<32} {:>10.3} ms/iter (checksum {})",
+    name,
+    mean.as_secs_f64() * 1e3,
+    checksum
+  );
+  elapsed
+}
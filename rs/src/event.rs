@@ -0,0 +1,133 @@
+//! Parse events
+//!
+//! The parser does not build a tree directly: it records events, then sends
+//! them to a `TreeSink`. The rowan tree of `parser::Parsed` is built by one of
+//! these sinks.
+
+use crate::parser::SyntaxError;
+use crate::types::syntax::SyntaxKind;
+use rowan::SmolStr;
+use std::mem;
+
+/// Receiver of the syntax tree as a stream of events
+///
+/// Nodes are well nested: each `start_node` is matched by a later
+/// `finish_node`. Tokens include trivia, so the concatenation of their text is
+/// the source text. An error is received after the tokens consumed before it
+/// was reported.
+pub trait TreeSink {
+  fn start_node(&mut self, kind: SyntaxKind);
+
+  fn token(&mut self, kind: SyntaxKind, text: SmolStr);
+
+  fn finish_node(&mut self);
+
+  fn error(&mut self, error: SyntaxError);
+}
+
+/// Event recorded by the parser
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Event {
+  /// Start of a node
+  ///
+  /// `inner` is the index of the start of a node started earlier at the same
+  /// checkpoint: it is nested in this one and must be started right after it.
+  Start {
+    kind: SyntaxKind,
+    inner: Option<usize>,
+  },
+  Token {
+    kind: SyntaxKind,
+    text: SmolStr,
+  },
+  Finish,
+  Error(SyntaxError),
+  /// Checkpoint where no node was started, or start already sent
+  Tombstone,
+}
+
+/// Sends recorded events to a sink.
+pub(crate) fn process<S: TreeSink + ?Sized>(mut events: Vec<Event>, sink: &mut S) {
+  for index in 0..events.len() {
+    match mem::replace(&mut events[index], Event::Tombstone) {
+      Event::Start { kind, mut inner } => {
+        sink.start_node(kind);
+        while let Some(inner_index) = inner {
+          inner = match mem::replace(&mut events[inner_index], Event::Tombstone) {
+            Event::Start { kind, inner } => {
+              sink.start_node(kind);
+              inner
+            }
+            _ => unreachable!("inner event must be a node start"),
+          };
+        }
+      }
+      Event::Token { kind, text } => sink.token(kind, text),
+      Event::Finish => sink.finish_node(),
+      Event::Error(error) => sink.error(error),
+      Event::Tombstone => {}
+    }
+  }
+}
+
+#[cfg(test)]
+mod event_tests {
+  use crate::event::TreeSink;
+  use crate::parser::{parse, parse_with_sink, ParseOptions, SyntaxError};
+  use crate::types::syntax::SyntaxKind;
+  use rowan::SmolStr;
+
+  /// Sink counting nodes, and checking that they are well nested
+  #[derive(Default)]
+  struct CountSink {
+    text: String,
+    nodes: usize,
+    depth: usize,
+    errors: Vec<SyntaxError>,
+  }
+
+  impl TreeSink for CountSink {
+    fn start_node(&mut self, _kind: SyntaxKind) {
+      self.nodes += 1;
+      self.depth += 1;
+    }
+
+    fn token(&mut self, _kind: SyntaxKind, text: SmolStr) {
+      assert!(self.depth > 0);
+      self.text.push_str(&text);
+    }
+
+    fn finish_node(&mut self) {
+      self.depth -= 1;
+    }
+
+    fn error(&mut self, error: SyntaxError) {
+      self.errors.push(error);
+    }
+  }
+
+  #[test]
+  fn test_count_sink() {
+    let text = "a = b + c * d;\nif (e) { f(1, ) }";
+    let mut sink = CountSink::default();
+    parse_with_sink(text, &ParseOptions::default(), &mut sink);
+    let parsed = parse(text, &ParseOptions::default());
+    assert_eq!(sink.depth, 0);
+    assert_eq!(sink.text, text);
+    assert_eq!(sink.nodes, parsed.syntax().descendants().count());
+    assert_eq!(sink.errors, parsed.errors());
+  }
+
+  #[test]
+  fn test_long_operator_chain() {
+    const TERMS: usize = 100_000;
+    let text = format!("{}a;", "a + ".repeat(TERMS - 1));
+    let mut sink = CountSink::default();
+    parse_with_sink(&text, &ParseOptions::default(), &mut sink);
+    assert_eq!(sink.depth, 0);
+    assert_eq!(sink.text, text);
+    // Script, statement, identifiers and binary expressions
+    assert_eq!(sink.nodes, 2 + TERMS + (TERMS - 1));
+    assert_eq!(sink.errors, vec![]);
+  }
+}
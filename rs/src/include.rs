@@ -0,0 +1,406 @@
+//! Expansion of `#include` directives
+//!
+//! Included files are parsed separately, then their tree is inserted as the
+//! last child of the `NodeInclude` directive. The resulting tree spans the
+//! text of all the files: an origin map traces each range back to the file
+//! where it was written.
+
+use crate::parser::{parse, ParseOptions, SyntaxError};
+use crate::types::syntax::{unescape_string, SyntaxKind, SyntaxNode};
+use rowan::{NodeOrToken, TextRange, TextUnit, WalkEvent};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+/// Provides the text of included files
+pub trait SourceLoader {
+  /// Loads the text of the file at `path`.
+  ///
+  /// Paths of included files are resolved relative to the directory of the
+  /// including file and normalized.
+  fn load(&self, path: &Path) -> io::Result<String>;
+}
+
+/// Loads files from the file system
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct FsLoader;
+
+impl SourceLoader for FsLoader {
+  fn load(&self, path: &Path) -> io::Result<String> {
+    fs::read_to_string(path)
+  }
+}
+
+/// Loads files from memory, mostly for tests
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MemoryLoader {
+  files: HashMap<PathBuf, String>,
+}
+
+impl MemoryLoader {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Adds a file, replacing any file with the same path.
+  pub fn insert<P: AsRef<Path>, T: Into<String>>(&mut self, path: P, text: T) {
+    self.files.insert(normalize(path.as_ref()), text.into());
+  }
+}
+
+impl SourceLoader for MemoryLoader {
+  fn load(&self, path: &Path) -> io::Result<String> {
+    match self.files.get(path) {
+      Some(text) => Ok(text.clone()),
+      None => Err(io::Error::new(
+        io::ErrorKind::NotFound,
+        "file not found in memory loader",
+      )),
+    }
+  }
+}
+
+/// File parsed during include expansion
+///
+/// A file included multiple times has one source per inclusion.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Source {
+  pub path: PathBuf,
+  /// Syntax errors, with ranges relative to this source
+  pub errors: Vec<SyntaxError>,
+  /// Index of the including source and range of the directive in it, `None`
+  /// for the root source
+  pub included_from: Option<(usize, TextRange)>,
+}
+
+/// Error found while expanding an `#include` directive
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IncludeError {
+  pub kind: IncludeErrorKind,
+  /// Index of the source containing the directive
+  pub source: usize,
+  /// Range of the directive in its source
+  pub range: TextRange,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IncludeErrorKind {
+  /// The file includes itself, directly or indirectly
+  Cycle,
+  /// The path is not a valid string literal
+  InvalidPath,
+  /// The loader failed to provide the file
+  Load(io::ErrorKind),
+}
+
+/// Contiguous part of the expanded text coming from a single source
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Segment {
+  /// Range in the expanded text
+  range: TextRange,
+  source: usize,
+  /// Start of the segment in its source
+  source_start: TextUnit,
+}
+
+/// Result of include expansion
+#[derive(Debug, Clone)]
+pub struct Expanded {
+  green: rowan::GreenNode,
+  sources: Vec<Source>,
+  errors: Vec<IncludeError>,
+  /// Segments in text order
+  segments: Vec<Segment>,
+}
+
+impl Expanded {
+  /// Returns the expanded tree, spanning the text of all the sources.
+  pub fn syntax(&self) -> SyntaxNode {
+    SyntaxNode::new_root(self.green.clone())
+  }
+
+  /// Returns the parsed sources. The root source is the first one.
+  pub fn sources(&self) -> &[Source] {
+    &self.sources
+  }
+
+  pub fn errors(&self) -> &[IncludeError] {
+    &self.errors
+  }
+
+  /// Maps a range of the expanded text to the index of its source and the
+  /// range in this source.
+  ///
+  /// The range is attributed to the source containing its start. A range
+  /// containing expanded directives, such as the range of their parent node,
+  /// maps to the range enclosing the directives. Returns `None` if the range
+  /// ends in another source.
+  pub fn origin(&self, range: TextRange) -> Option<(usize, TextRange)> {
+    let first = self
+      .segments
+      .iter()
+      .position(|segment| segment.range.contains(range.start()))
+      .or_else(|| {
+        self
+          .segments
+          .iter()
+          .rposition(|segment| segment.range.end() == range.start())
+      })?;
+    let start = &self.segments[first];
+    let end = self.segments[first..]
+      .iter()
+      .find(|segment| segment.source == start.source && segment.range.contains_inclusive(range.end()))?;
+    Some((
+      start.source,
+      TextRange::from_to(
+        start.source_start + (range.start() - start.range.start()),
+        end.source_start + (range.end() - end.range.start()),
+      ),
+    ))
+  }
+
+  /// Returns the source path and range where a node of the expanded tree was
+  /// written.
+  pub fn node_origin(&self, node: &SyntaxNode) -> Option<(&Path, TextRange)> {
+    let (source, range) = self.origin(node.text_range())?;
+    Some((&self.sources[source].path, range))
+  }
+}
+
+/// Parses the file at `path` and recursively expands its `#include`
+/// directives.
+///
+/// Only a failure to load the root file is returned as an error: failures of
+/// included files are reported in `Expanded::errors`.
+pub fn expand<L: SourceLoader + ?Sized>(path: &Path, loader: &L, options: &ParseOptions) -> io::Result<Expanded> {
+  let path = normalize(path);
+  let text = loader.load(&path)?;
+  let mut expander = Expander {
+    loader,
+    options,
+    builder: rowan::GreenNodeBuilder::new(),
+    offset: TextUnit::from(0),
+    sources: Vec::new(),
+    errors: Vec::new(),
+    segments: Vec::new(),
+    stack: Vec::new(),
+  };
+  expander.source(path, &text, None);
+  Ok(Expanded {
+    green: expander.builder.finish(),
+    sources: expander.sources,
+    errors: expander.errors,
+    segments: expander.segments,
+  })
+}
+
+struct Expander<'a, L: SourceLoader + ?Sized> {
+  loader: &'a L,
+  options: &'a ParseOptions,
+  builder: rowan::GreenNodeBuilder<'static>,
+  /// Length of the expanded text built so far
+  offset: TextUnit,
+  sources: Vec<Source>,
+  errors: Vec<IncludeError>,
+  segments: Vec<Segment>,
+  /// Paths of the sources currently being expanded, to detect cycles
+  stack: Vec<PathBuf>,
+}
+
+impl<'a, L: SourceLoader + ?Sized> Expander<'a, L> {
+  /// Parses a source and copies its tree to the builder, expanding directives.
+  fn source(&mut self, path: PathBuf, text: &str, included_from: Option<(usize, TextRange)>) {
+    let parsed = parse(text, self.options);
+    let source = self.sources.len();
+    self.sources.push(Source {
+      path: path.clone(),
+      errors: parsed.errors().to_vec(),
+      included_from,
+    });
+    self.stack.push(path);
+    // Start of the current segment, in the expanded text and in the source
+    let mut segment_start = (self.offset, TextUnit::from(0));
+    for event in parsed.syntax().preorder_with_tokens() {
+      match event {
+        WalkEvent::Enter(NodeOrToken::Node(node)) => self.builder.start_node(node.kind().into()),
+        WalkEvent::Enter(NodeOrToken::Token(token)) => {
+          self.offset += TextUnit::of_str(token.text());
+          self.builder.token(token.kind().into(), token.text().clone());
+        }
+        WalkEvent::Leave(NodeOrToken::Node(node)) => {
+          if node.kind() == SyntaxKind::NodeInclude {
+            self.push_segment(segment_start, source);
+            self.include(source, &node);
+            segment_start = (self.offset, node.text_range().end());
+          }
+          self.builder.finish_node();
+        }
+        WalkEvent::Leave(NodeOrToken::Token(_)) => {}
+      }
+    }
+    self.push_segment(segment_start, source);
+    self.stack.pop();
+  }
+
+  /// Records the segment from `start` to the current offset.
+  fn push_segment(&mut self, start: (TextUnit, TextUnit), source: usize) {
+    let (start, source_start) = start;
+    self.segments.push(Segment {
+      range: TextRange::from_to(start, self.offset),
+      source,
+      source_start,
+    });
+  }
+
+  /// Expands a directive from `source`, before its node is finished.
+  fn include(&mut self, source: usize, directive: &SyntaxNode) {
+    let range = directive.text_range();
+    let value = match directive
+      .children()
+      .find(|child| child.kind() == SyntaxKind::NodeStrLit)
+    {
+      // The missing string literal is already reported as a syntax error
+      None => return,
+      Some(str_lit) => str_lit.first_token().and_then(|token| unescape_string(token.text())),
+    };
+    let value = match value {
+      Some(value) => value,
+      None => return self.error(IncludeErrorKind::InvalidPath, source, range),
+    };
+    let including = &self.sources[source].path;
+    let path = normalize(&including.parent().unwrap_or_else(|| Path::new("")).join(value));
+    if self.stack.contains(&path) {
+      return self.error(IncludeErrorKind::Cycle, source, range);
+    }
+    match self.loader.load(&path) {
+      Ok(text) => self.source(path, &text, Some((source, range))),
+      Err(e) => self.error(IncludeErrorKind::Load(e.kind()), source, range),
+    }
+  }
+
+  fn error(&mut self, kind: IncludeErrorKind, source: usize, range: TextRange) {
+    self.errors.push(IncludeError { kind, source, range })
+  }
+}
+
+/// Lexically removes `.` and `..` components from a path.
+fn normalize(path: &Path) -> PathBuf {
+  let mut normalized = PathBuf::new();
+  for component in path.components() {
+    match component {
+      Component::CurDir => {}
+      Component::ParentDir => match normalized.components().next_back() {
+        Some(Component::Normal(_)) => {
+          normalized.pop();
+        }
+        _ => normalized.push(".."),
+      },
+      component => normalized.push(component),
+    }
+  }
+  normalized
+}
+
+#[cfg(test)]
+mod include_tests {
+  use crate::include::{expand, IncludeErrorKind, MemoryLoader};
+  use crate::parser::ParseOptions;
+  use crate::types::syntax::SyntaxKind;
+  use rowan::{TextRange, TextUnit};
+  use std::io;
+  use std::path::Path;
+
+  fn range(start: u32, end: u32) -> TextRange {
+    TextRange::from_to(TextUnit::from(start), TextUnit::from(end))
+  }
+
+  #[test]
+  fn test_expand_include() {
+    let mut loader = MemoryLoader::new();
+    loader.insert("main.as", "#include \"lib/util.as\"\nf(x);\n");
+    loader.insert("lib/util.as", "var x = 1;\n#include \"../consts.as\"\n");
+    loader.insert("consts.as", "var y = 2;\n");
+    let expanded = expand(Path::new("main.as"), &loader, &ParseOptions::default()).unwrap();
+
+    let root = expanded.syntax();
+    assert_eq!(
+      root.text().to_string(),
+      "#include \"lib/util.as\"var x = 1;\n#include \"../consts.as\"var y = 2;\n\n\nf(x);\n"
+    );
+    assert_eq!(expanded.errors(), &[]);
+    let paths: Vec<&Path> = expanded.sources().iter().map(|source| source.path.as_path()).collect();
+    assert_eq!(
+      paths,
+      vec![Path::new("main.as"), Path::new("lib/util.as"), Path::new("consts.as")]
+    );
+    assert_eq!(expanded.sources()[2].included_from, Some((1, range(11, 34))));
+
+    let origins: Vec<(SyntaxKind, &Path, TextRange)> = root
+      .descendants()
+      .filter(|node| node.kind() == SyntaxKind::NodeVarDecl || node.kind() == SyntaxKind::NodeStatement)
+      .map(|node| {
+        let (path, range) = expanded.node_origin(&node).unwrap();
+        (node.kind(), path, range)
+      })
+      .collect();
+    assert_eq!(
+      origins,
+      vec![
+        (SyntaxKind::NodeVarDecl, Path::new("lib/util.as"), range(0, 10)),
+        (SyntaxKind::NodeVarDecl, Path::new("consts.as"), range(0, 10)),
+        (SyntaxKind::NodeStatement, Path::new("main.as"), range(23, 28)),
+      ]
+    );
+    let include = root
+      .descendants()
+      .find(|node| node.kind() == SyntaxKind::NodeInclude)
+      .unwrap();
+    assert_eq!(
+      expanded.node_origin(&include),
+      Some((Path::new("main.as"), range(0, 22)))
+    );
+    assert_eq!(expanded.node_origin(&root), Some((Path::new("main.as"), range(0, 29))));
+  }
+
+  #[test]
+  fn test_include_errors() {
+    let mut loader = MemoryLoader::new();
+    loader.insert("a.as", "#include \"b.as\"\n#include \"missing.as\"\n");
+    loader.insert("b.as", "#include \"a.as\"\n");
+    let expanded = expand(Path::new("a.as"), &loader, &ParseOptions::default()).unwrap();
+
+    let errors: Vec<(IncludeErrorKind, usize, TextRange)> = expanded
+      .errors()
+      .iter()
+      .map(|error| (error.kind, error.source, error.range))
+      .collect();
+    assert_eq!(
+      errors,
+      vec![
+        (IncludeErrorKind::Cycle, 1, range(0, 15)),
+        (IncludeErrorKind::Load(io::ErrorKind::NotFound), 0, range(16, 37)),
+      ]
+    );
+    assert_eq!(
+      expanded.syntax().text().to_string(),
+      "#include \"b.as\"#include \"a.as\"\n\n#include \"missing.as\"\n"
+    );
+    assert!(expand(Path::new("missing.as"), &loader, &ParseOptions::default()).is_err());
+  }
+
+  #[test]
+  fn test_include_invalid_path() {
+    // `\l` is a plain `l`, but `\u` must start a unicode escape
+    let mut loader = MemoryLoader::new();
+    loader.insert("main.as", "#include \"..\\lib\\util.as\"\n");
+    let expanded = expand(Path::new("main.as"), &loader, &ParseOptions::default()).unwrap();
+
+    let errors: Vec<(IncludeErrorKind, usize, TextRange)> = expanded
+      .errors()
+      .iter()
+      .map(|error| (error.kind, error.source, error.range))
+      .collect();
+    assert_eq!(errors, vec![(IncludeErrorKind::InvalidPath, 0, range(0, 25))]);
+  }
+}
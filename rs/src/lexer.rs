@@ -12,11 +12,43 @@ pub struct LexerToken {
 #[derive(Debug, Clone)]
 pub struct Lexer<'text> {
   text: &'text str,
+  /// Emit Flash 4 target paths such as `/clip:var`
+  flash4: bool,
+  /// Emit decompiler pseudo-instructions such as `§§push`
+  decompiler: bool,
 }
 
 impl Lexer<'_> {
   pub fn new(text: &str) -> Lexer {
-    Lexer { text }
+    Lexer {
+      text,
+      flash4: false,
+      decompiler: false,
+    }
+  }
+
+  /// Creates a lexer in Flash 4 compatibility mode.
+  ///
+  /// In this mode, target paths with a variable such as `/clip/sub:score` are
+  /// lexed as a single `TokenTargetPath`.
+  pub fn flash4(text: &str) -> Lexer {
+    Lexer {
+      text,
+      flash4: true,
+      decompiler: false,
+    }
+  }
+
+  /// Enables decompiler mode.
+  ///
+  /// In this mode, the pseudo-instructions `§§push`, `§§pop` and `§§goto`
+  /// emitted by the JPEXS/FFDec decompiler are lexed as `TokenPush`,
+  /// `TokenPop` and `TokenGoto`.
+  pub fn with_decompiler_mode(self) -> Self {
+    Lexer {
+      decompiler: true,
+      ..self
+    }
   }
 }
 
@@ -24,7 +56,12 @@ impl<'text> Iterator for Lexer<'text> {
   type Item = LexerToken;
 
   fn next(&mut self) -> Option<Self::Item> {
-    let token = next_token(self.text);
+    let special = match (self.flash4, self.decompiler) {
+      (_, true) if self.text.starts_with('§') => next_pseudo_instruction(self.text),
+      (true, _) => next_target_path(self.text),
+      _ => None,
+    };
+    let token = special.or_else(|| next_token(self.text));
     if let Some(ref token) = &token {
       self.text = &self.text[token.text.len()..];
     }
@@ -45,17 +82,148 @@ fn next_token(input: &str) -> Option<LexerToken> {
     Some(c) => c,
   };
   let kind: SyntaxKind = match first {
-    '/' => match chars.next() {
-      Some('/') => end_trailing_comment(chars),
-      _ => unimplemented!(),
-    },
-    c if is_id_start(c) => end_id_or_keyword(c, chars),
+    '/' => {
+      if eat_char(chars, '/') {
+        end_trailing_comment(chars)
+      } else if eat_char(chars, '*') {
+        end_delimited_comment(chars)
+      } else if eat_char(chars, '=') {
+        SyntaxKind::TokenSlashEq
+      } else {
+        SyntaxKind::TokenSlash
+      }
+    }
+    c if is_id_start(c) => end_id_or_keyword(input, chars),
     c if is_whitespace(c) => end_whitespace(c, chars),
+    c if is_dec_digit(c) => end_num_lit(c, chars),
+    '.' if chars.as_str().starts_with(is_dec_digit) => end_num_lit('.', chars),
+    '.' => SyntaxKind::TokenDot,
+    ',' => SyntaxKind::TokenComma,
+    ':' => SyntaxKind::TokenColon,
+    '?' => SyntaxKind::TokenQuestion,
     ';' => SyntaxKind::TokenSemicolon,
     '(' => SyntaxKind::TokenOpenParen,
     ')' => SyntaxKind::TokenCloseParen,
-    '"' => end_double_quoted_string(chars),
-    c => unimplemented!("Tokens starting with: {:?}", c),
+    '[' => SyntaxKind::TokenOpenBracket,
+    ']' => SyntaxKind::TokenCloseBracket,
+    '{' => SyntaxKind::TokenOpenBrace,
+    '}' => SyntaxKind::TokenCloseBrace,
+    '~' => SyntaxKind::TokenTilde,
+    '<' => {
+      if eat_char(chars, '<') {
+        if eat_char(chars, '=') {
+          SyntaxKind::TokenLtLtEq
+        } else {
+          SyntaxKind::TokenLtLt
+        }
+      } else if eat_char(chars, '=') {
+        SyntaxKind::TokenLtEq
+      } else {
+        SyntaxKind::TokenLt
+      }
+    }
+    '>' => {
+      if eat_char(chars, '>') {
+        if eat_char(chars, '>') {
+          if eat_char(chars, '=') {
+            SyntaxKind::TokenGtGtGtEq
+          } else {
+            SyntaxKind::TokenGtGtGt
+          }
+        } else if eat_char(chars, '=') {
+          SyntaxKind::TokenGtGtEq
+        } else {
+          SyntaxKind::TokenGtGt
+        }
+      } else if eat_char(chars, '=') {
+        SyntaxKind::TokenGtEq
+      } else {
+        SyntaxKind::TokenGt
+      }
+    }
+    '=' => {
+      if eat_char(chars, '=') {
+        if eat_char(chars, '=') {
+          SyntaxKind::TokenEqEqEq
+        } else {
+          SyntaxKind::TokenEqEq
+        }
+      } else {
+        SyntaxKind::TokenEq
+      }
+    }
+    '!' => {
+      if eat_char(chars, '=') {
+        if eat_char(chars, '=') {
+          SyntaxKind::TokenExclEqEq
+        } else {
+          SyntaxKind::TokenExclEq
+        }
+      } else {
+        SyntaxKind::TokenExcl
+      }
+    }
+    '+' => {
+      if eat_char(chars, '+') {
+        SyntaxKind::TokenPlusPlus
+      } else if eat_char(chars, '=') {
+        SyntaxKind::TokenPlusEq
+      } else {
+        SyntaxKind::TokenPlus
+      }
+    }
+    '-' => {
+      if eat_char(chars, '-') {
+        SyntaxKind::TokenMinusMinus
+      } else if eat_char(chars, '=') {
+        SyntaxKind::TokenMinusEq
+      } else {
+        SyntaxKind::TokenMinus
+      }
+    }
+    '*' => {
+      if eat_char(chars, '=') {
+        SyntaxKind::TokenStarEq
+      } else {
+        SyntaxKind::TokenStar
+      }
+    }
+    '%' => {
+      if eat_char(chars, '=') {
+        SyntaxKind::TokenPercentEq
+      } else {
+        SyntaxKind::TokenPercent
+      }
+    }
+    '&' => {
+      if eat_char(chars, '&') {
+        SyntaxKind::TokenAmpAmp
+      } else if eat_char(chars, '=') {
+        SyntaxKind::TokenAmpEq
+      } else {
+        SyntaxKind::TokenAmp
+      }
+    }
+    '|' => {
+      if eat_char(chars, '|') {
+        SyntaxKind::TokenPipePipe
+      } else if eat_char(chars, '=') {
+        SyntaxKind::TokenPipeEq
+      } else {
+        SyntaxKind::TokenPipe
+      }
+    }
+    '^' => {
+      if eat_char(chars, '=') {
+        SyntaxKind::TokenCaretEq
+      } else {
+        SyntaxKind::TokenCaret
+      }
+    }
+    '"' => end_string('"', chars),
+    '\'' => end_string('\'', chars),
+    '#' => end_directive(chars),
+    _ => SyntaxKind::TokenError,
   };
   let token_len = input_len - chars.as_str().len();
   Some(LexerToken {
@@ -80,12 +248,33 @@ fn end_trailing_comment(chars: &mut Chars) -> SyntaxKind {
   SyntaxKind::TokenTrailingComment
 }
 
-fn end_double_quoted_string(chars: &mut Chars) -> SyntaxKind {
+/// Consumes a delimited comment.
+/// The starting `/*` must already be consumed.
+fn end_delimited_comment(chars: &mut Chars) -> SyntaxKind {
+  let mut multiline = false;
+  loop {
+    match chars.next() {
+      None => return SyntaxKind::TokenError,
+      Some('*') if eat_char(chars, '/') => break,
+      Some(c) if is_line_terminator_sequence_start(c) => multiline = true,
+      _ => {}
+    }
+  }
+  if multiline {
+    SyntaxKind::TokenMultilineComment
+  } else {
+    SyntaxKind::TokenUnilineComment
+  }
+}
+
+/// Consumes a string literal.
+/// The opening quote must already be consumed.
+fn end_string(quote: char, chars: &mut Chars) -> SyntaxKind {
   // TODO: Handle line terminators
   loop {
     match chars.next() {
       None => return SyntaxKind::TokenError,
-      Some('"') => return SyntaxKind::TokenStrLit,
+      Some(c) if c == quote => return SyntaxKind::TokenStrLit,
       Some('\\') => {
         chars.next(); // Skip next char
       }
@@ -94,86 +283,173 @@ fn end_double_quoted_string(chars: &mut Chars) -> SyntaxKind {
   }
 }
 
-/// Consumes an identifier or keyword
-// TODO: Simplify this function!
-#[allow(clippy::cognitive_complexity)]
-fn end_id_or_keyword(first: char, chars: &mut Chars) -> SyntaxKind {
-  debug_assert!(is_id_start(first));
-  match first {
-    't' => match chars.next() {
-      Some('h') => match chars.next() {
-        Some('i') => match chars.next() {
-          Some('s') => {
-            let old_chars = chars.clone();
-            match chars.next() {
-              Some(c) if is_id_continue(c) => end_id(chars),
-              _ => {
-                *chars = old_chars;
-                SyntaxKind::TokenThis
-              }
-            }
-          }
-          Some(c) if is_id_continue(c) => end_id(chars),
-          _ => SyntaxKind::TokenIdent,
-        },
-        Some('r') => match chars.next() {
-          Some('o') => match chars.next() {
-            Some('w') => {
-              let old_chars = chars.clone();
-              match chars.next() {
-                Some(c) if is_id_continue(c) => end_id(chars),
-                _ => {
-                  *chars = old_chars;
-                  SyntaxKind::TokenThrow
-                }
-              }
-            }
-            Some(c) if is_id_continue(c) => end_id(chars),
-            _ => SyntaxKind::TokenIdent,
-          },
-          Some(c) if is_id_continue(c) => end_id(chars),
-          _ => SyntaxKind::TokenIdent,
-        },
-        Some(c) if is_id_continue(c) => end_id(chars),
-        _ => SyntaxKind::TokenIdent,
-      },
-      Some('r') => match chars.next() {
-        Some('u') => match chars.next() {
-          Some('e') => {
-            let old_chars = chars.clone();
-            match chars.next() {
-              Some(c) if is_id_continue(c) => end_id(chars),
-              _ => {
-                *chars = old_chars;
-                SyntaxKind::TokenTrue
-              }
-            }
-          }
-          Some(c) if is_id_continue(c) => end_id(chars),
-          _ => SyntaxKind::TokenIdent,
-        },
-        Some('y') => {
-          let old_chars = chars.clone();
-          match chars.next() {
-            Some(c) if is_id_continue(c) => end_id(chars),
-            _ => {
-              *chars = old_chars;
-              SyntaxKind::TokenTry
-            }
-          }
+/// Consumes a number literal.
+/// The first character (a digit or the decimal point) must already be consumed.
+fn end_num_lit(first: char, chars: &mut Chars) -> SyntaxKind {
+  if first == '0' && (eat_char(chars, 'x') || eat_char(chars, 'X')) {
+    if !chars.as_str().starts_with(is_hex_digit) {
+      return SyntaxKind::TokenError;
+    }
+    eat_while(chars, is_hex_digit);
+    return SyntaxKind::TokenNumLit;
+  }
+  eat_while(chars, is_dec_digit);
+  if first != '.' && eat_char(chars, '.') {
+    eat_while(chars, is_dec_digit);
+  }
+  let old_chars = chars.clone();
+  if eat_char(chars, 'e') || eat_char(chars, 'E') {
+    if !eat_char(chars, '+') {
+      eat_char(chars, '-');
+    }
+    if chars.as_str().starts_with(is_dec_digit) {
+      eat_while(chars, is_dec_digit);
+    } else {
+      // Not an exponent: the `e` starts the next token
+      *chars = old_chars;
+    }
+  }
+  SyntaxKind::TokenNumLit
+}
+
+/// Lexes a Flash 4 target path with its variable, if the input starts with one.
+///
+/// The path is made of identifiers, `.` and `..` separated by slashes. It must
+/// contain at least one slash: `a:b` is not a target path.
+fn next_target_path(input: &str) -> Option<LexerToken> {
+  let mut chars = input.chars();
+  let mut has_slash = false;
+  let mut after_segment = false;
+  loop {
+    match chars.next()? {
+      '/' => {
+        // Comments take precedence
+        if chars.as_str().starts_with(|c| c == '/' || c == '*') {
+          return None;
         }
-        Some(c) if is_id_continue(c) => end_id(chars),
-        _ => SyntaxKind::TokenIdent,
-      },
-      Some(c) if is_id_continue(c) => end_id(chars),
-      _ => SyntaxKind::TokenIdent,
-    },
-    _ => unimplemented!(),
+        has_slash = true;
+        after_segment = false;
+      }
+      '.' if !after_segment => {
+        eat_char(&mut chars, '.');
+        after_segment = true;
+      }
+      c if is_id_start(c) && !after_segment => {
+        end_id(&mut chars);
+        after_segment = true;
+      }
+      ':' => break,
+      _ => return None,
+    }
+  }
+  if !has_slash || !chars.next().map_or(false, is_id_start) {
+    return None;
   }
+  end_id(&mut chars);
+  let len = input.len() - chars.as_str().len();
+  Some(LexerToken {
+    kind: SyntaxKind::TokenTargetPath,
+    text: SmolStr::new(&input[..len]),
+  })
+}
+
+/// Reads a decompiler pseudo-instruction such as `§§push`.
+///
+/// Returns `None` if the input does not start with a known pseudo-instruction.
+fn next_pseudo_instruction(input: &str) -> Option<LexerToken> {
+  let name_start = "§§".len();
+  if !input.starts_with("§§") {
+    return None;
+  }
+  let mut chars = input[name_start..].chars();
+  end_id(&mut chars);
+  let len = input.len() - chars.as_str().len();
+  let kind = match &input[name_start..len] {
+    "push" => SyntaxKind::TokenPush,
+    "pop" => SyntaxKind::TokenPop,
+    "goto" => SyntaxKind::TokenGoto,
+    _ => return None,
+  };
+  Some(LexerToken {
+    kind,
+    text: SmolStr::new(&input[..len]),
+  })
+}
+
+/// Consumes a preprocessor directive.
+/// The starting `#` must already be consumed.
+///
+/// `#include` is the only supported directive, anything else is an error.
+fn end_directive(chars: &mut Chars) -> SyntaxKind {
+  let mut directive = chars.clone();
+  end_id(&mut directive);
+  let name = &chars.as_str()[..chars.as_str().len() - directive.as_str().len()];
+  if name == "include" {
+    *chars = directive;
+    SyntaxKind::TokenInclude
+  } else {
+    SyntaxKind::TokenError
+  }
+}
+
+/// Consumes an identifier or keyword.
+/// The first character must already be consumed, `input` starts at this character.
+fn end_id_or_keyword(input: &str, chars: &mut Chars) -> SyntaxKind {
+  end_id(chars);
+  let id = &input[..input.len() - chars.as_str().len()];
+  keyword(id).unwrap_or(SyntaxKind::TokenIdent)
+}
+
+/// Returns the keyword kind corresponding to an identifier name, if any.
+fn keyword(id: &str) -> Option<SyntaxKind> {
+  let kind = match id {
+    "break" => SyntaxKind::TokenBreak,
+    "case" => SyntaxKind::TokenCase,
+    "catch" => SyntaxKind::TokenCatch,
+    "class" => SyntaxKind::TokenClass,
+    "continue" => SyntaxKind::TokenContinue,
+    "default" => SyntaxKind::TokenDefault,
+    "delete" => SyntaxKind::TokenDelete,
+    "do" => SyntaxKind::TokenDo,
+    "dynamic" => SyntaxKind::TokenDynamic,
+    "else" => SyntaxKind::TokenElse,
+    "extends" => SyntaxKind::TokenExtends,
+    "false" => SyntaxKind::TokenFalse,
+    "finally" => SyntaxKind::TokenFinally,
+    "for" => SyntaxKind::TokenFor,
+    "function" => SyntaxKind::TokenFunction,
+    "if" => SyntaxKind::TokenIf,
+    "ifFrameLoaded" => SyntaxKind::TokenIfFrameLoaded,
+    "implements" => SyntaxKind::TokenImplements,
+    "import" => SyntaxKind::TokenImport,
+    "in" => SyntaxKind::TokenIn,
+    "instanceof" => SyntaxKind::TokenInstanceOf,
+    "interface" => SyntaxKind::TokenInterface,
+    "intrinsic" => SyntaxKind::TokenIntrinsic,
+    "null" => SyntaxKind::TokenNull,
+    "private" => SyntaxKind::TokenPrivate,
+    "public" => SyntaxKind::TokenPublic,
+    "return" => SyntaxKind::TokenReturn,
+    "static" => SyntaxKind::TokenStatic,
+    "super" => SyntaxKind::TokenSuper,
+    "switch" => SyntaxKind::TokenSwitch,
+    "tellTarget" => SyntaxKind::TokenTellTarget,
+    "this" => SyntaxKind::TokenThis,
+    "throw" => SyntaxKind::TokenThrow,
+    "true" => SyntaxKind::TokenTrue,
+    "try" => SyntaxKind::TokenTry,
+    "typeof" => SyntaxKind::TokenTypeOf,
+    "var" => SyntaxKind::TokenVar,
+    "void" => SyntaxKind::TokenVoid,
+    "while" => SyntaxKind::TokenWhile,
+    "with" => SyntaxKind::TokenWith,
+    _ => return None,
+  };
+  Some(kind)
 }
 
 /// Ends an identifier
-fn end_id(chars: &mut Chars) -> SyntaxKind {
+fn end_id(chars: &mut Chars) {
   loop {
     let old_chars = chars.clone();
     match chars.next() {
@@ -184,7 +460,6 @@ fn end_id(chars: &mut Chars) -> SyntaxKind {
       }
     }
   }
-  SyntaxKind::TokenIdent
 }
 
 fn end_whitespace(first: char, chars: &mut Chars) -> SyntaxKind {
@@ -231,8 +506,33 @@ fn end_line_terminator_sequence(first: char, chars: &mut Chars) {
   }
 }
 
+/// Consumes the next char if it is equal to `expected`.
+fn eat_char(chars: &mut Chars, expected: char) -> bool {
+  if chars.as_str().starts_with(expected) {
+    chars.next();
+    true
+  } else {
+    false
+  }
+}
+
+/// Consumes chars while they match the predicate.
+fn eat_while(chars: &mut Chars, predicate: fn(char) -> bool) {
+  while chars.as_str().starts_with(predicate) {
+    chars.next();
+  }
+}
+
 fn is_whitespace(c: char) -> bool {
-  c == '\n' || c == '\r' || c == ' '
+  c == '\n' || c == '\r' || c == ' ' || c == '\t'
+}
+
+fn is_dec_digit(c: char) -> bool {
+  c.is_ascii_digit()
+}
+
+fn is_hex_digit(c: char) -> bool {
+  c.is_ascii_hexdigit()
 }
 
 fn is_line_terminator_sequence_start(c: char) -> bool {
@@ -255,7 +555,7 @@ fn is_id_continue(c: char) -> bool {
 
 #[cfg(test)]
 mod lexer_tests {
-  use crate::lexer::{lex, LexerToken};
+  use crate::lexer::{lex, Lexer, LexerToken};
   use crate::types::syntax::SyntaxKind;
   use ::test_generator::test_resources;
   use std::path::Path;
@@ -278,7 +578,25 @@ mod lexer_tests {
     let as2_path = path.join("main.as2");
     let as2_text: String = ::std::fs::read_to_string(as2_path).expect("Failed to read input");
 
-    let tokens = lex(&as2_text);
+    // Samples in the `decompiler` group contain pseudo-instructions
+    let group = path
+      .parent()
+      .and_then(|parent| parent.file_name())
+      .and_then(|group| group.to_str())
+      .expect("Failed to retrieve sample group");
+    let tokens: Vec<LexerToken> = match group {
+      "decompiler" => Lexer::new(&as2_text).with_decompiler_mode().collect(),
+      _ => lex(&as2_text),
+    };
+
+    let lexed_text: String = tokens.iter().map(|token| token.text.as_str()).collect();
+    assert_eq!(&lexed_text, &as2_text);
+    assert!(tokens.iter().all(|token| token.kind != SyntaxKind::TokenError));
+  }
+
+  #[test]
+  fn test_lex_hello_world() {
+    let tokens = lex("trace(\"Hello, World!\");\n");
 
     let expected = vec![
       LexerToken {
@@ -309,4 +627,89 @@ mod lexer_tests {
 
     assert_eq!(&tokens, &expected);
   }
+
+  #[test]
+  fn test_lex_keywords() {
+    let kinds: Vec<SyntaxKind> = lex("this thistle try x").iter().map(|token| token.kind).collect();
+    let expected = vec![
+      SyntaxKind::TokenThis,
+      SyntaxKind::TokenUnilineWhitespace,
+      SyntaxKind::TokenIdent,
+      SyntaxKind::TokenUnilineWhitespace,
+      SyntaxKind::TokenTry,
+      SyntaxKind::TokenUnilineWhitespace,
+      SyntaxKind::TokenIdent,
+    ];
+    assert_eq!(kinds, expected);
+  }
+
+  #[test]
+  fn test_lex_flash4_target_paths() {
+    let kinds = |text: &str| -> Vec<(SyntaxKind, String)> {
+      Lexer::flash4(text)
+        .filter(|token| !token.kind.is_trivia())
+        .map(|token| (token.kind, token.text.to_string()))
+        .collect()
+    };
+
+    assert_eq!(
+      kinds("/clip/sub:score ../:counter _root/menu:visible"),
+      vec![
+        (SyntaxKind::TokenTargetPath, "/clip/sub:score".to_string()),
+        (SyntaxKind::TokenTargetPath, "../:counter".to_string()),
+        (SyntaxKind::TokenTargetPath, "_root/menu:visible".to_string()),
+      ]
+    );
+    assert_eq!(
+      kinds("a / b:c"),
+      vec![
+        (SyntaxKind::TokenIdent, "a".to_string()),
+        (SyntaxKind::TokenSlash, "/".to_string()),
+        (SyntaxKind::TokenIdent, "b".to_string()),
+        (SyntaxKind::TokenColon, ":".to_string()),
+        (SyntaxKind::TokenIdent, "c".to_string()),
+      ]
+    );
+  }
+
+  #[test]
+  fn test_lex_decompiler_pseudo_instructions() {
+    let kinds = |lexer: Lexer| -> Vec<(SyntaxKind, String)> {
+      lexer
+        .filter(|token| !token.kind.is_trivia())
+        .map(|token| (token.kind, token.text.to_string()))
+        .collect()
+    };
+
+    assert_eq!(
+      kinds(Lexer::new("§§push(§§pop());").with_decompiler_mode()),
+      vec![
+        (SyntaxKind::TokenPush, "§§push".to_string()),
+        (SyntaxKind::TokenOpenParen, "(".to_string()),
+        (SyntaxKind::TokenPop, "§§pop".to_string()),
+        (SyntaxKind::TokenOpenParen, "(".to_string()),
+        (SyntaxKind::TokenCloseParen, ")".to_string()),
+        (SyntaxKind::TokenCloseParen, ")".to_string()),
+        (SyntaxKind::TokenSemicolon, ";".to_string()),
+      ]
+    );
+    assert_eq!(
+      kinds(Lexer::flash4("§§goto /a:b §§dup").with_decompiler_mode()),
+      vec![
+        (SyntaxKind::TokenGoto, "§§goto".to_string()),
+        (SyntaxKind::TokenTargetPath, "/a:b".to_string()),
+        (SyntaxKind::TokenError, "§".to_string()),
+        (SyntaxKind::TokenError, "§".to_string()),
+        (SyntaxKind::TokenIdent, "dup".to_string()),
+      ]
+    );
+    assert_eq!(
+      kinds(Lexer::new("§§pop")),
+      vec![
+        (SyntaxKind::TokenError, "§".to_string()),
+        (SyntaxKind::TokenError, "§".to_string()),
+        (SyntaxKind::TokenIdent, "pop".to_string()),
+      ]
+    );
+  }
 }
@@ -1,13 +1,15 @@
 #![cfg_attr(feature = "gat", allow(incomplete_features), feature(generic_associated_types))]
 
+pub mod event;
+pub mod include;
 pub mod lexer;
+pub mod lower;
 pub mod parser;
 pub mod types;
 
 #[cfg(test)]
 mod parser_tests {
-  use crate::parser::parse_script;
-  use crate::types::owned;
+  use crate::parser::{parse_script, parse_script_fast, ParseOptions};
   use ::test_generator::test_resources;
   use std::path::Path;
 
@@ -29,11 +31,8 @@ mod parser_tests {
     let as2_path = path.join("main.as2");
     let as2_text: String = ::std::fs::read_to_string(as2_path).expect("Failed to read input");
 
-    let actual_tree = parse_script(&as2_text);
-    let expected_tree: owned::StrLit = owned::StrLit {
-      loc: (),
-      value: String::new(),
-    };
+    let actual_tree = parse_script_fast(&as2_text, &ParseOptions::default());
+    let expected_tree = parse_script(&as2_text, &ParseOptions::default());
 
     assert_eq!(actual_tree, expected_tree);
   }
@@ -0,0 +1,2093 @@
+//! Lowering to the owned AST
+//!
+//! `OwnedSink` builds the nodes of `types::owned` from parse events. It is used
+//! both to lower a syntax tree, by replaying the tree as events, and to parse
+//! straight to the owned AST: the two results are always identical.
+//!
+//! The owned AST has no trivia and no recovery nodes. Invalid or missing
+//! statements and expressions are lowered to their `SyntaxError` variant and
+//! missing names to empty strings. Unknown handler events and invalid class
+//! members are dropped. The statements of expanded `#include` directives are
+//! inlined. Parentheses are dropped.
+
+use crate::event::TreeSink;
+use crate::parser::{register_index, SyntaxError};
+use crate::types::ast::traits::{
+  Access, AssignOp, BinOp, ButtonEvent, ClipEvent, MethodKind, Modifiers, UnaryOp, UpdateOp,
+};
+use crate::types::owned;
+use crate::types::syntax::{unescape_string, SyntaxKind, SyntaxNode};
+use rowan::{NodeOrToken, SmolStr, WalkEvent};
+
+/// Lowers a syntax tree to an owned script.
+///
+/// The statements of a root node (`NodeScript`, `NodeClassFile`, etc.) become
+/// the statements of the script. Any other statement node is lowered to a
+/// script containing only this statement.
+pub fn lower_script(syntax: &SyntaxNode) -> owned::Script {
+  let mut sink = OwnedSink::default();
+  for event in syntax.preorder_with_tokens() {
+    match event {
+      WalkEvent::Enter(NodeOrToken::Node(node)) => sink.start_node(node.kind()),
+      WalkEvent::Enter(NodeOrToken::Token(token)) => sink.token(token.kind(), token.text().clone()),
+      WalkEvent::Leave(NodeOrToken::Node(_)) => sink.finish_node(),
+      WalkEvent::Leave(NodeOrToken::Token(_)) => {}
+    }
+  }
+  sink.finish()
+}
+
+/// Sink building the owned AST
+///
+/// Trivia and errors are ignored. Each node is lowered when it is finished,
+/// from the lowered elements of its children.
+#[derive(Debug, Default)]
+pub struct OwnedSink {
+  /// Lowered children of the unfinished nodes, after the lowered roots
+  elements: Vec<Element>,
+  /// Kind of each unfinished node, with the index of its first child in `elements`
+  nodes: Vec<(SyntaxKind, usize)>,
+}
+
+impl OwnedSink {
+  /// Returns the script made of the lowered root statements.
+  pub fn finish(self) -> owned::Script {
+    debug_assert!(self.nodes.is_empty());
+    owned::Script {
+      loc: (),
+      stmts: stmts(self.elements),
+    }
+  }
+}
+
+impl TreeSink for OwnedSink {
+  fn start_node(&mut self, kind: SyntaxKind) {
+    self.nodes.push((kind, self.elements.len()));
+  }
+
+  fn token(&mut self, kind: SyntaxKind, text: SmolStr) {
+    if !kind.is_trivia() {
+      self.elements.push(Element::Token(kind, text));
+    }
+  }
+
+  fn finish_node(&mut self) {
+    let (kind, start) = self.nodes.pop().expect("Finished node must be started");
+    let element = lower(kind, self.elements.drain(start..));
+    self.elements.push(element);
+  }
+
+  fn error(&mut self, _error: SyntaxError) {}
+}
+
+/// Lowered node or token
+#[derive(Debug)]
+enum Element {
+  Token(SyntaxKind, SmolStr),
+  /// `NodeIdent`: an expression, a pattern or a name depending on its parent
+  Ident(String),
+  Expr(owned::Expr),
+  Stmt(owned::Stmt),
+  /// Root statements, or statements of an `#include` directive
+  Stmts(Vec<owned::Stmt>),
+  Block(owned::BlockStmt),
+  VarDecl(owned::VarDecl),
+  VarDeclarator(owned::VarDeclarator),
+  Case(owned::CaseClause),
+  Catch(owned::CatchClause),
+  Finally(owned::BlockStmt),
+  /// Event name and `keyPress` key literal
+  Event(Option<SmolStr>, Option<SmolStr>),
+  /// Segments of a qualified name
+  Name(Vec<String>),
+  /// Type annotation, or `extends` clause
+  TypeRef(owned::TypeRef),
+  Implements(Vec<owned::TypeRef>),
+  Member(owned::ClassMember),
+  Params(Vec<owned::Param>),
+  Param(owned::Param),
+  Metadata(owned::Metadata),
+  MetadataArg(owned::MetadataArg),
+  Error,
+}
+
+impl Element {
+  /// Returns `true` if the element may be an expression.
+  fn is_expr(&self) -> bool {
+    match self {
+      Element::Expr(_) | Element::Ident(_) | Element::Error => true,
+      _ => false,
+    }
+  }
+
+  /// Returns `true` if the element may be a statement.
+  fn is_stmt(&self) -> bool {
+    match self {
+      Element::Stmt(_) | Element::Stmts(_) | Element::Block(_) | Element::VarDecl(_) | Element::Error => true,
+      _ => false,
+    }
+  }
+
+  fn into_expr(self) -> Option<owned::Expr> {
+    match self {
+      Element::Expr(expr) => Some(expr),
+      Element::Ident(name) => Some(owned::Expr::Ident(owned::IdentExpr { loc: (), name })),
+      Element::Error => Some(owned::Expr::SyntaxError),
+      _ => None,
+    }
+  }
+
+  /// Converts the element to a single statement.
+  ///
+  /// Statements of an `#include` directive are wrapped in a block.
+  fn into_stmt(self) -> Option<owned::Stmt> {
+    match self {
+      Element::Stmt(stmt) => Some(stmt),
+      Element::Stmts(stmts) => Some(owned::Stmt::Block(owned::BlockStmt { loc: (), stmts })),
+      Element::Block(block) => Some(owned::Stmt::Block(block)),
+      Element::VarDecl(decl) => Some(owned::Stmt::VarDecl(decl)),
+      Element::Error => Some(owned::Stmt::SyntaxError),
+      _ => None,
+    }
+  }
+}
+
+/// Lowers a node from the lowered elements of its children.
+fn lower<I: Iterator<Item = Element>>(kind: SyntaxKind, elements: I) -> Element {
+  use SyntaxKind::*;
+  match kind {
+    NodeScript | NodeClassFile | NodeInstanceScript | NodeStmtRoot | NodeInclude => Element::Stmts(stmts(elements)),
+    NodeExprRoot => Element::Stmts(elements.filter_map(Element::into_expr).map(expr_stmt).collect()),
+    NodeBlock => Element::Block(block(elements)),
+    NodeEmptyStmt => Element::Stmt(owned::Stmt::Empty(owned::EmptyStmt { loc: () })),
+    NodeStatement => Element::Stmt(expr_stmt(first_expr(elements))),
+    NodeIf => Element::Stmt(if_stmt(elements)),
+    NodeWhile => {
+      let (test, body) = head_body(elements);
+      Element::Stmt(owned::Stmt::While(owned::WhileStmt {
+        loc: (),
+        test: expr_or_error(test),
+        body: stmt_or_error(body),
+      }))
+    }
+    NodeDoWhile => Element::Stmt(do_while_stmt(elements)),
+    NodeFor => Element::Stmt(for_stmt(elements)),
+    NodeForIn => Element::Stmt(for_in_stmt(elements)),
+    NodeSwitch => Element::Stmt(switch_stmt(elements)),
+    NodeCase => Element::Case(case_clause(elements)),
+    NodeTry => Element::Stmt(try_stmt(elements)),
+    NodeCatch => Element::Catch(catch_clause(elements)),
+    NodeFinally => Element::Finally(first_block(elements)),
+    NodeThrow => Element::Stmt(owned::Stmt::Throw(owned::ThrowStmt {
+      loc: (),
+      value: Box::new(first_expr(elements)),
+    })),
+    NodeReturn => Element::Stmt(owned::Stmt::Return(owned::ReturnStmt {
+      loc: (),
+      value: elements.filter_map(Element::into_expr).next().map(Box::new),
+    })),
+    NodeBreak => Element::Stmt(owned::Stmt::Break(owned::BreakStmt {
+      loc: (),
+      label: first_ident(elements),
+    })),
+    NodeContinue => Element::Stmt(owned::Stmt::Continue(owned::ContinueStmt {
+      loc: (),
+      label: first_ident(elements),
+    })),
+    NodeLabelled => Element::Stmt(labelled_stmt(elements)),
+    NodePush => Element::Stmt(owned::Stmt::Push(owned::PushStmt {
+      loc: (),
+      value: Box::new(first_expr(elements)),
+    })),
+    NodeGoto => Element::Stmt(owned::Stmt::Goto(owned::GotoStmt {
+      loc: (),
+      label: first_ident(elements).unwrap_or_default(),
+    })),
+    NodeWith => {
+      let (object, body) = head_body(elements);
+      Element::Stmt(owned::Stmt::With(owned::WithStmt {
+        loc: (),
+        object: expr_or_error(object),
+        body: stmt_or_error(body),
+      }))
+    }
+    NodeTellTarget => {
+      let (scene, target, body) = scoped_stmt(elements);
+      Element::Stmt(owned::Stmt::TellTarget(owned::TellTargetStmt {
+        loc: (),
+        scene,
+        target,
+        body,
+      }))
+    }
+    NodeIfFrameLoaded => {
+      let (scene, frame, body) = scoped_stmt(elements);
+      Element::Stmt(owned::Stmt::IfFrameLoaded(owned::IfFrameLoadedStmt {
+        loc: (),
+        scene,
+        frame,
+        body,
+      }))
+    }
+    NodeOnHandler => {
+      let (events, body) = event_handler(elements);
+      Element::Stmt(owned::Stmt::OnHandler(owned::OnHandler {
+        loc: (),
+        events: events
+          .iter()
+          .filter_map(|(name, key)| button_event(name, key))
+          .collect(),
+        body,
+      }))
+    }
+    NodeClipEventHandler => {
+      let (events, body) = event_handler(elements);
+      Element::Stmt(owned::Stmt::ClipEventHandler(owned::ClipEventHandler {
+        loc: (),
+        events: events
+          .iter()
+          .filter_map(|(name, _)| ClipEvent::from_name(name))
+          .collect(),
+        body,
+      }))
+    }
+    NodeEvent => {
+      let mut name = None;
+      let mut key = None;
+      for element in elements {
+        match element {
+          Element::Token(TokenIdent, text) => name = name.or(Some(text)),
+          Element::Token(TokenStrLit, text) => key = key.or(Some(text)),
+          _ => {}
+        }
+      }
+      Element::Event(name, key)
+    }
+    NodeClass => Element::Stmt(class_decl(elements)),
+    NodeInterface => Element::Stmt(interface_decl(elements)),
+    NodeExtends => Element::TypeRef(first_type_ref(elements)),
+    NodeImplements => Element::Implements(
+      elements
+        .filter_map(|element| match element {
+          Element::Name(segments) => Some(type_ref(segments)),
+          _ => None,
+        })
+        .collect(),
+    ),
+    NodeQualifiedName => Element::Name(
+      elements
+        .filter_map(|element| match element {
+          Element::Token(TokenIdent, text) | Element::Token(TokenStar, text) => Some(text.to_string()),
+          _ => None,
+        })
+        .collect(),
+    ),
+    NodeTypeAnnotation => Element::TypeRef(first_type_ref(elements)),
+    NodeField => Element::Member(field_decl(elements)),
+    NodeMethod => Element::Member(method_decl(elements)),
+    NodeParams => Element::Params(
+      elements
+        .filter_map(|element| match element {
+          Element::Param(param) => Some(param),
+          _ => None,
+        })
+        .collect(),
+    ),
+    NodeParam => {
+      let mut name = None;
+      let mut type_annotation = None;
+      for element in elements {
+        match element {
+          Element::Ident(ident) => name = name.or(Some(ident)),
+          Element::TypeRef(type_ref) => type_annotation = type_annotation.or(Some(type_ref)),
+          _ => {}
+        }
+      }
+      Element::Param(owned::Param {
+        loc: (),
+        name: name.unwrap_or_default(),
+        type_annotation,
+      })
+    }
+    NodeImport => Element::Stmt(import_decl(elements)),
+    NodeMetadata => Element::Metadata(metadata(elements)),
+    NodeMetadataArg => Element::MetadataArg(metadata_arg(elements)),
+    NodeVarDecl => Element::VarDecl(owned::VarDecl {
+      loc: (),
+      declarators: declarators(elements),
+    }),
+    NodeVarDeclarator => Element::VarDeclarator(var_declarator(elements)),
+    NodeIdent => Element::Ident(first_token_text(elements).unwrap_or_default()),
+    NodeNumLit => Element::Expr(owned::Expr::NumLit(owned::NumLit {
+      loc: (),
+      value: num_value(&first_token_text(elements).unwrap_or_default()),
+    })),
+    NodeStrLit => match first_token_text(elements)
+      .as_ref()
+      .and_then(|text| unescape_string(text))
+    {
+      Some(value) => Element::Expr(owned::Expr::StrLit(owned::StrLit { loc: (), value })),
+      None => Element::Error,
+    },
+    NodeTargetPath => Element::Expr(owned::Expr::TargetPath(target_path(
+      &first_token_text(elements).unwrap_or_default(),
+    ))),
+    NodeBoolLit => Element::Expr(owned::Expr::BoolLit(owned::BoolLit {
+      loc: (),
+      value: first_token_text(elements).map_or(false, |text| text == "true"),
+    })),
+    NodeNullLit => Element::Expr(owned::Expr::NullLit(owned::NullLit { loc: () })),
+    NodeUndefinedLit => Element::Expr(owned::Expr::UndefinedLit(owned::UndefinedLit { loc: () })),
+    NodeNewlineLit => Element::Expr(owned::Expr::NewlineLit(owned::NewlineLit { loc: () })),
+    NodeThisExpr => Element::Expr(owned::Expr::This(owned::ThisExpr { loc: () })),
+    NodeSuperExpr => Element::Expr(owned::Expr::Super(owned::SuperExpr { loc: () })),
+    NodeParenExpr => Element::Expr(first_expr(elements)),
+    NodeRegister => Element::Expr(owned::Expr::Register(owned::Register {
+      loc: (),
+      index: first_token_text(elements)
+        .and_then(|text| register_index(&text.to_ascii_lowercase()))
+        .unwrap_or_default(),
+    })),
+    NodePopExpr => Element::Expr(owned::Expr::Pop(owned::PopExpr { loc: () })),
+    NodeCall => Element::Expr(call_expr(elements)),
+    NodeMember => Element::Expr(member_expr(elements)),
+    NodeUnaryExpr => Element::Expr(unary_expr(elements)),
+    NodeUpdateExpr => Element::Expr(update_expr(elements)),
+    NodeBinExpr => Element::Expr(bin_expr(elements)),
+    NodeAssignExpr => Element::Expr(assign_expr(elements)),
+    NodeCondExpr => Element::Expr(cond_expr(elements)),
+    NodeSeqExpr => Element::Expr(owned::Expr::Seq(owned::SeqExpr {
+      loc: (),
+      exprs: elements.filter_map(Element::into_expr).collect(),
+    })),
+    _ => Element::Error,
+  }
+}
+
+/// Collects statements, inlining the statements of `#include` directives.
+fn stmts<I: IntoIterator<Item = Element>>(elements: I) -> Vec<owned::Stmt> {
+  let mut stmts: Vec<owned::Stmt> = Vec::new();
+  for element in elements {
+    match element {
+      Element::Stmts(inner) => stmts.extend(inner),
+      element => stmts.extend(element.into_stmt()),
+    }
+  }
+  stmts
+}
+
+fn block<I: Iterator<Item = Element>>(elements: I) -> owned::BlockStmt {
+  owned::BlockStmt {
+    loc: (),
+    stmts: stmts(elements),
+  }
+}
+
+fn first_block<I: Iterator<Item = Element>>(elements: I) -> owned::BlockStmt {
+  elements
+    .filter_map(|element| match element {
+      Element::Block(block) => Some(block),
+      _ => None,
+    })
+    .next()
+    .unwrap_or_else(empty_block)
+}
+
+fn empty_block() -> owned::BlockStmt {
+  owned::BlockStmt {
+    loc: (),
+    stmts: Vec::new(),
+  }
+}
+
+fn first_expr<I: Iterator<Item = Element>>(elements: I) -> owned::Expr {
+  elements
+    .filter_map(Element::into_expr)
+    .next()
+    .unwrap_or(owned::Expr::SyntaxError)
+}
+
+fn first_ident<I: Iterator<Item = Element>>(elements: I) -> Option<String> {
+  elements
+    .filter_map(|element| match element {
+      Element::Ident(name) => Some(name),
+      _ => None,
+    })
+    .next()
+}
+
+fn first_token_text<I: Iterator<Item = Element>>(elements: I) -> Option<String> {
+  elements
+    .filter_map(|element| match element {
+      Element::Token(_, text) => Some(text.to_string()),
+      _ => None,
+    })
+    .next()
+}
+
+fn first_type_ref<I: Iterator<Item = Element>>(elements: I) -> owned::TypeRef {
+  elements
+    .filter_map(|element| match element {
+      Element::Name(segments) => Some(type_ref(segments)),
+      _ => None,
+    })
+    .next()
+    .unwrap_or_else(|| type_ref(Vec::new()))
+}
+
+fn expr_or_error(expr: Option<owned::Expr>) -> Box<owned::Expr> {
+  Box::new(expr.unwrap_or(owned::Expr::SyntaxError))
+}
+
+fn stmt_or_error(stmt: Option<owned::Stmt>) -> Box<owned::Stmt> {
+  Box::new(stmt.unwrap_or(owned::Stmt::SyntaxError))
+}
+
+fn expr_stmt(expr: owned::Expr) -> owned::Stmt {
+  owned::Stmt::Expr(owned::ExprStmt {
+    loc: (),
+    expr: Box::new(expr),
+  })
+}
+
+fn if_stmt<I: Iterator<Item = Element>>(elements: I) -> owned::Stmt {
+  let mut test: Option<owned::Expr> = None;
+  let mut consequent: Option<owned::Stmt> = None;
+  let mut alternate: Option<owned::Stmt> = None;
+  let mut in_body = false;
+  let mut in_else = false;
+  for element in elements {
+    match element {
+      Element::Token(SyntaxKind::TokenCloseParen, _) => in_body = true,
+      Element::Token(SyntaxKind::TokenElse, _) => in_else = true,
+      Element::Token(..) => {}
+      element if !in_body && test.is_none() && element.is_expr() => test = element.into_expr(),
+      element if in_else => alternate = alternate.or_else(|| element.into_stmt()),
+      element => consequent = consequent.or_else(|| element.into_stmt()),
+    }
+  }
+  owned::Stmt::If(owned::IfStmt {
+    loc: (),
+    test: expr_or_error(test),
+    consequent: stmt_or_error(consequent),
+    alternate: alternate.map(Box::new),
+  })
+}
+
+/// Lowers the children of a statement with a parenthesized expression followed
+/// by a body: `while (test) body` or `with (object) body`.
+fn head_body<I: Iterator<Item = Element>>(elements: I) -> (Option<owned::Expr>, Option<owned::Stmt>) {
+  let mut head: Option<owned::Expr> = None;
+  let mut body: Option<owned::Stmt> = None;
+  let mut in_body = false;
+  for element in elements {
+    match element {
+      Element::Token(SyntaxKind::TokenCloseParen, _) => in_body = true,
+      Element::Token(..) => {}
+      element if !in_body && head.is_none() && element.is_expr() => head = element.into_expr(),
+      element => body = body.or_else(|| element.into_stmt()),
+    }
+  }
+  (head, body)
+}
+
+fn do_while_stmt<I: Iterator<Item = Element>>(elements: I) -> owned::Stmt {
+  let mut body: Option<owned::Stmt> = None;
+  let mut test: Option<owned::Expr> = None;
+  let mut in_test = false;
+  for element in elements {
+    match element {
+      Element::Token(SyntaxKind::TokenWhile, _) => in_test = true,
+      Element::Token(..) => {}
+      element if !in_test && body.is_none() && element.is_stmt() => body = element.into_stmt(),
+      element => test = test.or_else(|| element.into_expr()),
+    }
+  }
+  owned::Stmt::DoWhile(owned::DoWhileStmt {
+    loc: (),
+    body: stmt_or_error(body),
+    test: expr_or_error(test),
+  })
+}
+
+/// Lowers a `for` loop.
+///
+/// Missing semicolons are tolerated: the expressions of the head fill the
+/// initializer, test and update in order.
+fn for_stmt<I: Iterator<Item = Element>>(elements: I) -> owned::Stmt {
+  let mut init: Option<owned::ForInit> = None;
+  let mut test: Option<owned::Expr> = None;
+  let mut update: Option<owned::Expr> = None;
+  let mut body: Option<owned::Stmt> = None;
+  // Index of the next part: initializer, test, update or body
+  let mut part: usize = 0;
+  let mut semicolons: usize = 0;
+  for element in elements {
+    match element {
+      Element::Token(SyntaxKind::TokenSemicolon, _) => {
+        semicolons += 1;
+        part = part.max(semicolons);
+      }
+      Element::Token(SyntaxKind::TokenCloseParen, _) => part = 3,
+      Element::Token(..) => {}
+      Element::VarDecl(decl) if part == 0 => {
+        init = Some(owned::ForInit::VarDecl(decl));
+        part = 1;
+      }
+      element if part < 3 && element.is_expr() => {
+        let expr = element.into_expr();
+        match part {
+          0 => init = expr.map(|expr| owned::ForInit::Expr(Box::new(expr))),
+          1 => test = expr,
+          _ => update = expr,
+        }
+        part += 1;
+      }
+      element => body = body.or_else(|| element.into_stmt()),
+    }
+  }
+  owned::Stmt::For(owned::ForStmt {
+    loc: (),
+    init,
+    test: test.map(Box::new),
+    update: update.map(Box::new),
+    body: stmt_or_error(body),
+  })
+}
+
+fn for_in_stmt<I: Iterator<Item = Element>>(elements: I) -> owned::Stmt {
+  let mut target: Option<owned::ForInTarget> = None;
+  let mut object: Option<owned::Expr> = None;
+  let mut body: Option<owned::Stmt> = None;
+  // Index of the next part: target, object or body
+  let mut part: usize = 0;
+  for element in elements {
+    match element {
+      Element::Token(SyntaxKind::TokenIn, _) => part = part.max(1),
+      Element::Token(SyntaxKind::TokenCloseParen, _) => part = 2,
+      Element::Token(..) => {}
+      Element::VarDecl(decl) if part == 0 => {
+        target = Some(owned::ForInTarget::VarDecl(decl));
+        part = 1;
+      }
+      element if part < 2 && element.is_expr() => {
+        let expr = element.into_expr();
+        if part == 0 {
+          target = expr.map(|expr| owned::ForInTarget::Pat(Box::new(pat(expr))));
+        } else {
+          object = expr;
+        }
+        part += 1;
+      }
+      element => body = body.or_else(|| element.into_stmt()),
+    }
+  }
+  owned::Stmt::ForIn(owned::ForInStmt {
+    loc: (),
+    target: target.unwrap_or_else(|| owned::ForInTarget::Pat(Box::new(owned::Pat::SyntaxError))),
+    object: expr_or_error(object),
+    body: stmt_or_error(body),
+  })
+}
+
+fn switch_stmt<I: Iterator<Item = Element>>(elements: I) -> owned::Stmt {
+  let mut discriminant: Option<owned::Expr> = None;
+  let mut clauses: Vec<owned::CaseClause> = Vec::new();
+  for element in elements {
+    match element {
+      Element::Case(clause) => clauses.push(clause),
+      element if discriminant.is_none() && element.is_expr() => discriminant = element.into_expr(),
+      _ => {}
+    }
+  }
+  owned::Stmt::Switch(owned::SwitchStmt {
+    loc: (),
+    discriminant: expr_or_error(discriminant),
+    clauses,
+  })
+}
+
+fn case_clause<I: Iterator<Item = Element>>(elements: I) -> owned::CaseClause {
+  let mut is_case = false;
+  let mut test: Option<owned::Expr> = None;
+  let mut body: Vec<owned::Stmt> = Vec::new();
+  let mut in_body = false;
+  for element in elements {
+    match element {
+      Element::Token(SyntaxKind::TokenCase, _) => is_case = true,
+      Element::Token(SyntaxKind::TokenColon, _) => in_body = true,
+      Element::Token(..) => {}
+      Element::Stmts(stmts) => body.extend(stmts),
+      element if is_case && !in_body && test.is_none() && element.is_expr() => test = element.into_expr(),
+      element => body.extend(element.into_stmt()),
+    }
+  }
+  owned::CaseClause {
+    loc: (),
+    test: if is_case { Some(expr_or_error(test)) } else { None },
+    body,
+  }
+}
+
+fn try_stmt<I: Iterator<Item = Element>>(elements: I) -> owned::Stmt {
+  let mut block: Option<owned::BlockStmt> = None;
+  let mut handlers: Vec<owned::CatchClause> = Vec::new();
+  let mut finalizer: Option<owned::BlockStmt> = None;
+  for element in elements {
+    match element {
+      Element::Block(inner) => block = block.or(Some(inner)),
+      Element::Catch(handler) => handlers.push(handler),
+      Element::Finally(inner) => finalizer = finalizer.or(Some(inner)),
+      _ => {}
+    }
+  }
+  owned::Stmt::Try(owned::TryStmt {
+    loc: (),
+    block: block.unwrap_or_else(empty_block),
+    handlers,
+    finalizer,
+  })
+}
+
+fn catch_clause<I: Iterator<Item = Element>>(elements: I) -> owned::CatchClause {
+  let mut param: Option<String> = None;
+  let mut param_type: Option<owned::TypeRef> = None;
+  let mut body: Option<owned::BlockStmt> = None;
+  for element in elements {
+    match element {
+      Element::Ident(name) => param = param.or(Some(name)),
+      Element::TypeRef(type_ref) => param_type = param_type.or(Some(type_ref)),
+      Element::Block(block) => body = body.or(Some(block)),
+      _ => {}
+    }
+  }
+  owned::CatchClause {
+    loc: (),
+    param: owned::IdentPat {
+      loc: (),
+      name: param.unwrap_or_default(),
+    },
+    param_type,
+    body: body.unwrap_or_else(empty_block),
+  }
+}
+
+fn labelled_stmt<I: Iterator<Item = Element>>(elements: I) -> owned::Stmt {
+  let mut label: Option<String> = None;
+  let mut body: Option<owned::Stmt> = None;
+  for element in elements {
+    match element {
+      Element::Ident(name) if label.is_none() => label = Some(name),
+      element => body = body.or_else(|| element.into_stmt()),
+    }
+  }
+  owned::Stmt::Labelled(owned::LabelledStmt {
+    loc: (),
+    label: label.unwrap_or_default(),
+    body: stmt_or_error(body),
+  })
+}
+
+/// Lowers the children of `tellTarget` or `ifFrameLoaded`.
+///
+/// Returns the optional scene, the target or frame, and the body.
+fn scoped_stmt<I: Iterator<Item = Element>>(
+  elements: I,
+) -> (Option<Box<owned::Expr>>, Box<owned::Expr>, Box<owned::Stmt>) {
+  let mut head: [Option<owned::Expr>; 2] = [None, None];
+  let mut body: Option<owned::Stmt> = None;
+  let mut has_scene = false;
+  // Index of the next part: first value, second value or body
+  let mut part: usize = 0;
+  for element in elements {
+    match element {
+      Element::Token(SyntaxKind::TokenComma, _) => {
+        has_scene = true;
+        part = part.max(1);
+      }
+      Element::Token(SyntaxKind::TokenCloseParen, _) => part = 2,
+      Element::Token(..) => {}
+      element if part < 2 && element.is_expr() => {
+        head[part] = element.into_expr();
+        part += 1;
+      }
+      element => body = body.or_else(|| element.into_stmt()),
+    }
+  }
+  let [first, second] = head;
+  if has_scene {
+    (Some(expr_or_error(first)), expr_or_error(second), stmt_or_error(body))
+  } else {
+    (None, expr_or_error(first), stmt_or_error(body))
+  }
+}
+
+/// Lowers the children of `on` or `onClipEvent`.
+///
+/// Returns the names and keys of the events, and the body.
+fn event_handler<I: Iterator<Item = Element>>(elements: I) -> (Vec<(SmolStr, Option<SmolStr>)>, owned::BlockStmt) {
+  let mut events: Vec<(SmolStr, Option<SmolStr>)> = Vec::new();
+  let mut body: Option<owned::BlockStmt> = None;
+  for element in elements {
+    match element {
+      Element::Event(Some(name), key) => events.push((name, key)),
+      Element::Block(block) => body = body.or(Some(block)),
+      _ => {}
+    }
+  }
+  (events, body.unwrap_or_else(empty_block))
+}
+
+fn button_event(name: &str, key: &Option<SmolStr>) -> Option<ButtonEvent> {
+  match key {
+    // The literal is checked without its quotes, as in the parser
+    Some(key) if name == "keyPress" => ButtonEvent::key_code(&key[1..key.len() - 1]).map(ButtonEvent::KeyPress),
+    _ => ButtonEvent::from_name(name),
+  }
+}
+
+fn class_decl<I: Iterator<Item = Element>>(elements: I) -> owned::Stmt {
+  let mut metadata: Vec<owned::Metadata> = Vec::new();
+  let mut name: Option<owned::TypeRef> = None;
+  let mut is_dynamic = false;
+  let mut is_intrinsic = false;
+  let mut extends: Option<owned::TypeRef> = None;
+  let mut implements: Vec<owned::TypeRef> = Vec::new();
+  let mut members: Vec<owned::ClassMember> = Vec::new();
+  for element in elements {
+    match element {
+      Element::Token(SyntaxKind::TokenDynamic, _) => is_dynamic = true,
+      Element::Token(SyntaxKind::TokenIntrinsic, _) => is_intrinsic = true,
+      Element::Metadata(tag) => metadata.push(tag),
+      Element::Name(segments) => name = name.or_else(|| Some(type_ref(segments))),
+      Element::TypeRef(type_ref) => extends = extends.or(Some(type_ref)),
+      Element::Implements(interfaces) => implements.extend(interfaces),
+      Element::Member(member) => members.push(member),
+      _ => {}
+    }
+  }
+  let name = name.unwrap_or_else(|| type_ref(Vec::new()));
+  // Constructors are the methods named after their class
+  for member in members.iter_mut() {
+    if let owned::ClassMember::Method(method) = member {
+      if method.kind == MethodKind::Method && method.name == name.name {
+        method.kind = MethodKind::Constructor;
+      }
+    }
+  }
+  owned::Stmt::Class(owned::ClassDecl {
+    loc: (),
+    metadata,
+    name,
+    is_dynamic,
+    is_intrinsic,
+    extends,
+    implements,
+    members,
+  })
+}
+
+fn interface_decl<I: Iterator<Item = Element>>(elements: I) -> owned::Stmt {
+  let mut metadata: Vec<owned::Metadata> = Vec::new();
+  let mut name: Option<owned::TypeRef> = None;
+  let mut extends: Option<owned::TypeRef> = None;
+  let mut methods: Vec<owned::MethodDecl> = Vec::new();
+  for element in elements {
+    match element {
+      Element::Metadata(tag) => metadata.push(tag),
+      Element::Name(segments) => name = name.or_else(|| Some(type_ref(segments))),
+      Element::TypeRef(type_ref) => extends = extends.or(Some(type_ref)),
+      Element::Member(owned::ClassMember::Method(method)) => methods.push(method),
+      _ => {}
+    }
+  }
+  owned::Stmt::Interface(owned::InterfaceDecl {
+    loc: (),
+    metadata,
+    name: name.unwrap_or_else(|| type_ref(Vec::new())),
+    extends,
+    methods,
+  })
+}
+
+/// Creates a type reference from the segments of a qualified name.
+fn type_ref(mut segments: Vec<String>) -> owned::TypeRef {
+  let name = segments.pop().unwrap_or_default();
+  owned::TypeRef {
+    loc: (),
+    package: segments,
+    name,
+  }
+}
+
+/// Updates the modifiers of a class member with a modifier keyword.
+pub(crate) fn modifier(modifiers: &mut Modifiers, kind: SyntaxKind) {
+  match kind {
+    SyntaxKind::TokenStatic => modifiers.is_static = true,
+    SyntaxKind::TokenPublic => modifiers.access = Some(Access::Public),
+    SyntaxKind::TokenPrivate => modifiers.access = Some(Access::Private),
+    _ => {}
+  }
+}
+
+fn field_decl<I: Iterator<Item = Element>>(elements: I) -> owned::ClassMember {
+  let mut metadata: Vec<owned::Metadata> = Vec::new();
+  let mut modifiers = Modifiers::default();
+  let mut declarators: Vec<owned::VarDeclarator> = Vec::new();
+  for element in elements {
+    match element {
+      Element::Token(kind, _) => modifier(&mut modifiers, kind),
+      Element::Metadata(tag) => metadata.push(tag),
+      Element::VarDeclarator(declarator) => declarators.push(declarator),
+      _ => {}
+    }
+  }
+  owned::ClassMember::Field(owned::FieldDecl {
+    loc: (),
+    metadata,
+    modifiers,
+    declarators,
+  })
+}
+
+fn method_decl<I: Iterator<Item = Element>>(elements: I) -> owned::ClassMember {
+  let mut metadata: Vec<owned::Metadata> = Vec::new();
+  let mut modifiers = Modifiers::default();
+  let mut kind = MethodKind::Method;
+  let mut name: Option<String> = None;
+  let mut params: Vec<owned::Param> = Vec::new();
+  let mut return_type: Option<owned::TypeRef> = None;
+  let mut body: Option<owned::BlockStmt> = None;
+  for element in elements {
+    match element {
+      Element::Token(SyntaxKind::TokenGet, _) => kind = MethodKind::Get,
+      Element::Token(SyntaxKind::TokenSet, _) => kind = MethodKind::Set,
+      Element::Token(token_kind, _) => modifier(&mut modifiers, token_kind),
+      Element::Metadata(tag) => metadata.push(tag),
+      Element::Ident(ident) => name = name.or(Some(ident)),
+      Element::Params(list) => params = list,
+      Element::TypeRef(type_ref) => return_type = return_type.or(Some(type_ref)),
+      Element::Block(block) => body = body.or(Some(block)),
+      _ => {}
+    }
+  }
+  owned::ClassMember::Method(owned::MethodDecl {
+    loc: (),
+    metadata,
+    modifiers,
+    kind,
+    name: name.unwrap_or_default(),
+    params,
+    return_type,
+    body,
+  })
+}
+
+/// Lowers an `import` declaration: a wildcard import has no name.
+fn import_decl<I: Iterator<Item = Element>>(elements: I) -> owned::Stmt {
+  let mut package: Vec<String> = elements
+    .filter_map(|element| match element {
+      Element::Name(segments) => Some(segments),
+      _ => None,
+    })
+    .next()
+    .unwrap_or_default();
+  let name = match package.pop() {
+    Some(ref name) if name == "*" => None,
+    name => name,
+  };
+  owned::Stmt::Import(owned::ImportDecl { loc: (), package, name })
+}
+
+fn metadata<I: Iterator<Item = Element>>(elements: I) -> owned::Metadata {
+  let mut name: Option<String> = None;
+  let mut args: Vec<owned::MetadataArg> = Vec::new();
+  for element in elements {
+    match element {
+      Element::Token(SyntaxKind::TokenIdent, text) => name = name.or_else(|| Some(text.to_string())),
+      Element::MetadataArg(arg) => args.push(arg),
+      _ => {}
+    }
+  }
+  owned::Metadata {
+    loc: (),
+    name: name.unwrap_or_default(),
+    args,
+  }
+}
+
+/// Lowers a metadata argument.
+///
+/// String values are unescaped, other values are kept as written. The sign of
+/// a negative number is joined to its digits.
+fn metadata_arg<I: Iterator<Item = Element>>(elements: I) -> owned::MetadataArg {
+  let mut key: Option<String> = None;
+  let mut value: Option<String> = None;
+  for element in elements {
+    match element {
+      Element::Token(SyntaxKind::TokenEq, _) => key = value.take(),
+      Element::Token(SyntaxKind::TokenStrLit, text) => {
+        value = value.or_else(|| Some(unescape_string(&text).unwrap_or_else(|| text.to_string())))
+      }
+      Element::Token(_, text) => value.get_or_insert_with(String::new).push_str(&text),
+      _ => {}
+    }
+  }
+  owned::MetadataArg {
+    loc: (),
+    key,
+    value: value.unwrap_or_default(),
+  }
+}
+
+fn declarators<I: Iterator<Item = Element>>(elements: I) -> Vec<owned::VarDeclarator> {
+  elements
+    .filter_map(|element| match element {
+      Element::VarDeclarator(declarator) => Some(declarator),
+      _ => None,
+    })
+    .collect()
+}
+
+fn var_declarator<I: Iterator<Item = Element>>(elements: I) -> owned::VarDeclarator {
+  let mut name: Option<String> = None;
+  let mut type_annotation: Option<owned::TypeRef> = None;
+  let mut init: Option<owned::Expr> = None;
+  let mut has_init = false;
+  for element in elements {
+    match element {
+      Element::Token(SyntaxKind::TokenEq, _) => has_init = true,
+      Element::Token(..) => {}
+      Element::Ident(ident) if !has_init && name.is_none() => name = Some(ident),
+      Element::TypeRef(type_ref) => type_annotation = type_annotation.or(Some(type_ref)),
+      element if has_init => init = init.or_else(|| element.into_expr()),
+      _ => {}
+    }
+  }
+  owned::VarDeclarator {
+    loc: (),
+    name: name.unwrap_or_default(),
+    type_annotation,
+    init: init.map(Box::new),
+  }
+}
+
+/// Returns the value of a number literal.
+pub(crate) fn num_value(text: &str) -> f64 {
+  if text.starts_with("0x") || text.starts_with("0X") {
+    text[2..]
+      .chars()
+      .filter_map(|c| c.to_digit(16))
+      .fold(0.0, |value, digit| value * 16.0 + f64::from(digit))
+  } else {
+    text.parse().unwrap_or(std::f64::NAN)
+  }
+}
+
+/// Splits a target path with its variable: `/clip/sub:score`
+fn target_path(text: &str) -> owned::TargetPath {
+  let (path, name) = match text.rfind(':') {
+    Some(colon) => (&text[..colon], &text[colon + 1..]),
+    None => (text, ""),
+  };
+  owned::TargetPath {
+    loc: (),
+    is_absolute: path.starts_with('/'),
+    segments: path
+      .split('/')
+      .filter(|segment| !segment.is_empty())
+      .map(String::from)
+      .collect(),
+    name: name.to_string(),
+  }
+}
+
+/// Reinterprets an expression as an assignment pattern.
+fn pat(expr: owned::Expr) -> owned::Pat {
+  match expr {
+    owned::Expr::Ident(ident) => owned::Pat::IdentPat(owned::IdentPat {
+      loc: (),
+      name: ident.name,
+    }),
+    owned::Expr::Member(member) => owned::Pat::MemberPat(owned::MemberPat {
+      loc: (),
+      base: member.base,
+      key: member.key,
+    }),
+    owned::Expr::TargetPath(path) => owned::Pat::TargetPath(path),
+    owned::Expr::Register(register) => owned::Pat::Register(register),
+    _ => owned::Pat::SyntaxError,
+  }
+}
+
+fn call_expr<I: Iterator<Item = Element>>(elements: I) -> owned::Expr {
+  let mut exprs = elements.filter_map(Element::into_expr);
+  let callee = exprs.next();
+  owned::Expr::Call(owned::CallExpr {
+    loc: (),
+    callee: expr_or_error(callee),
+    args: exprs.collect(),
+  })
+}
+
+/// Lowers a member access: the key of `base.key` is a string literal.
+fn member_expr<I: Iterator<Item = Element>>(elements: I) -> owned::Expr {
+  let mut base: Option<owned::Expr> = None;
+  let mut key: Option<owned::Expr> = None;
+  let mut has_dot = false;
+  for element in elements {
+    match element {
+      Element::Token(SyntaxKind::TokenDot, _) => has_dot = true,
+      Element::Token(..) => {}
+      element if base.is_none() => base = element.into_expr(),
+      Element::Ident(name) if has_dot => {
+        key = key.or(Some(owned::Expr::StrLit(owned::StrLit { loc: (), value: name })))
+      }
+      element => key = key.or_else(|| element.into_expr()),
+    }
+  }
+  owned::Expr::Member(owned::MemberExpr {
+    loc: (),
+    base: expr_or_error(base),
+    key: expr_or_error(key),
+  })
+}
+
+fn unary_expr<I: Iterator<Item = Element>>(elements: I) -> owned::Expr {
+  let mut op: Option<UnaryOp> = None;
+  let mut arg: Option<owned::Expr> = None;
+  for element in elements {
+    match element {
+      Element::Token(kind, _) => op = op.or_else(|| unary_op(kind)),
+      element => arg = arg.or_else(|| element.into_expr()),
+    }
+  }
+  match op {
+    Some(op) => owned::Expr::Unary(owned::UnaryExpr {
+      loc: (),
+      op,
+      arg: expr_or_error(arg),
+    }),
+    None => owned::Expr::SyntaxError,
+  }
+}
+
+fn update_expr<I: Iterator<Item = Element>>(elements: I) -> owned::Expr {
+  let mut op: Option<UpdateOp> = None;
+  let mut arg: Option<owned::Expr> = None;
+  let mut prefix = false;
+  for element in elements {
+    match element {
+      Element::Token(kind, _) => {
+        prefix = prefix || arg.is_none();
+        op = op.or(match kind {
+          SyntaxKind::TokenPlusPlus => Some(UpdateOp::Increment),
+          SyntaxKind::TokenMinusMinus => Some(UpdateOp::Decrement),
+          _ => None,
+        });
+      }
+      element => arg = arg.or_else(|| element.into_expr()),
+    }
+  }
+  match op {
+    Some(op) => owned::Expr::Update(owned::UpdateExpr {
+      loc: (),
+      op,
+      prefix,
+      arg: expr_or_error(arg),
+    }),
+    None => owned::Expr::SyntaxError,
+  }
+}
+
+fn bin_expr<I: Iterator<Item = Element>>(elements: I) -> owned::Expr {
+  let mut op: Option<BinOp> = None;
+  let mut exprs = Vec::with_capacity(2);
+  for element in elements {
+    match element {
+      Element::Token(kind, _) => op = op.or_else(|| bin_op(kind)),
+      element => exprs.extend(element.into_expr()),
+    }
+  }
+  let mut exprs = exprs.into_iter();
+  match op {
+    Some(op) => owned::Expr::Bin(owned::BinExpr {
+      loc: (),
+      op,
+      left: expr_or_error(exprs.next()),
+      right: expr_or_error(exprs.next()),
+    }),
+    None => owned::Expr::SyntaxError,
+  }
+}
+
+fn assign_expr<I: Iterator<Item = Element>>(elements: I) -> owned::Expr {
+  let mut op: Option<AssignOp> = None;
+  let mut exprs = Vec::with_capacity(2);
+  for element in elements {
+    match element {
+      Element::Token(kind, _) => op = op.or_else(|| assign_op(kind)),
+      element => exprs.extend(element.into_expr()),
+    }
+  }
+  let mut exprs = exprs.into_iter();
+  match op {
+    Some(op) => owned::Expr::Assign(owned::AssignExpr {
+      loc: (),
+      op,
+      target: Box::new(exprs.next().map_or(owned::Pat::SyntaxError, pat)),
+      value: expr_or_error(exprs.next()),
+    }),
+    None => owned::Expr::SyntaxError,
+  }
+}
+
+/// Lowers a conditional expression.
+///
+/// Missing operands are tolerated: the `?` and `:` tokens start the
+/// consequent and alternate.
+fn cond_expr<I: Iterator<Item = Element>>(elements: I) -> owned::Expr {
+  let mut operands: [Option<owned::Expr>; 3] = [None, None, None];
+  let mut part: usize = 0;
+  for element in elements {
+    match element {
+      Element::Token(SyntaxKind::TokenQuestion, _) => part = part.max(1),
+      Element::Token(SyntaxKind::TokenColon, _) => part = part.max(2),
+      Element::Token(..) => {}
+      element if part < 3 => {
+        operands[part] = element.into_expr();
+        part += 1;
+      }
+      _ => {}
+    }
+  }
+  let [test, consequent, alternate] = operands;
+  owned::Expr::Cond(owned::CondExpr {
+    loc: (),
+    test: expr_or_error(test),
+    consequent: expr_or_error(consequent),
+    alternate: expr_or_error(alternate),
+  })
+}
+
+pub(crate) fn unary_op(kind: SyntaxKind) -> Option<UnaryOp> {
+  use SyntaxKind::*;
+  let op = match kind {
+    TokenTilde => UnaryOp::BitNot,
+    TokenDelete => UnaryOp::Delete,
+    TokenExcl => UnaryOp::LogicalNot,
+    TokenMinus => UnaryOp::Minus,
+    TokenPlus => UnaryOp::Plus,
+    TokenTypeOf => UnaryOp::TypeOf,
+    TokenVoid => UnaryOp::Void,
+    _ => return None,
+  };
+  Some(op)
+}
+
+pub(crate) fn bin_op(kind: SyntaxKind) -> Option<BinOp> {
+  use SyntaxKind::*;
+  let op = match kind {
+    TokenPlus => BinOp::Add,
+    TokenAmp => BinOp::BitAnd,
+    TokenPipe => BinOp::BitOr,
+    TokenCaret => BinOp::BitXor,
+    TokenSlash => BinOp::Divide,
+    TokenEqEq => BinOp::Equals,
+    TokenGt => BinOp::Greater,
+    TokenGtEq => BinOp::GreaterOrEquals,
+    TokenIn => BinOp::In,
+    TokenInstanceOf => BinOp::InstanceOf,
+    TokenAdd => BinOp::LegacyAdd,
+    TokenLtLt => BinOp::LeftShift,
+    TokenLt => BinOp::Less,
+    TokenLtEq => BinOp::LessOrEquals,
+    TokenAmpAmp => BinOp::LogicalAnd,
+    TokenPipePipe => BinOp::LogicalOr,
+    TokenStar => BinOp::Multiply,
+    TokenExclEq => BinOp::NotEquals,
+    TokenExclEqEq => BinOp::NotStrictEquals,
+    TokenPercent => BinOp::Remainder,
+    TokenGtGt => BinOp::SignedRightShift,
+    TokenMinus => BinOp::Subtract,
+    TokenEqEqEq => BinOp::StrictEquals,
+    TokenGtGtGt => BinOp::UnsignedRightShift,
+    _ => return None,
+  };
+  Some(op)
+}
+
+pub(crate) fn assign_op(kind: SyntaxKind) -> Option<AssignOp> {
+  use SyntaxKind::*;
+  let op = match kind {
+    TokenEq => AssignOp::Assign,
+    TokenPlusEq => AssignOp::Add,
+    TokenAmpEq => AssignOp::BitAnd,
+    TokenPipeEq => AssignOp::BitOr,
+    TokenCaretEq => AssignOp::BitXor,
+    TokenSlashEq => AssignOp::Divide,
+    TokenLtLtEq => AssignOp::LeftShift,
+    TokenStarEq => AssignOp::Multiply,
+    TokenPercentEq => AssignOp::Remainder,
+    TokenGtGtEq => AssignOp::SignedRightShift,
+    TokenMinusEq => AssignOp::Subtract,
+    TokenGtGtGtEq => AssignOp::UnsignedRightShift,
+    _ => return None,
+  };
+  Some(op)
+}
+
+#[cfg(test)]
+mod lower_tests {
+  use crate::include::{expand, MemoryLoader};
+  use crate::lower::{lower_script, OwnedSink};
+  use crate::parser::{
+    parse, parse_class_file, parse_expr, parse_flash4, parse_instance_script, parse_script, parse_script_fast,
+    parse_stmt, parse_with_sink, ParseOptions,
+  };
+  use crate::types::ast::traits::{
+    Access, AssignOp, BinOp, ButtonEvent, ClipEvent, MethodKind, Modifiers, UnaryOp, UpdateOp,
+  };
+  use crate::types::owned;
+  use std::path::Path;
+
+  /// Parses a script with and without syntax tree, and returns its lowered
+  /// statements.
+  fn parse_stmts(text: &str) -> Vec<owned::Stmt> {
+    let script = parse_script(text, &ParseOptions::default());
+    assert_eq!(parse_script_fast(text, &ParseOptions::default()), script);
+    script.stmts
+  }
+
+  fn ident(name: &str) -> owned::Expr {
+    owned::Expr::Ident(owned::IdentExpr {
+      loc: (),
+      name: name.to_string(),
+    })
+  }
+
+  fn num_lit(value: f64) -> owned::Expr {
+    owned::Expr::NumLit(owned::NumLit { loc: (), value })
+  }
+
+  fn call(callee: &str, args: Vec<owned::Expr>) -> owned::Expr {
+    owned::Expr::Call(owned::CallExpr {
+      loc: (),
+      callee: Box::new(ident(callee)),
+      args,
+    })
+  }
+
+  fn expr_stmt(expr: owned::Expr) -> owned::Stmt {
+    owned::Stmt::Expr(owned::ExprStmt {
+      loc: (),
+      expr: Box::new(expr),
+    })
+  }
+
+  fn block(stmts: Vec<owned::Stmt>) -> owned::Stmt {
+    owned::Stmt::Block(block_stmt(stmts))
+  }
+
+  fn block_stmt(stmts: Vec<owned::Stmt>) -> owned::BlockStmt {
+    owned::BlockStmt { loc: (), stmts }
+  }
+
+  fn str_lit(value: &str) -> owned::Expr {
+    owned::Expr::StrLit(owned::StrLit {
+      loc: (),
+      value: value.to_string(),
+    })
+  }
+
+  /// Creates a type reference from a possibly dotted name.
+  fn type_ref(name: &str) -> owned::TypeRef {
+    let mut package: Vec<String> = name.split('.').map(String::from).collect();
+    let name = package.pop().unwrap_or_default();
+    owned::TypeRef { loc: (), package, name }
+  }
+
+  /// Creates a `var` declaration from the names and initializers of its declarators.
+  fn var_decl(declarators: Vec<(&str, Option<owned::Expr>)>) -> owned::VarDecl {
+    owned::VarDecl {
+      loc: (),
+      declarators: declarators
+        .into_iter()
+        .map(|(name, init)| owned::VarDeclarator {
+          loc: (),
+          name: name.to_string(),
+          type_annotation: None,
+          init: init.map(Box::new),
+        })
+        .collect(),
+    }
+  }
+
+  #[test]
+  fn test_lower_if() {
+    let text = "if (a) { b(); } else if (c) ; else {}";
+    let expected = vec![owned::Stmt::If(owned::IfStmt {
+      loc: (),
+      test: Box::new(ident("a")),
+      consequent: Box::new(block(vec![expr_stmt(call("b", Vec::new()))])),
+      alternate: Some(Box::new(owned::Stmt::If(owned::IfStmt {
+        loc: (),
+        test: Box::new(ident("c")),
+        consequent: Box::new(owned::Stmt::Empty(owned::EmptyStmt { loc: () })),
+        alternate: Some(Box::new(block(Vec::new()))),
+      }))),
+    })];
+    assert_eq!(parse_stmts(text), expected);
+  }
+
+  #[test]
+  fn test_lower_loops() {
+    let text = concat!(
+      "while (a) b();\n",
+      "do c(); while (d);\n",
+      "for (var i = 0, j = 10; i < j; i++) {}\n",
+      "for (;;) ;\n",
+      "for (k in obj) {}\n",
+      "for (var k in obj) {}\n",
+    );
+    let expected = vec![
+      owned::Stmt::While(owned::WhileStmt {
+        loc: (),
+        test: Box::new(ident("a")),
+        body: Box::new(expr_stmt(call("b", Vec::new()))),
+      }),
+      owned::Stmt::DoWhile(owned::DoWhileStmt {
+        loc: (),
+        body: Box::new(expr_stmt(call("c", Vec::new()))),
+        test: Box::new(ident("d")),
+      }),
+      owned::Stmt::For(owned::ForStmt {
+        loc: (),
+        init: Some(owned::ForInit::VarDecl(var_decl(vec![
+          ("i", Some(num_lit(0.0))),
+          ("j", Some(num_lit(10.0))),
+        ]))),
+        test: Some(Box::new(owned::Expr::Bin(owned::BinExpr {
+          loc: (),
+          op: BinOp::Less,
+          left: Box::new(ident("i")),
+          right: Box::new(ident("j")),
+        }))),
+        update: Some(Box::new(owned::Expr::Update(owned::UpdateExpr {
+          loc: (),
+          op: UpdateOp::Increment,
+          prefix: false,
+          arg: Box::new(ident("i")),
+        }))),
+        body: Box::new(block(Vec::new())),
+      }),
+      owned::Stmt::For(owned::ForStmt {
+        loc: (),
+        init: None,
+        test: None,
+        update: None,
+        body: Box::new(owned::Stmt::Empty(owned::EmptyStmt { loc: () })),
+      }),
+      owned::Stmt::ForIn(owned::ForInStmt {
+        loc: (),
+        target: owned::ForInTarget::Pat(Box::new(owned::Pat::IdentPat(owned::IdentPat {
+          loc: (),
+          name: "k".to_string(),
+        }))),
+        object: Box::new(ident("obj")),
+        body: Box::new(block(Vec::new())),
+      }),
+      owned::Stmt::ForIn(owned::ForInStmt {
+        loc: (),
+        target: owned::ForInTarget::VarDecl(var_decl(vec![("k", None)])),
+        object: Box::new(ident("obj")),
+        body: Box::new(block(Vec::new())),
+      }),
+    ];
+    assert_eq!(parse_stmts(text), expected);
+  }
+
+  #[test]
+  fn test_lower_switch() {
+    let text = "switch (x) { case 1: case 2: a(); default: b(); case \"c\": }";
+    let expected = vec![owned::Stmt::Switch(owned::SwitchStmt {
+      loc: (),
+      discriminant: Box::new(ident("x")),
+      clauses: vec![
+        owned::CaseClause {
+          loc: (),
+          test: Some(Box::new(num_lit(1.0))),
+          body: Vec::new(),
+        },
+        owned::CaseClause {
+          loc: (),
+          test: Some(Box::new(num_lit(2.0))),
+          body: vec![expr_stmt(call("a", Vec::new()))],
+        },
+        owned::CaseClause {
+          loc: (),
+          test: None,
+          body: vec![expr_stmt(call("b", Vec::new()))],
+        },
+        owned::CaseClause {
+          loc: (),
+          test: Some(Box::new(owned::Expr::StrLit(owned::StrLit {
+            loc: (),
+            value: "c".to_string(),
+          }))),
+          body: Vec::new(),
+        },
+      ],
+    })];
+    assert_eq!(parse_stmts(text), expected);
+  }
+
+  #[test]
+  fn test_lower_try_throw() {
+    let text = "try { a(); } catch (e: Error) { b(); } catch (f) {} finally { c(); }\ntry {} finally {}\nthrow x;";
+    let expected = vec![
+      owned::Stmt::Try(owned::TryStmt {
+        loc: (),
+        block: block_stmt(vec![expr_stmt(call("a", Vec::new()))]),
+        handlers: vec![
+          owned::CatchClause {
+            loc: (),
+            param: owned::IdentPat {
+              loc: (),
+              name: "e".to_string(),
+            },
+            param_type: Some(type_ref("Error")),
+            body: block_stmt(vec![expr_stmt(call("b", Vec::new()))]),
+          },
+          owned::CatchClause {
+            loc: (),
+            param: owned::IdentPat {
+              loc: (),
+              name: "f".to_string(),
+            },
+            param_type: None,
+            body: block_stmt(Vec::new()),
+          },
+        ],
+        finalizer: Some(block_stmt(vec![expr_stmt(call("c", Vec::new()))])),
+      }),
+      owned::Stmt::Try(owned::TryStmt {
+        loc: (),
+        block: block_stmt(Vec::new()),
+        handlers: Vec::new(),
+        finalizer: Some(block_stmt(Vec::new())),
+      }),
+      owned::Stmt::Throw(owned::ThrowStmt {
+        loc: (),
+        value: Box::new(ident("x")),
+      }),
+    ];
+    assert_eq!(parse_stmts(text), expected);
+  }
+
+  #[test]
+  fn test_lower_jumps() {
+    // The line terminator after the second `return` ends the statement
+    let text = "outer: while (a) { break outer; continue; break; }\nreturn a;\nreturn\nb;";
+    let expected = vec![
+      owned::Stmt::Labelled(owned::LabelledStmt {
+        loc: (),
+        label: "outer".to_string(),
+        body: Box::new(owned::Stmt::While(owned::WhileStmt {
+          loc: (),
+          test: Box::new(ident("a")),
+          body: Box::new(block(vec![
+            owned::Stmt::Break(owned::BreakStmt {
+              loc: (),
+              label: Some("outer".to_string()),
+            }),
+            owned::Stmt::Continue(owned::ContinueStmt { loc: (), label: None }),
+            owned::Stmt::Break(owned::BreakStmt { loc: (), label: None }),
+          ])),
+        })),
+      }),
+      owned::Stmt::Return(owned::ReturnStmt {
+        loc: (),
+        value: Some(Box::new(ident("a"))),
+      }),
+      owned::Stmt::Return(owned::ReturnStmt { loc: (), value: None }),
+      expr_stmt(ident("b")),
+    ];
+    assert_eq!(parse_stmts(text), expected);
+  }
+
+  #[test]
+  fn test_lower_scoped_stmts() {
+    let text = concat!(
+      "with (_root.menu) { a(); }\n",
+      "tellTarget (\"/clip\") {}\n",
+      "tellTarget (\"Scene 2\", \"/clip\") {}\n",
+      "ifFrameLoaded (10) {}\n",
+      "ifFrameLoaded (\"Scene 1\", 10) b();\n",
+    );
+    let expected = vec![
+      owned::Stmt::With(owned::WithStmt {
+        loc: (),
+        object: Box::new(owned::Expr::Member(owned::MemberExpr {
+          loc: (),
+          base: Box::new(ident("_root")),
+          key: Box::new(str_lit("menu")),
+        })),
+        body: Box::new(block(vec![expr_stmt(call("a", Vec::new()))])),
+      }),
+      owned::Stmt::TellTarget(owned::TellTargetStmt {
+        loc: (),
+        scene: None,
+        target: Box::new(str_lit("/clip")),
+        body: Box::new(block(Vec::new())),
+      }),
+      owned::Stmt::TellTarget(owned::TellTargetStmt {
+        loc: (),
+        scene: Some(Box::new(str_lit("Scene 2"))),
+        target: Box::new(str_lit("/clip")),
+        body: Box::new(block(Vec::new())),
+      }),
+      owned::Stmt::IfFrameLoaded(owned::IfFrameLoadedStmt {
+        loc: (),
+        scene: None,
+        frame: Box::new(num_lit(10.0)),
+        body: Box::new(block(Vec::new())),
+      }),
+      owned::Stmt::IfFrameLoaded(owned::IfFrameLoadedStmt {
+        loc: (),
+        scene: Some(Box::new(str_lit("Scene 1"))),
+        frame: Box::new(num_lit(10.0)),
+        body: Box::new(expr_stmt(call("b", Vec::new()))),
+      }),
+    ];
+    assert_eq!(parse_stmts(text), expected);
+  }
+
+  #[test]
+  fn test_lower_event_handlers() {
+    let text = concat!(
+      "on (press, keyPress \"<Enter>\", keyPress \"a\") { a(); }\n",
+      "onClipEvent (enterFrame) {}\n",
+    );
+    let expected = vec![
+      owned::Stmt::OnHandler(owned::OnHandler {
+        loc: (),
+        events: vec![
+          ButtonEvent::Press,
+          ButtonEvent::KeyPress(13),
+          ButtonEvent::KeyPress(b'a'),
+        ],
+        body: block_stmt(vec![expr_stmt(call("a", Vec::new()))]),
+      }),
+      owned::Stmt::ClipEventHandler(owned::ClipEventHandler {
+        loc: (),
+        events: vec![ClipEvent::EnterFrame],
+        body: block_stmt(Vec::new()),
+      }),
+    ];
+    assert_eq!(
+      lower_script(&parse_instance_script(text, &ParseOptions::default()).syntax()).stmts,
+      expected
+    );
+  }
+
+  #[test]
+  fn test_lower_class() {
+    let text = concat!(
+      "dynamic class a.Shape extends Base implements IDrawable, b.IMovable {\n",
+      "  private static var count:Number = 0, label;\n",
+      "  function Shape(x:Number, y) {}\n",
+      "  public function get area():Number { return 0; }\n",
+      "  function draw();\n",
+      "}\n",
+    );
+    let method = |modifiers: Modifiers, kind: MethodKind, name: &str| owned::MethodDecl {
+      loc: (),
+      metadata: Vec::new(),
+      modifiers,
+      kind,
+      name: name.to_string(),
+      params: Vec::new(),
+      return_type: None,
+      body: Some(block_stmt(Vec::new())),
+    };
+    let expected = vec![owned::Stmt::Class(owned::ClassDecl {
+      loc: (),
+      metadata: Vec::new(),
+      name: type_ref("a.Shape"),
+      is_dynamic: true,
+      is_intrinsic: false,
+      extends: Some(type_ref("Base")),
+      implements: vec![type_ref("IDrawable"), type_ref("b.IMovable")],
+      members: vec![
+        owned::ClassMember::Field(owned::FieldDecl {
+          loc: (),
+          metadata: Vec::new(),
+          modifiers: Modifiers {
+            is_static: true,
+            access: Some(Access::Private),
+          },
+          declarators: vec![
+            owned::VarDeclarator {
+              loc: (),
+              name: "count".to_string(),
+              type_annotation: Some(type_ref("Number")),
+              init: Some(Box::new(num_lit(0.0))),
+            },
+            owned::VarDeclarator {
+              loc: (),
+              name: "label".to_string(),
+              type_annotation: None,
+              init: None,
+            },
+          ],
+        }),
+        owned::ClassMember::Method(owned::MethodDecl {
+          params: vec![
+            owned::Param {
+              loc: (),
+              name: "x".to_string(),
+              type_annotation: Some(type_ref("Number")),
+            },
+            owned::Param {
+              loc: (),
+              name: "y".to_string(),
+              type_annotation: None,
+            },
+          ],
+          ..method(Modifiers::default(), MethodKind::Constructor, "Shape")
+        }),
+        owned::ClassMember::Method(owned::MethodDecl {
+          return_type: Some(type_ref("Number")),
+          body: Some(block_stmt(vec![owned::Stmt::Return(owned::ReturnStmt {
+            loc: (),
+            value: Some(Box::new(num_lit(0.0))),
+          })])),
+          ..method(
+            Modifiers {
+              is_static: false,
+              access: Some(Access::Public),
+            },
+            MethodKind::Get,
+            "area",
+          )
+        }),
+        owned::ClassMember::Method(owned::MethodDecl {
+          body: None,
+          ..method(Modifiers::default(), MethodKind::Method, "draw")
+        }),
+      ],
+    })];
+    assert_eq!(parse_stmts(text), expected);
+  }
+
+  #[test]
+  fn test_lower_interface() {
+    let text = "interface a.IShape extends IDrawable { var size; function area():Number; function draw() {} }";
+    let expected = vec![owned::Stmt::Interface(owned::InterfaceDecl {
+      loc: (),
+      metadata: Vec::new(),
+      name: type_ref("a.IShape"),
+      extends: Some(type_ref("IDrawable")),
+      methods: vec![
+        owned::MethodDecl {
+          loc: (),
+          metadata: Vec::new(),
+          modifiers: Modifiers::default(),
+          kind: MethodKind::Method,
+          name: "area".to_string(),
+          params: Vec::new(),
+          return_type: Some(type_ref("Number")),
+          body: None,
+        },
+        owned::MethodDecl {
+          loc: (),
+          metadata: Vec::new(),
+          modifiers: Modifiers::default(),
+          kind: MethodKind::Method,
+          name: "draw".to_string(),
+          params: Vec::new(),
+          return_type: None,
+          body: Some(block_stmt(Vec::new())),
+        },
+      ],
+    })];
+    assert_eq!(parse_stmts(text), expected);
+  }
+
+  #[test]
+  fn test_lower_import() {
+    let text = "import a.b.C;\nimport a.b.*;";
+    let expected = vec![
+      owned::Stmt::Import(owned::ImportDecl {
+        loc: (),
+        package: vec!["a".to_string(), "b".to_string()],
+        name: Some("C".to_string()),
+      }),
+      owned::Stmt::Import(owned::ImportDecl {
+        loc: (),
+        package: vec!["a".to_string(), "b".to_string()],
+        name: None,
+      }),
+    ];
+    assert_eq!(parse_stmts(text), expected);
+  }
+
+  #[test]
+  fn test_lower_metadata() {
+    let text = concat!(
+      "[Event(\"click\")]\n",
+      "class A {\n",
+      "  [Inspectable(defaultValue=10, type=\"Number\")]\n",
+      "  [Bindable]\n",
+      "  var a;\n",
+      "  [Inspectable(defaultValue=false, minValue=-1, format=null)]\n",
+      "  var b;\n",
+      "}\n",
+    );
+    let tag = |name: &str, args: Vec<(Option<&str>, &str)>| owned::Metadata {
+      loc: (),
+      name: name.to_string(),
+      args: args
+        .into_iter()
+        .map(|(key, value)| owned::MetadataArg {
+          loc: (),
+          key: key.map(String::from),
+          value: value.to_string(),
+        })
+        .collect(),
+    };
+    let expected = vec![owned::Stmt::Class(owned::ClassDecl {
+      loc: (),
+      metadata: vec![tag("Event", vec![(None, "click")])],
+      name: type_ref("A"),
+      is_dynamic: false,
+      is_intrinsic: false,
+      extends: None,
+      implements: Vec::new(),
+      members: vec![
+        owned::ClassMember::Field(owned::FieldDecl {
+          loc: (),
+          metadata: vec![
+            tag(
+              "Inspectable",
+              vec![(Some("defaultValue"), "10"), (Some("type"), "Number")],
+            ),
+            tag("Bindable", Vec::new()),
+          ],
+          modifiers: Modifiers::default(),
+          declarators: vec![owned::VarDeclarator {
+            loc: (),
+            name: "a".to_string(),
+            type_annotation: None,
+            init: None,
+          }],
+        }),
+        owned::ClassMember::Field(owned::FieldDecl {
+          loc: (),
+          metadata: vec![tag(
+            "Inspectable",
+            vec![
+              (Some("defaultValue"), "false"),
+              (Some("minValue"), "-1"),
+              (Some("format"), "null"),
+            ],
+          )],
+          modifiers: Modifiers::default(),
+          declarators: vec![owned::VarDeclarator {
+            loc: (),
+            name: "b".to_string(),
+            type_annotation: None,
+            init: None,
+          }],
+        }),
+      ],
+    })];
+    assert_eq!(parse_stmts(text), expected);
+  }
+
+  #[test]
+  fn test_lower_inserted_semicolons() {
+    let text = "{ a()\nreturn\nb }";
+    let expected = vec![block(vec![
+      expr_stmt(call("a", Vec::new())),
+      owned::Stmt::Return(owned::ReturnStmt { loc: (), value: None }),
+      expr_stmt(ident("b")),
+    ])];
+    assert_eq!(parse_stmts(text), expected);
+  }
+
+  #[test]
+  fn test_lower_assignment() {
+    let text = "a.b += -c[1] * 0x10;\nx = y ? 1 : 2, z;";
+    let member = owned::MemberPat {
+      loc: (),
+      base: Box::new(ident("a")),
+      key: Box::new(str_lit("b")),
+    };
+    let index = owned::Expr::Member(owned::MemberExpr {
+      loc: (),
+      base: Box::new(ident("c")),
+      key: Box::new(num_lit(1.0)),
+    });
+    let value = owned::Expr::Bin(owned::BinExpr {
+      loc: (),
+      op: BinOp::Multiply,
+      left: Box::new(owned::Expr::Unary(owned::UnaryExpr {
+        loc: (),
+        op: UnaryOp::Minus,
+        arg: Box::new(index),
+      })),
+      right: Box::new(num_lit(16.0)),
+    });
+    let expected = vec![
+      expr_stmt(owned::Expr::Assign(owned::AssignExpr {
+        loc: (),
+        op: AssignOp::Add,
+        target: Box::new(owned::Pat::MemberPat(member)),
+        value: Box::new(value),
+      })),
+      expr_stmt(owned::Expr::Seq(owned::SeqExpr {
+        loc: (),
+        exprs: vec![
+          owned::Expr::Assign(owned::AssignExpr {
+            loc: (),
+            op: AssignOp::Assign,
+            target: Box::new(owned::Pat::IdentPat(owned::IdentPat {
+              loc: (),
+              name: "x".to_string(),
+            })),
+            value: Box::new(owned::Expr::Cond(owned::CondExpr {
+              loc: (),
+              test: Box::new(ident("y")),
+              consequent: Box::new(num_lit(1.0)),
+              alternate: Box::new(num_lit(2.0)),
+            })),
+          }),
+          ident("z"),
+        ],
+      })),
+    ];
+    assert_eq!(parse_stmts(text), expected);
+  }
+
+  #[test]
+  fn test_lower_target_path() {
+    let text = "/clip/sub:score = ../:x;";
+    let expected = vec![expr_stmt(owned::Expr::Assign(owned::AssignExpr {
+      loc: (),
+      op: AssignOp::Assign,
+      target: Box::new(owned::Pat::TargetPath(owned::TargetPath {
+        loc: (),
+        is_absolute: true,
+        segments: vec!["clip".to_string(), "sub".to_string()],
+        name: "score".to_string(),
+      })),
+      value: Box::new(owned::Expr::TargetPath(owned::TargetPath {
+        loc: (),
+        is_absolute: false,
+        segments: vec!["..".to_string()],
+        name: "x".to_string(),
+      })),
+    }))];
+    assert_eq!(lower_script(&parse_flash4(text).syntax()).stmts, expected);
+  }
+
+  #[test]
+  fn test_lower_recovery() {
+    let text = "if (a b; x = ;";
+    let expected = vec![
+      owned::Stmt::If(owned::IfStmt {
+        loc: (),
+        test: Box::new(ident("a")),
+        consequent: Box::new(expr_stmt(ident("b"))),
+        alternate: None,
+      }),
+      expr_stmt(owned::Expr::Assign(owned::AssignExpr {
+        loc: (),
+        op: AssignOp::Assign,
+        target: Box::new(owned::Pat::IdentPat(owned::IdentPat {
+          loc: (),
+          name: "x".to_string(),
+        })),
+        value: Box::new(owned::Expr::SyntaxError),
+      })),
+    ];
+    assert_eq!(parse_stmts(text), expected);
+  }
+
+  #[test]
+  fn test_lower_entry_points() {
+    let class = vec![
+      owned::Stmt::Import(owned::ImportDecl {
+        loc: (),
+        package: vec!["a".to_string()],
+        name: Some("B".to_string()),
+      }),
+      owned::Stmt::Interface(owned::InterfaceDecl {
+        loc: (),
+        metadata: Vec::new(),
+        name: type_ref("C"),
+        extends: None,
+        methods: Vec::new(),
+      }),
+    ];
+    assert_eq!(
+      lower_script(&parse_class_file("import a.B;\ninterface C {}", &ParseOptions::default()).syntax()).stmts,
+      class
+    );
+    assert_eq!(
+      lower_script(&parse_stmt("if (a) b();", &ParseOptions::default()).syntax()).stmts,
+      parse_stmts("if (a) b();")
+    );
+    assert_eq!(
+      lower_script(&parse_expr("a(1)", &ParseOptions::default()).syntax()).stmts,
+      vec![expr_stmt(call("a", vec![num_lit(1.0)]))]
+    );
+  }
+
+  #[test]
+  fn test_lower_include() {
+    let mut loader = MemoryLoader::new();
+    loader.insert(
+      "main.as",
+      "#include \"a.as\"\nif (x) #include \"a.as\"\nswitch (x) { case 1: #include \"a.as\"\n}",
+    );
+    loader.insert("a.as", "f(); g();\n");
+    let expanded = expand(Path::new("main.as"), &loader, &ParseOptions::default()).unwrap();
+    let included = || vec![expr_stmt(call("f", Vec::new())), expr_stmt(call("g", Vec::new()))];
+    let mut expected = included();
+    expected.push(owned::Stmt::If(owned::IfStmt {
+      loc: (),
+      test: Box::new(ident("x")),
+      consequent: Box::new(block(included())),
+      alternate: None,
+    }));
+    expected.push(owned::Stmt::Switch(owned::SwitchStmt {
+      loc: (),
+      discriminant: Box::new(ident("x")),
+      clauses: vec![owned::CaseClause {
+        loc: (),
+        test: Some(Box::new(num_lit(1.0))),
+        body: included(),
+      }],
+    }));
+    assert_eq!(lower_script(&expanded.syntax()).stmts, expected);
+  }
+
+  #[test]
+  fn test_lower_sink() {
+    let text = "a(1);\nif (b c();";
+    let mut sink = OwnedSink::default();
+    parse_with_sink(text, &ParseOptions::default(), &mut sink);
+    let expected = vec![
+      expr_stmt(call("a", vec![num_lit(1.0)])),
+      owned::Stmt::If(owned::IfStmt {
+        loc: (),
+        test: Box::new(ident("b")),
+        consequent: Box::new(expr_stmt(call("c", Vec::new()))),
+        alternate: None,
+      }),
+    ];
+    assert_eq!(sink.finish().stmts, expected);
+    assert_eq!(
+      lower_script(&parse(text, &ParseOptions::default()).syntax()).stmts,
+      expected
+    );
+  }
+
+  #[test]
+  fn test_lower_fast() {
+    let text = "/* a */ a( // b\n  1 )\n\tif (x) /* c */ b()";
+    let expected = owned::Script {
+      loc: (),
+      stmts: vec![
+        expr_stmt(call("a", vec![num_lit(1.0)])),
+        owned::Stmt::If(owned::IfStmt {
+          loc: (),
+          test: Box::new(ident("x")),
+          consequent: Box::new(expr_stmt(call("b", Vec::new()))),
+          alternate: None,
+        }),
+      ],
+    };
+    assert_eq!(parse_script_fast(text, &ParseOptions::default()), expected);
+    assert_eq!(parse_script(text, &ParseOptions::default()), expected);
+  }
+
+  #[test]
+  fn test_lower_decompiler() {
+    let text = "_loc1_ = §§pop();\n§§push(_loc1_);\nloop0:\n§§goto(loop0);";
+    let mut sink = OwnedSink::default();
+    parse_with_sink(text, &ParseOptions::decompiler(), &mut sink);
+    let actual = sink.finish();
+    let register = owned::Register { loc: (), index: 1 };
+    let expected = owned::Script {
+      loc: (),
+      stmts: vec![
+        expr_stmt(owned::Expr::Assign(owned::AssignExpr {
+          loc: (),
+          op: AssignOp::Assign,
+          target: Box::new(owned::Pat::Register(register.clone())),
+          value: Box::new(owned::Expr::Pop(owned::PopExpr { loc: () })),
+        })),
+        owned::Stmt::Push(owned::PushStmt {
+          loc: (),
+          value: Box::new(owned::Expr::Register(register)),
+        }),
+        owned::Stmt::Labelled(owned::LabelledStmt {
+          loc: (),
+          label: "loop0".to_string(),
+          body: Box::new(owned::Stmt::Goto(owned::GotoStmt {
+            loc: (),
+            label: "loop0".to_string(),
+          })),
+        }),
+      ],
+    };
+    assert_eq!(actual, expected);
+    let parsed = parse(text, &ParseOptions::decompiler());
+    assert_eq!(lower_script(&parsed.syntax()), expected);
+  }
+
+  #[test]
+  fn test_lower_primary() {
+    let text = "this.a = ((true) ? null : undefined) + newline;";
+    let expected = vec![expr_stmt(owned::Expr::Assign(owned::AssignExpr {
+      loc: (),
+      op: AssignOp::Assign,
+      target: Box::new(owned::Pat::MemberPat(owned::MemberPat {
+        loc: (),
+        base: Box::new(owned::Expr::This(owned::ThisExpr { loc: () })),
+        key: Box::new(str_lit("a")),
+      })),
+      value: Box::new(owned::Expr::Bin(owned::BinExpr {
+        loc: (),
+        op: BinOp::Add,
+        left: Box::new(owned::Expr::Cond(owned::CondExpr {
+          loc: (),
+          test: Box::new(owned::Expr::BoolLit(owned::BoolLit { loc: (), value: true })),
+          consequent: Box::new(owned::Expr::NullLit(owned::NullLit { loc: () })),
+          alternate: Box::new(owned::Expr::UndefinedLit(owned::UndefinedLit { loc: () })),
+        })),
+        right: Box::new(owned::Expr::NewlineLit(owned::NewlineLit { loc: () })),
+      })),
+    }))];
+    assert_eq!(parse_stmts(text), expected);
+  }
+
+  #[test]
+  fn test_lower_string_escapes() {
+    let text = r#"trace("\u00e9\x41\0\101\q\uD83D\uDE00\uD83D", '\400\08', "\xZZ");"#;
+    let expected = vec![expr_stmt(owned::Expr::Call(owned::CallExpr {
+      loc: (),
+      callee: Box::new(ident("trace")),
+      args: vec![
+        str_lit("\u{e9}A\0Aq\u{1f600}\u{fffd}"),
+        str_lit(" 0\08"),
+        owned::Expr::SyntaxError,
+      ],
+    }))];
+    assert_eq!(parse_stmts(text), expected);
+  }
+}
@@ -1,6 +1,12 @@
+use crate::event::{self, Event, TreeSink};
 use crate::lexer::{Lexer, LexerToken};
+use crate::lower::{lower_script, OwnedSink};
+use crate::types::ast::traits::{ButtonEvent, ClipEvent};
 use crate::types::owned;
-use crate::types::syntax::{SyntaxKind, SyntaxNode};
+use crate::types::syntax::{SyntaxKind, SyntaxNode, SyntaxSymbol};
+use rowan::{SmolStr, TextRange, TextUnit};
+use std::borrow::Cow;
+use std::mem;
 
 // use crate::types::cast::ExprCast;
 //
@@ -18,25 +24,332 @@ use crate::types::syntax::{SyntaxKind, SyntaxNode};
 
 pub struct Parsed {
   green_node: rowan::GreenNode,
+  errors: Vec<SyntaxError>,
+  /// Offset of the parser when each error was reported
+  ///
+  /// These offsets are nondecreasing. Incremental reparsing uses them to find
+  /// the errors reported while parsing a given block.
+  error_offsets: Vec<TextUnit>,
+  options: ParseOptions,
 }
 
 impl Parsed {
   pub fn syntax(&self) -> SyntaxNode {
     SyntaxNode::new_root(self.green_node.clone())
   }
+
+  /// Errors found while parsing, in source order.
+  pub fn errors(&self) -> &[SyntaxError] {
+    &self.errors
+  }
+
+  /// Returns the result of parsing the text modified by `edit`.
+  ///
+  /// If the edit stays inside a single identifier, number or string literal,
+  /// or inside a single `{ ... }` block, only this token or block is parsed
+  /// again and the rest of the tree is reused. Otherwise, the whole text is
+  /// parsed again. Either way, the result is the same as a full parse with
+  /// the same entry point and options.
+  ///
+  /// The range of the edit must be on character boundaries.
+  pub fn reparse(&self, edit: &TextEdit) -> Parsed {
+    self
+      .reparse_token(edit)
+      .or_else(|| self.reparse_block(edit))
+      .unwrap_or_else(|| {
+        let root = self.syntax();
+        let text = edit.apply(&root.text().to_string());
+        parse_root(&text, root.kind(), &self.options)
+      })
+  }
+
+  /// Reparses an edit inside a single identifier, number or string literal.
+  ///
+  /// The edited token is relexed with its neighbors, to check that token
+  /// boundaries are unchanged. Identifiers used as contextual keywords are
+  /// never reparsed this way, nor any token with an error.
+  fn reparse_token(&self, edit: &TextEdit) -> Option<Parsed> {
+    // Target paths may span multiple tokens of the regular syntax
+    if self.options.has_target_paths() {
+      return None;
+    }
+    let token = self.syntax().covering_element(edit.range).into_token()?;
+    match (token.kind(), token.parent().kind()) {
+      (SyntaxKind::TokenIdent, SyntaxKind::NodeIdent)
+      | (SyntaxKind::TokenNumLit, SyntaxKind::NodeNumLit)
+      | (SyntaxKind::TokenStrLit, SyntaxKind::NodeStrLit) => {}
+      _ => return None,
+    }
+    let range = token.text_range();
+    let text = edit.apply_in(token.text(), range.start())?;
+    let is_keyword = |name: &str| is_contextual_keyword(name) || is_contextual_keyword(&self.options.fold_case(name));
+    if token.kind() == SyntaxKind::TokenIdent && (is_keyword(token.text()) || is_keyword(&text)) {
+      return None;
+    }
+    // The identifier would become a `NodeRegister`
+    if self.options.dialect == Dialect::Decompiler && register_index(&self.options.fold_case(&text)).is_some() {
+      return None;
+    }
+
+    let mut expected: Vec<LexerToken> = Vec::new();
+    if let Some(prev) = token.prev_token() {
+      expected.push(LexerToken {
+        kind: prev.kind(),
+        text: prev.text().clone(),
+      });
+    }
+    expected.push(LexerToken {
+      kind: token.kind(),
+      text: SmolStr::from(text.as_str()),
+    });
+    if let Some(next) = token.next_token() {
+      expected.push(LexerToken {
+        kind: next.kind(),
+        text: next.text().clone(),
+      });
+    }
+    let window: String = expected.iter().map(|token| token.text.as_str()).collect();
+    let actual: Vec<LexerToken> = new_lexer(&window, &self.options).collect();
+    if actual != expected {
+      return None;
+    }
+
+    let map = OffsetMap::new(range, TextUnit::of_str(&text));
+    let mut errors = Vec::with_capacity(self.errors.len());
+    for error in self.errors.iter() {
+      errors.push(SyntaxError {
+        kind: error.kind,
+        range: map.range(error.range)?,
+      });
+    }
+    let error_offsets = self
+      .error_offsets
+      .iter()
+      .map(|offset| map.offset(*offset))
+      .collect::<Option<_>>()?;
+    let green_token = rowan::GreenToken::new(token.kind().into(), SmolStr::from(text));
+    Some(Parsed {
+      green_node: token.replace_with(green_token),
+      errors,
+      error_offsets,
+      options: self.options,
+    })
+  }
+
+  /// Reparses an edit strictly inside the braces of a block.
+  ///
+  /// The new block must still end at its closing brace. It is reparsed only if
+  /// the nesting limit cannot be reached, as the depth of the block is unknown.
+  fn reparse_block(&self, edit: &TextEdit) -> Option<Parsed> {
+    let root = self.syntax();
+    let node = match root.covering_element(edit.range) {
+      rowan::NodeOrToken::Node(node) => node,
+      rowan::NodeOrToken::Token(token) => token.parent(),
+    };
+    let block = node.ancestors().find(|node| {
+      let range = node.text_range();
+      node.kind() == SyntaxKind::NodeBlock && range.start() < edit.range.start() && edit.range.end() < range.end()
+    })?;
+    let is_brace = |symbol: Option<SyntaxSymbol>, kind: SyntaxKind| match symbol {
+      Some(rowan::NodeOrToken::Token(token)) => token.kind() == kind,
+      _ => false,
+    };
+    if !is_brace(block.first_child_or_token(), SyntaxKind::TokenOpenBrace)
+      || !is_brace(block.last_child_or_token(), SyntaxKind::TokenCloseBrace)
+    {
+      return None;
+    }
+
+    let range = block.text_range();
+    let text = edit.apply_in(&block.text().to_string(), range.start())?;
+    let reparsed = Parser::new(new_lexer(&text, &self.options), &self.options).block_root()?;
+    let new_block = reparsed.syntax();
+    if block.ancestors().count() + tree_height(&new_block) >= self.options.max_depth {
+      return None;
+    }
+
+    // Errors reported while parsing the old block are replaced by the new ones
+    let inside_start = self
+      .error_offsets
+      .iter()
+      .take_while(|offset| **offset <= range.start())
+      .count();
+    let inside_end = inside_start
+      + self.error_offsets[inside_start..]
+        .iter()
+        .take_while(|offset| **offset < range.end())
+        .count();
+    let map = OffsetMap::new(range, TextUnit::of_str(&text));
+    let mut errors = self.errors[..inside_start].to_vec();
+    let mut error_offsets = self.error_offsets[..inside_start].to_vec();
+    for (error, offset) in reparsed.errors.iter().zip(reparsed.error_offsets.iter()) {
+      errors.push(SyntaxError {
+        kind: error.kind,
+        range: TextRange::offset_len(range.start() + error.range.start(), error.range.len()),
+      });
+      error_offsets.push(range.start() + *offset);
+    }
+    for (error, offset) in self.errors[inside_end..]
+      .iter()
+      .zip(self.error_offsets[inside_end..].iter())
+    {
+      errors.push(SyntaxError {
+        kind: error.kind,
+        range: map.range(error.range)?,
+      });
+      error_offsets.push(map.offset(*offset)?);
+    }
+    Some(Parsed {
+      green_node: block.replace_with(reparsed.green_node),
+      errors,
+      error_offsets,
+      options: self.options,
+    })
+  }
+}
+
+/// Replacement of a range of the source text
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TextEdit {
+  pub range: TextRange,
+  pub insert: String,
+}
+
+impl TextEdit {
+  /// Returns `text` modified by this edit.
+  pub fn apply(&self, text: &str) -> String {
+    let mut result = String::with_capacity(text.len() + self.insert.len());
+    result.push_str(&text[..self.range.start().to_usize()]);
+    result.push_str(&self.insert);
+    result.push_str(&text[self.range.end().to_usize()..]);
+    result
+  }
+
+  /// Applies this edit to `text`, a slice of the source starting at `offset`.
+  ///
+  /// Returns `None` if the edit is not inside `text`.
+  fn apply_in(&self, text: &str, offset: TextUnit) -> Option<String> {
+    let start = self.range.start().checked_sub(offset)?.to_usize();
+    let end = self.range.end().checked_sub(offset)?.to_usize();
+    if end > text.len() || !text.is_char_boundary(start) || !text.is_char_boundary(end) {
+      return None;
+    }
+    let mut result = String::with_capacity(text.len() + self.insert.len());
+    result.push_str(&text[..start]);
+    result.push_str(&self.insert);
+    result.push_str(&text[end..]);
+    Some(result)
+  }
+}
+
+/// Maps offsets around a replaced range to the text after the replacement
+struct OffsetMap {
+  old: TextRange,
+  new_end: TextUnit,
+}
+
+impl OffsetMap {
+  fn new(old: TextRange, new_len: TextUnit) -> Self {
+    Self {
+      old,
+      new_end: old.start() + new_len,
+    }
+  }
+
+  /// Returns `None` for offsets strictly inside the replaced range.
+  fn offset(&self, offset: TextUnit) -> Option<TextUnit> {
+    if offset <= self.old.start() {
+      Some(offset)
+    } else if offset >= self.old.end() {
+      Some(self.new_end + (offset - self.old.end()))
+    } else {
+      None
+    }
+  }
+
+  fn range(&self, range: TextRange) -> Option<TextRange> {
+    Some(TextRange::from_to(
+      self.offset(range.start())?,
+      self.offset(range.end())?,
+    ))
+  }
+}
+
+/// Error found while parsing
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SyntaxError {
+  pub kind: SyntaxErrorKind,
+  /// Range of the source text where the error was detected
+  pub range: TextRange,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SyntaxErrorKind {
+  /// A `switch` statement has more than one `default` clause
+  DuplicateDefaultClause,
+  /// A class file declares more than one class or interface
+  DuplicateTypeDecl,
+  /// The left-hand side of an assignment, or the target of a `for in` loop, is
+  /// neither an identifier nor a member access
+  InvalidAssignmentTarget,
+  /// A line terminator separates `throw` from its value
+  LineTerminatorAfterThrow,
+  /// A `try` statement has neither a `catch` nor a `finally` clause
+  MissingCatchOrFinally,
+  /// An expression is required, but the next token cannot start one
+  MissingExpression,
+  /// A method of a regular class has no body
+  MissingMethodBody,
+  /// A statement is required, but the next token cannot start one
+  MissingStatement,
+  /// A class file declares no class or interface
+  MissingTypeDecl,
+  /// A required token is missing
+  ///
+  /// The range is empty, at the end of the previous token.
+  MissingToken(SyntaxKind),
+  /// The `var` declaration of a `for in` loop has more than one declarator
+  MultipleForInDeclarators,
+  /// Statements or expressions are nested deeper than `ParseOptions::max_depth`
+  ///
+  /// The innermost construct is wrapped in a `NodeError`.
+  NestingTooDeep,
+  /// The syntax is only available in ActionScript 2: classes, interfaces,
+  /// imports and type annotations
+  RequiresAs2,
+  /// The syntax requires a SWF version at least equal to the provided one
+  RequiresSwfVersion(u8),
+  /// A statement is at the top level of a class file, outside of the class or interface
+  StatementOutsideClass,
+  /// A statement is at the top level of an instance script, outside of any event handler
+  StatementOutsideHandler,
+  /// An interface declares a member variable
+  UnexpectedInterfaceField,
+  /// A method of an interface or intrinsic class has a body
+  UnexpectedMethodBody,
+  /// Tokens could not be parsed and were wrapped in a `NodeError`
+  UnexpectedToken,
+  /// The event of an `on` or `onClipEvent` handler is not supported
+  UnknownEvent,
+  /// The key of a `keyPress` event is not supported
+  UnknownKey,
 }
 
 struct PeekableLexer<'text> {
   lexer: Lexer<'text>,
   /// Next non-trivia token, or None if at the end
   peeked: Option<LexerToken>,
-  /// Cached status of the kind of trivia in `trivia_buffer`:
-  /// - `None` indicates an empty `trivia_buffer`,
+  /// Cached status of the kind of unconsumed trivia:
+  /// - `None` indicates there is no trivia,
   /// - `Uniline` indicates there's only uniline trivia
   /// - `Multiline` otherwise (at least one multiline trivia)
   trivia_kind: TriviaKind,
-  /// Unconsumed trivia before `peeked`.
+  /// Unconsumed trivia before `peeked`, unless trivia is skipped.
   trivia_buffer: Vec<LexerToken>,
+  /// Length of the unconsumed trivia
+  trivia_len: TextUnit,
+  /// If `false`, trivia is only measured and never buffered.
+  keep_trivia: bool,
 }
 
 impl<'text> PeekableLexer<'text> {
@@ -44,32 +357,29 @@ impl<'text> PeekableLexer<'text> {
   ///
   /// This will initialize it eagerly by peeking into the inner lexer
   /// during initialization to find the next non-trivia token.
-  pub(crate) fn new(mut lexer: Lexer<'text>) -> Self {
-    let mut peeked: Option<LexerToken> = None;
-    let mut has_multiline_trivia: bool = false;
-    let mut trivia_buffer: Vec<LexerToken> = Vec::with_capacity(1);
-    while let Some(token) = lexer.next() {
-      if token.kind.is_trivia() {
-        has_multiline_trivia = has_multiline_trivia || token.kind.is_multiline_trivia();
-        trivia_buffer.push(token);
-      } else {
-        peeked = Some(token);
-        break;
-      }
-    }
-    let trivia_kind = if trivia_buffer.is_empty() {
-      TriviaKind::None
-    } else if has_multiline_trivia {
-      TriviaKind::Multiline
-    } else {
-      TriviaKind::Uniline
-    };
-    Self {
+  pub(crate) fn new(lexer: Lexer<'text>) -> Self {
+    Self::with_trivia(lexer, true)
+  }
+
+  /// Creates a new peekable lexer skipping trivia
+  ///
+  /// The kind and length of the trivia are still tracked, but the trivia
+  /// tokens are never sent to `eat_trivia` callbacks.
+  pub(crate) fn without_trivia(lexer: Lexer<'text>) -> Self {
+    Self::with_trivia(lexer, false)
+  }
+
+  fn with_trivia(lexer: Lexer<'text>, keep_trivia: bool) -> Self {
+    let mut peekable = Self {
       lexer,
-      peeked,
-      trivia_kind,
-      trivia_buffer,
-    }
+      peeked: None,
+      trivia_kind: TriviaKind::None,
+      trivia_buffer: Vec::with_capacity(if keep_trivia { 1 } else { 0 }),
+      trivia_len: TextUnit::from(0),
+      keep_trivia,
+    };
+    peekable.advance();
+    peekable
   }
 
   /// Peeks the next non-trivia token
@@ -91,14 +401,29 @@ impl<'text> PeekableLexer<'text> {
     self.peek().map(|token| token.kind)
   }
 
-  // /// Peeks the next non-trivia token and preceding trivia kind.
-  // ///
-  // /// No token is consumed.
-  // pub(crate) fn peek_with_trivia(&self) -> (TriviaKind, Option<&LexerToken>) {
-  //   (self.trivia_kind, self.peeked.as_ref())
-  // }
+  /// Peeks the next non-trivia token and preceding trivia kind.
+  ///
+  /// No token is consumed.
+  pub(crate) fn peek_with_trivia(&self) -> (TriviaKind, Option<&LexerToken>) {
+    (self.trivia_kind, self.peeked.as_ref())
+  }
+
+  /// Peeks the kind of the non-trivia token following the peeked token.
+  ///
+  /// This lexes ahead on a copy of the inner lexer, so it should only be used
+  /// for rare ambiguities.
+  pub(crate) fn peek_second_kind(&self) -> Option<SyntaxKind> {
+    self
+      .lexer
+      .clone()
+      .map(|token| token.kind)
+      .find(|kind| !kind.is_trivia())
+  }
 
-  pub(crate) fn eat_trivia<F>(&mut self, callback: &mut F)
+  /// Consumes the trivia before the peeked token and returns its length.
+  ///
+  /// The trivia tokens are passed to `callback`, unless trivia is skipped.
+  pub(crate) fn eat_trivia<F>(&mut self, callback: &mut F) -> TextUnit
   where
     F: FnMut(LexerToken),
   {
@@ -107,6 +432,7 @@ impl<'text> PeekableLexer<'text> {
       callback(trivia);
     }
     self.trivia_kind = TriviaKind::None;
+    mem::replace(&mut self.trivia_len, TextUnit::from(0))
   }
 
   /// Consumes to the next non-trivia token.
@@ -124,30 +450,197 @@ impl<'text> PeekableLexer<'text> {
       None => unreachable!("Precondition violation: peeked token must be defined"),
       Some(token) => token,
     };
+    self.advance();
+    token
+  }
+
+  /// Lexes the trivia up to the next non-trivia token, and peeks this token.
+  fn advance(&mut self) {
+    let mut has_trivia: bool = false;
     let mut has_multiline_trivia: bool = false;
     while let Some(token) = self.lexer.next() {
       if token.kind.is_trivia() {
+        has_trivia = true;
         has_multiline_trivia = has_multiline_trivia || token.kind.is_multiline_trivia();
-        self.trivia_buffer.push(token);
+        self.trivia_len += TextUnit::of_str(&token.text);
+        if self.keep_trivia {
+          self.trivia_buffer.push(token);
+        }
       } else {
         self.peeked = Some(token);
         break;
       }
     }
-    self.trivia_kind = if self.trivia_buffer.is_empty() {
+    self.trivia_kind = if !has_trivia {
       TriviaKind::None
     } else if has_multiline_trivia {
       TriviaKind::Multiline
     } else {
       TriviaKind::Uniline
     };
-    token
+  }
+}
+
+/// Default value of `ParseOptions::max_depth`
+pub const DEFAULT_MAX_DEPTH: usize = 256;
+
+/// ActionScript language level
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LanguageLevel {
+  /// Flash 4 actions: ActionScript 1 with target paths such as `/clip:var`
+  ///
+  /// Slash target paths are lexed instead of divisions wherever they may
+  /// start, so this level must be requested explicitly.
+  Flash4,
+  /// ActionScript 1, without classes or type annotations
+  As1,
+  /// ActionScript 2
+  As2,
+}
+
+/// Dialect of the parsed source
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dialect {
+  /// ActionScript as accepted by the Flash authoring tool
+  Standard,
+  /// Output of the JPEXS/FFDec decompiler
+  ///
+  /// It adds the pseudo-instructions `§§push(value);`, `§§pop()` and
+  /// `§§goto(label);`, and allows a label at the end of a block. Register
+  /// locals such as `_loc3_` are parsed as `NodeRegister` in expressions.
+  Decompiler,
+}
+
+/// Options controlling how a script is parsed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ParseOptions {
+  /// Version of the SWF file containing the script
+  ///
+  /// Syntax unsupported by the corresponding Flash Player is reported with
+  /// `SyntaxErrorKind::RequiresSwfVersion`.
+  pub swf_version: u8,
+  /// Syntax from ActionScript 2 is reported with `SyntaxErrorKind::RequiresAs2`
+  /// below ActionScript 2.
+  pub language: LanguageLevel,
+  /// Maximum nesting depth of statements and expressions
+  ///
+  /// Deeper constructs are reported with `SyntaxErrorKind::NestingTooDeep`
+  /// instead of exhausting the stack on adversarial input.
+  pub max_depth: usize,
+  /// Dialect of the source: standard ActionScript or decompiler output
+  pub dialect: Dialect,
+}
+
+impl ParseOptions {
+  /// Options for a Flash 4 script, with target paths
+  pub fn flash4() -> Self {
+    Self {
+      swf_version: 4,
+      language: LanguageLevel::Flash4,
+      ..Self::default()
+    }
+  }
+
+  /// Options for a script exported by the JPEXS/FFDec decompiler
+  pub fn decompiler() -> Self {
+    Self {
+      dialect: Dialect::Decompiler,
+      ..Self::default()
+    }
+  }
+
+  /// Returns `true` if Flash 4 target paths such as `/clip:var` are accepted.
+  pub fn has_target_paths(&self) -> bool {
+    self.language == LanguageLevel::Flash4
+  }
+
+  /// Returns `true` if identifiers are case-sensitive.
+  ///
+  /// Below SWF 7, Flash Player resolves identifiers case-insensitively: `foo`
+  /// and `FOO` are the same variable. The parser then recognizes the constants
+  /// `undefined` and `newline`, and decompiler register locals, in any case.
+  /// Keywords stay case-sensitive and identifiers keep their spelling in the
+  /// trees: resolving other names is left to the consumer.
+  pub fn is_case_sensitive(&self) -> bool {
+    self.swf_version >= 7
+  }
+
+  /// Returns the name as compared by Flash Player: lowercased below SWF 7.
+  fn fold_case<'name>(&self, name: &'name str) -> Cow<'name, str> {
+    if self.is_case_sensitive() {
+      Cow::Borrowed(name)
+    } else {
+      Cow::Owned(name.to_ascii_lowercase())
+    }
+  }
+}
+
+impl Default for ParseOptions {
+  fn default() -> Self {
+    Self {
+      swf_version: 8,
+      language: LanguageLevel::As2,
+      max_depth: DEFAULT_MAX_DEPTH,
+      dialect: Dialect::Standard,
+    }
+  }
+}
+
+/// Sink building the rowan tree of `Parsed`
+#[derive(Default)]
+struct GreenSink {
+  builder: rowan::GreenNodeBuilder<'static>,
+  /// Length of the text received so far
+  offset: TextUnit,
+  errors: Vec<SyntaxError>,
+  error_offsets: Vec<TextUnit>,
+}
+
+impl TreeSink for GreenSink {
+  fn start_node(&mut self, kind: SyntaxKind) {
+    self.builder.start_node(kind.into());
+  }
+
+  fn token(&mut self, kind: SyntaxKind, text: SmolStr) {
+    self.offset += TextUnit::of_str(&text);
+    self.builder.token(kind.into(), text);
+  }
+
+  fn finish_node(&mut self) {
+    self.builder.finish_node();
+  }
+
+  fn error(&mut self, error: SyntaxError) {
+    self.errors.push(error);
+    self.error_offsets.push(self.offset);
   }
 }
 
 struct Parser<'text> {
   lexer: PeekableLexer<'text>,
-  builder: rowan::GreenNodeBuilder<'static>,
+  events: Vec<Event>,
+  /// Offset of the end of the last consumed token
+  offset: TextUnit,
+  /// Number of statements and expressions currently being parsed
+  depth: usize,
+  options: ParseOptions,
+}
+
+/// Position in the events where a node may be started later
+///
+/// This allows to wrap an already parsed element, such as the left operand of
+/// a binary expression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct Checkpoint(usize);
+
+/// Kind of declaration containing class members
+///
+/// It decides which members are valid, and whether methods have a body.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum MemberContext {
+  Class,
+  IntrinsicClass,
+  Interface,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -158,105 +651,1453 @@ enum TriviaKind {
 }
 
 impl<'text> Parser<'text> {
+  fn new(lexer: Lexer<'text>, options: &ParseOptions) -> Self {
+    Self::with_lexer(PeekableLexer::new(lexer), options)
+  }
+
+  /// Creates a parser skipping trivia: no trivia token is recorded.
+  ///
+  /// The recorded events do not cover the whole text, so they can't be used to
+  /// build a lossless tree.
+  fn without_trivia(lexer: Lexer<'text>, options: &ParseOptions) -> Self {
+    Self::with_lexer(PeekableLexer::without_trivia(lexer), options)
+  }
+
+  fn with_lexer(lexer: PeekableLexer<'text>, options: &ParseOptions) -> Self {
+    Self {
+      lexer,
+      events: Vec::new(),
+      offset: TextUnit::from(0),
+      depth: 0,
+      options: *options,
+    }
+  }
+
   /// Consume tokens while they are trivia
   fn eat_trivia(&mut self) {
-    let lexer = &mut self.lexer;
-    let builder = &mut self.builder;
-    lexer.eat_trivia(&mut |token| builder.token(token.kind.into(), token.text))
+    let events = &mut self.events;
+    self.offset += self.lexer.eat_trivia(&mut |token| {
+      events.push(Event::Token {
+        kind: token.kind,
+        text: token.text,
+      })
+    });
+  }
+
+  fn start_node(&mut self, kind: SyntaxKind) {
+    self.events.push(Event::Start { kind, inner: None });
+  }
+
+  fn finish_node(&mut self) {
+    self.events.push(Event::Finish);
+  }
+
+  fn checkpoint(&mut self) -> Checkpoint {
+    let checkpoint = Checkpoint(self.events.len());
+    self.events.push(Event::Tombstone);
+    checkpoint
+  }
+
+  /// Starts a node wrapping all the elements added since the checkpoint.
+  ///
+  /// If a node was already started at this checkpoint, it is moved to the end
+  /// of the events and nested in the new node. This keeps long chains of
+  /// left-associative operators linear.
+  fn start_node_at(&mut self, checkpoint: Checkpoint, kind: SyntaxKind) {
+    let inner = match mem::replace(&mut self.events[checkpoint.0], Event::Tombstone) {
+      Event::Tombstone => None,
+      start => {
+        self.events.push(start);
+        Some(self.events.len() - 1)
+      }
+    };
+    self.events[checkpoint.0] = Event::Start { kind, inner };
+  }
+
+  fn script(&mut self) {
+    self.start_node(SyntaxKind::NodeScript);
+    self.stmt_list(None);
+    self.finish_node();
+  }
+
+  /// Parses a standalone expression.
+  fn expr_root(&mut self) {
+    self.start_node(SyntaxKind::NodeExprRoot);
+    self.expr(false);
+    self.end_of_input();
+    self.finish_node();
+  }
+
+  /// Parses a standalone statement.
+  fn stmt_root(&mut self) {
+    self.start_node(SyntaxKind::NodeStmtRoot);
+    self.eat_trivia();
+    self.stmt();
+    self.end_of_input();
+    self.finish_node();
+  }
+
+  /// Parses a class file: imports followed by a single class or interface.
+  fn class_file(&mut self) {
+    self.start_node(SyntaxKind::NodeClassFile);
+    self.eat_trivia();
+    let mut has_type_decl = false;
+    while let Some(kind) = self.lexer.peek_kind() {
+      let start = self.offset;
+      match kind {
+        SyntaxKind::TokenImport => self.import_decl(),
+        SyntaxKind::TokenClass
+        | SyntaxKind::TokenDynamic
+        | SyntaxKind::TokenInterface
+        | SyntaxKind::TokenIntrinsic
+        | SyntaxKind::TokenOpenBracket => {
+          self.type_decl();
+          if has_type_decl {
+            self.error(
+              SyntaxErrorKind::DuplicateTypeDecl,
+              TextRange::from_to(start, self.offset),
+            );
+          }
+          has_type_decl = true;
+        }
+        SyntaxKind::TokenCloseBrace => self.error_stmt(),
+        _ => {
+          self.stmt();
+          self.error(
+            SyntaxErrorKind::StatementOutsideClass,
+            TextRange::from_to(start, self.offset),
+          );
+        }
+      }
+      self.eat_trivia();
+    }
+    if !has_type_decl {
+      self.error(
+        SyntaxErrorKind::MissingTypeDecl,
+        TextRange::offset_len(self.offset, 0.into()),
+      );
+    }
+    self.finish_node();
+  }
+
+  /// Wraps any remaining tokens in a `NodeError`.
+  ///
+  /// This is used by entry points expecting a single element.
+  fn end_of_input(&mut self) {
+    if self.next_kind().is_none() {
+      self.eat_trivia();
+      return;
+    }
+    self.eat_trivia();
+    let start = self.offset;
+    self.start_node(SyntaxKind::NodeError);
+    while self.lexer.peek().is_some() {
+      self.bump();
+      self.eat_trivia();
+    }
+    self.finish_node();
+    self.error(SyntaxErrorKind::UnexpectedToken, TextRange::from_to(start, self.offset));
+  }
+
+  fn finish(self) -> Parsed {
+    let mut sink = GreenSink::default();
+    let options = self.options;
+    self.finish_into(&mut sink);
+    Parsed {
+      green_node: sink.builder.finish(),
+      errors: sink.errors,
+      error_offsets: sink.error_offsets,
+      options,
+    }
+  }
+
+  /// Sends the recorded events to a sink.
+  fn finish_into<S: TreeSink + ?Sized>(self, sink: &mut S) {
+    event::process(self.events, sink)
+  }
+
+  /// Parses a block spanning the whole input, for incremental reparsing.
+  ///
+  /// Returns `None` if the block does not end with a closing brace at the end
+  /// of the input.
+  fn block_root(mut self) -> Option<Parsed> {
+    self.block();
+    if self.lexer.peek_with_trivia() != (TriviaKind::None, None) {
+      return None;
+    }
+    let parsed = self.finish();
+    match parsed.syntax().last_child_or_token() {
+      Some(rowan::NodeOrToken::Token(token)) if token.kind() == SyntaxKind::TokenCloseBrace => Some(parsed),
+      _ => None,
+    }
+  }
+
+  /// Parses a script attached to a button or movie clip instance.
+  ///
+  /// Its top level must only contain `on` or `onClipEvent` handlers.
+  fn instance_script(&mut self) {
+    self.start_node(SyntaxKind::NodeInstanceScript);
+    self.eat_trivia();
+    while let Some(token) = self.lexer.peek() {
+      match (token.kind, token.text.as_str()) {
+        (SyntaxKind::TokenIdent, "on") => self.event_handler(SyntaxKind::NodeOnHandler, SyntaxKind::TokenOn),
+        (SyntaxKind::TokenIdent, "onClipEvent") => {
+          self.event_handler(SyntaxKind::NodeClipEventHandler, SyntaxKind::TokenOnClipEvent)
+        }
+        (SyntaxKind::TokenCloseBrace, _) => self.error_stmt(),
+        _ => {
+          let start = self.offset;
+          self.stmt();
+          self.error(
+            SyntaxErrorKind::StatementOutsideHandler,
+            TextRange::from_to(start, self.offset),
+          );
+        }
+      }
+      self.eat_trivia();
+    }
+    self.finish_node();
+  }
+
+  fn stmt_list(&mut self, end: Option<SyntaxKind>) {
+    self.eat_trivia();
+    while let Some(kind) = self.lexer.peek_kind() {
+      match kind {
+        _ if Some(kind) == end => break,
+        // Unbalanced closing brace
+        SyntaxKind::TokenCloseBrace => self.error_stmt(),
+        _ => self.stmt(),
+      }
+      self.eat_trivia();
+    }
+  }
+
+  /// Parses a statement.
+  ///
+  /// If the next token cannot start a statement, it is wrapped in a `NodeError`
+  /// with the following tokens, until the parser can resynchronize. Nothing is
+  /// consumed at `}` or at the end of the input.
+  fn stmt(&mut self) {
+    let first = match self.lexer.peek() {
+      None => {
+        self.error(
+          SyntaxErrorKind::MissingStatement,
+          TextRange::offset_len(self.offset, 0.into()),
+        );
+        return;
+      }
+      Some(token) => token,
+    };
+    if self.depth >= self.options.max_depth && is_stmt_start(first.kind) {
+      self.too_deep(false);
+      return;
+    }
+    self.depth += 1;
+    match first.kind {
+      SyntaxKind::TokenBreak => self.jump_stmt(SyntaxKind::NodeBreak),
+      SyntaxKind::TokenClass
+      | SyntaxKind::TokenDynamic
+      | SyntaxKind::TokenInterface
+      | SyntaxKind::TokenIntrinsic
+      | SyntaxKind::TokenOpenBracket => self.type_decl(),
+      SyntaxKind::TokenContinue => self.jump_stmt(SyntaxKind::NodeContinue),
+      SyntaxKind::TokenDo => self.do_while_stmt(),
+      SyntaxKind::TokenFor => self.for_stmt(),
+      SyntaxKind::TokenGoto => self.goto_stmt(),
+      SyntaxKind::TokenIf => self.if_stmt(),
+      SyntaxKind::TokenIfFrameLoaded => self.scoped_stmt(SyntaxKind::NodeIfFrameLoaded, true),
+      SyntaxKind::TokenImport => self.import_decl(),
+      SyntaxKind::TokenInclude => self.include_directive(),
+      SyntaxKind::TokenOpenBrace => self.block(),
+      SyntaxKind::TokenPush => self.push_stmt(),
+      SyntaxKind::TokenReturn => self.return_stmt(),
+      SyntaxKind::TokenSemicolon => self.empty_stmt(),
+      SyntaxKind::TokenSwitch => self.switch_stmt(),
+      SyntaxKind::TokenTellTarget => self.scoped_stmt(SyntaxKind::NodeTellTarget, true),
+      SyntaxKind::TokenThrow => self.throw_stmt(),
+      SyntaxKind::TokenTry => self.try_stmt(),
+      SyntaxKind::TokenVar => self.var_stmt(),
+      SyntaxKind::TokenWhile => self.while_stmt(),
+      SyntaxKind::TokenWith => self.scoped_stmt(SyntaxKind::NodeWith, false),
+      kind if is_expr_start(kind) => self.expr_stmt(),
+      SyntaxKind::TokenCloseBrace => self.error(
+        SyntaxErrorKind::MissingStatement,
+        TextRange::offset_len(self.offset, 0.into()),
+      ),
+      _ => self.error_stmt(),
+    }
+    self.depth -= 1;
+  }
+
+  /// Wraps tokens that cannot start a statement in a `NodeError`.
+  ///
+  /// At least one token is consumed. Parsing resynchronizes at `;`, `}` or at
+  /// the next token starting a statement.
+  fn error_stmt(&mut self) {
+    let start = self.offset;
+    self.start_node(SyntaxKind::NodeError);
+    self.bump();
+    while let Some(kind) = self.next_kind() {
+      if kind == SyntaxKind::TokenCloseBrace || is_stmt_start(kind) {
+        break;
+      }
+      self.eat_trivia();
+      self.bump();
+    }
+    self.finish_node();
+    self.error(SyntaxErrorKind::UnexpectedToken, TextRange::from_to(start, self.offset));
+  }
+
+  /// Wraps a statement or expression nested deeper than the maximum depth in a
+  /// `NodeError`.
+  ///
+  /// Tokens are consumed iteratively while keeping brackets balanced, so this
+  /// never recurses. For a statement, it stops before an unbalanced closing
+  /// bracket or after `;`. For an expression, it also stops before `,`, `:`
+  /// or `;`. The next token must not be a closing bracket or separator, so at
+  /// least one token is consumed.
+  fn too_deep(&mut self, is_expr: bool) {
+    self.eat_trivia();
+    let start = self.offset;
+    self.start_node(SyntaxKind::NodeError);
+    let mut balance: usize = 0;
+    while let Some(kind) = self.next_kind() {
+      match kind {
+        SyntaxKind::TokenOpenBrace | SyntaxKind::TokenOpenBracket | SyntaxKind::TokenOpenParen => balance += 1,
+        SyntaxKind::TokenCloseBrace | SyntaxKind::TokenCloseBracket | SyntaxKind::TokenCloseParen => {
+          if balance == 0 {
+            break;
+          }
+          balance -= 1;
+        }
+        SyntaxKind::TokenComma | SyntaxKind::TokenColon if is_expr && balance == 0 => break,
+        SyntaxKind::TokenSemicolon if balance == 0 => {
+          if !is_expr {
+            self.eat_trivia();
+            self.bump();
+          }
+          break;
+        }
+        _ => {}
+      }
+      self.eat_trivia();
+      self.bump();
+    }
+    self.finish_node();
+    self.error(SyntaxErrorKind::NestingTooDeep, TextRange::from_to(start, self.offset));
+  }
+
+  /// Parses a labelled statement or an expression statement.
+  ///
+  /// Both may start with an identifier: it is a label if it is the whole
+  /// expression and is followed by a colon.
+  fn expr_stmt(&mut self) {
+    let cp = self.checkpoint();
+    let label_end = match self.lexer.peek() {
+      Some(token) if token.kind == SyntaxKind::TokenIdent => Some(self.offset + TextUnit::of_str(&token.text)),
+      _ => None,
+    };
+    self.expr(false);
+    if label_end == Some(self.offset) && self.next_kind() == Some(SyntaxKind::TokenColon) {
+      self.start_node_at(cp, SyntaxKind::NodeLabelled);
+      self.expect(SyntaxKind::TokenColon);
+      match self.next_kind() {
+        // Decompiled jump targets may end a block: they label an empty statement
+        Some(SyntaxKind::TokenCloseBrace) | None if self.options.dialect == Dialect::Decompiler => {
+          self.start_node(SyntaxKind::NodeEmptyStmt);
+          self.finish_node();
+        }
+        _ => {
+          self.eat_trivia();
+          self.stmt();
+        }
+      }
+    } else {
+      self.start_node_at(cp, SyntaxKind::NodeStatement);
+      self.semicolon();
+    }
+    self.finish_node();
+  }
+
+  fn empty_stmt(&mut self) {
+    self.start_node(SyntaxKind::NodeEmptyStmt);
+    self.expect(SyntaxKind::TokenSemicolon);
+    self.finish_node();
+  }
+
+  /// Parses a block statement.
+  ///
+  /// If the opening brace is missing, the block is reported and left empty.
+  fn block(&mut self) {
+    self.start_node(SyntaxKind::NodeBlock);
+    if self.expect(SyntaxKind::TokenOpenBrace) {
+      self.stmt_list(Some(SyntaxKind::TokenCloseBrace));
+      self.expect(SyntaxKind::TokenCloseBrace);
+    }
+    self.finish_node();
+  }
+
+  fn if_stmt(&mut self) {
+    self.start_node(SyntaxKind::NodeIf);
+    self.expect(SyntaxKind::TokenIf);
+    self.expect(SyntaxKind::TokenOpenParen);
+    self.expr(false);
+    self.expect(SyntaxKind::TokenCloseParen);
+    self.eat_trivia();
+    self.stmt();
+    // The `else` branch is bound to the closest `if`: trailing trivia is only
+    // consumed inside this node if it is followed by `else`.
+    if self.next_kind() == Some(SyntaxKind::TokenElse) {
+      self.expect(SyntaxKind::TokenElse);
+      self.eat_trivia();
+      self.stmt();
+    }
+    self.finish_node();
+  }
+
+  fn var_stmt(&mut self) {
+    self.start_node(SyntaxKind::NodeVarDecl);
+    self.var_declarators(false);
+    self.semicolon();
+    self.finish_node();
+  }
+
+  /// Parses the `var` keyword and the following declarators.
+  /// Parses `var` and its declarators, returning the number of declarators.
+  fn var_declarators(&mut self, no_in: bool) -> usize {
+    self.expect(SyntaxKind::TokenVar);
+    let mut count = 0;
+    loop {
+      count += 1;
+      self.eat_trivia();
+      self.start_node(SyntaxKind::NodeVarDeclarator);
+      self.ident();
+      if self.next_kind() == Some(SyntaxKind::TokenColon) {
+        self.type_annotation();
+      }
+      if self.next_kind() == Some(SyntaxKind::TokenEq) {
+        self.expect(SyntaxKind::TokenEq);
+        self.assign_expr(no_in);
+      }
+      self.finish_node();
+      if self.next_kind() != Some(SyntaxKind::TokenComma) {
+        break;
+      }
+      self.expect(SyntaxKind::TokenComma);
+    }
+    count
+  }
+
+  fn while_stmt(&mut self) {
+    self.start_node(SyntaxKind::NodeWhile);
+    self.expect(SyntaxKind::TokenWhile);
+    self.expect(SyntaxKind::TokenOpenParen);
+    self.expr(false);
+    self.expect(SyntaxKind::TokenCloseParen);
+    self.eat_trivia();
+    self.stmt();
+    self.finish_node();
+  }
+
+  fn do_while_stmt(&mut self) {
+    self.start_node(SyntaxKind::NodeDoWhile);
+    self.expect(SyntaxKind::TokenDo);
+    self.eat_trivia();
+    self.stmt();
+    self.expect(SyntaxKind::TokenWhile);
+    self.expect(SyntaxKind::TokenOpenParen);
+    self.expr(false);
+    self.expect(SyntaxKind::TokenCloseParen);
+    // A semicolon is always inserted after `do ... while (test)` if missing
+    if self.next_kind() == Some(SyntaxKind::TokenSemicolon) {
+      self.expect(SyntaxKind::TokenSemicolon);
+    } else {
+      self.insert_semicolon();
+    }
+    self.finish_node();
+  }
+
+  /// Parses a `for` or `for in` loop.
+  ///
+  /// The head is parsed in a no-`in` context until we know which kind of loop
+  /// it is: `for (a in b)` is a `for in` loop, while `in` has to be nested
+  /// (e.g. in call arguments) to be used in the initializer of a `for` loop.
+  fn for_stmt(&mut self) {
+    let cp = self.checkpoint();
+    self.expect(SyntaxKind::TokenFor);
+    self.expect(SyntaxKind::TokenOpenParen);
+    self.eat_trivia();
+    let head_start = self.offset;
+    // Number of declarators if the head is a `var` declaration, otherwise
+    // node kind of the head expression (`None` if it is missing)
+    let mut declarators: usize = 0;
+    let mut head_kind: Option<SyntaxKind> = None;
+    match self.lexer.peek_kind() {
+      Some(SyntaxKind::TokenSemicolon) => {}
+      Some(SyntaxKind::TokenVar) => {
+        self.start_node(SyntaxKind::NodeVarDecl);
+        declarators = self.var_declarators(true);
+        self.finish_node();
+      }
+      _ => head_kind = self.expr(true),
+    }
+    if self.next_kind() == Some(SyntaxKind::TokenIn) {
+      // The head is reinterpreted as the loop target, like the left-hand side
+      // of an assignment.
+      let range = TextRange::from_to(head_start, self.offset);
+      if declarators > 1 {
+        self.error(SyntaxErrorKind::MultipleForInDeclarators, range);
+      }
+      if let Some(head_kind) = head_kind {
+        if !is_pattern(head_kind) {
+          self.error(SyntaxErrorKind::InvalidAssignmentTarget, range);
+        }
+      }
+      self.start_node_at(cp, SyntaxKind::NodeForIn);
+      self.expect(SyntaxKind::TokenIn);
+      self.expr(false);
+    } else {
+      self.start_node_at(cp, SyntaxKind::NodeFor);
+      self.expect(SyntaxKind::TokenSemicolon);
+      if self.next_kind() != Some(SyntaxKind::TokenSemicolon) {
+        self.expr(false);
+      }
+      self.expect(SyntaxKind::TokenSemicolon);
+      if self.next_kind() != Some(SyntaxKind::TokenCloseParen) {
+        self.expr(false);
+      }
+    }
+    self.expect(SyntaxKind::TokenCloseParen);
+    self.eat_trivia();
+    self.stmt();
+    self.finish_node();
+  }
+
+  fn switch_stmt(&mut self) {
+    self.start_node(SyntaxKind::NodeSwitch);
+    self.expect(SyntaxKind::TokenSwitch);
+    self.expect(SyntaxKind::TokenOpenParen);
+    self.expr(false);
+    self.expect(SyntaxKind::TokenCloseParen);
+    self.expect(SyntaxKind::TokenOpenBrace);
+    let mut has_default = false;
+    loop {
+      self.eat_trivia();
+      match self.lexer.peek_kind() {
+        Some(SyntaxKind::TokenCase) => {
+          self.start_node(SyntaxKind::NodeCase);
+          self.bump();
+          self.expr(false);
+          self.case_body();
+          self.finish_node();
+        }
+        Some(SyntaxKind::TokenDefault) => {
+          self.start_node(SyntaxKind::NodeCase);
+          let range = self.bump_range();
+          if has_default {
+            self.error(SyntaxErrorKind::DuplicateDefaultClause, range);
+          }
+          has_default = true;
+          self.case_body();
+          self.finish_node();
+        }
+        _ => break,
+      }
+    }
+    self.expect(SyntaxKind::TokenCloseBrace);
+    self.finish_node();
+  }
+
+  /// Parses a `break` or `continue` statement, with its optional label.
+  ///
+  /// This is a restricted production: a line terminator after the keyword
+  /// ends the statement.
+  fn jump_stmt(&mut self, node_kind: SyntaxKind) {
+    self.start_node(node_kind);
+    self.bump();
+    if self.lexer.peek_with_trivia().0 == TriviaKind::Multiline {
+      self.insert_semicolon();
+    } else {
+      if self.next_kind() == Some(SyntaxKind::TokenIdent) {
+        self.eat_trivia();
+        self.ident();
+      }
+      self.semicolon();
+    }
+    self.finish_node();
+  }
+
+  /// Parses the decompiler pseudo-instruction `§§push(value);`.
+  fn push_stmt(&mut self) {
+    self.start_node(SyntaxKind::NodePush);
+    self.bump();
+    self.expect(SyntaxKind::TokenOpenParen);
+    self.expr(false);
+    self.expect(SyntaxKind::TokenCloseParen);
+    self.semicolon();
+    self.finish_node();
+  }
+
+  /// Parses the decompiler pseudo-instruction `§§goto(label);`.
+  fn goto_stmt(&mut self) {
+    self.start_node(SyntaxKind::NodeGoto);
+    self.bump();
+    self.expect(SyntaxKind::TokenOpenParen);
+    self.ident();
+    self.expect(SyntaxKind::TokenCloseParen);
+    self.semicolon();
+    self.finish_node();
+  }
+
+  /// Parses a `return` statement, with its optional value.
+  ///
+  /// This is a restricted production: a line terminator after `return` ends
+  /// the statement.
+  fn return_stmt(&mut self) {
+    self.start_node(SyntaxKind::NodeReturn);
+    self.bump();
+    if self.lexer.peek_with_trivia().0 == TriviaKind::Multiline {
+      self.insert_semicolon();
+    } else {
+      if self.next_kind().map_or(false, is_expr_start) {
+        self.expr(false);
+      }
+      self.semicolon();
+    }
+    self.finish_node();
+  }
+
+  /// Parses a statement with a parenthesized head and a body:
+  /// `with`, `tellTarget` or `ifFrameLoaded`.
+  ///
+  /// If `allow_scene` is true, the head may contain two comma-separated
+  /// values: the first one is the scene.
+  fn scoped_stmt(&mut self, node_kind: SyntaxKind, allow_scene: bool) {
+    self.start_node(node_kind);
+    self.bump();
+    self.expect(SyntaxKind::TokenOpenParen);
+    if allow_scene {
+      self.assign_expr(false);
+      if self.next_kind() == Some(SyntaxKind::TokenComma) {
+        self.expect(SyntaxKind::TokenComma);
+        self.assign_expr(false);
+      }
+    } else {
+      self.expr(false);
+    }
+    self.expect(SyntaxKind::TokenCloseParen);
+    self.eat_trivia();
+    self.stmt();
+    self.finish_node();
+  }
+
+  /// Parses a `throw` statement.
+  ///
+  /// This is a restricted production: there must be no line terminator
+  /// between `throw` and its value.
+  fn throw_stmt(&mut self) {
+    self.start_node(SyntaxKind::NodeThrow);
+    let range = self.bump_range();
+    self.require_swf_version(7, range);
+    if self.lexer.peek_with_trivia().0 == TriviaKind::Multiline {
+      self.error(SyntaxErrorKind::LineTerminatorAfterThrow, range);
+    }
+    self.expr(false);
+    self.semicolon();
+    self.finish_node();
+  }
+
+  /// Parses a `try` statement.
+  ///
+  /// AS2 allows multiple `catch` clauses, selected by the type annotation of
+  /// their parameter.
+  fn try_stmt(&mut self) {
+    self.start_node(SyntaxKind::NodeTry);
+    let range = self.bump_range();
+    self.require_swf_version(7, range);
+    self.eat_trivia();
+    self.block();
+    let mut has_handler = false;
+    while self.next_kind() == Some(SyntaxKind::TokenCatch) {
+      self.eat_trivia();
+      self.start_node(SyntaxKind::NodeCatch);
+      self.bump();
+      self.expect(SyntaxKind::TokenOpenParen);
+      self.eat_trivia();
+      self.ident();
+      if self.next_kind() == Some(SyntaxKind::TokenColon) {
+        self.type_annotation();
+      }
+      self.expect(SyntaxKind::TokenCloseParen);
+      self.eat_trivia();
+      self.block();
+      self.finish_node();
+      has_handler = true;
+    }
+    if self.next_kind() == Some(SyntaxKind::TokenFinally) {
+      self.eat_trivia();
+      self.start_node(SyntaxKind::NodeFinally);
+      self.bump();
+      self.eat_trivia();
+      self.block();
+      self.finish_node();
+      has_handler = true;
+    }
+    if !has_handler {
+      self.error(SyntaxErrorKind::MissingCatchOrFinally, range);
+    }
+    self.finish_node();
+  }
+
+  /// Parses an `on` or `onClipEvent` handler.
+  ///
+  /// The contextual keyword is lexed as an identifier and gets relabelled here.
+  fn event_handler(&mut self, node_kind: SyntaxKind, keyword: SyntaxKind) {
+    self.start_node(node_kind);
+    self.bump_as(keyword);
+    self.expect(SyntaxKind::TokenOpenParen);
+    loop {
+      self.eat_trivia();
+      self.event(node_kind == SyntaxKind::NodeOnHandler);
+      if self.next_kind() != Some(SyntaxKind::TokenComma) {
+        break;
+      }
+      self.expect(SyntaxKind::TokenComma);
+    }
+    self.expect(SyntaxKind::TokenCloseParen);
+    self.eat_trivia();
+    self.block();
+    self.finish_node();
+  }
+
+  /// Parses a single event of a handler: `press` or `keyPress "<Enter>"`.
+  fn event(&mut self, is_button: bool) {
+    self.start_node(SyntaxKind::NodeEvent);
+    let name = match self.lexer.peek() {
+      Some(token) if token.kind == SyntaxKind::TokenIdent => token.text.clone(),
+      _ => {
+        self.expect(SyntaxKind::TokenIdent);
+        self.finish_node();
+        return;
+      }
+    };
+    let range = self.bump_range();
+    if is_button && name == "keyPress" {
+      self.eat_trivia();
+      let key = match self.lexer.peek() {
+        Some(token) if token.kind == SyntaxKind::TokenStrLit => token.text.clone(),
+        _ => {
+          self.expect(SyntaxKind::TokenStrLit);
+          self.finish_node();
+          return;
+        }
+      };
+      let range = self.bump_range();
+      // The literal is checked without its quotes
+      if ButtonEvent::key_code(&key[1..key.len() - 1]).is_none() {
+        self.error(SyntaxErrorKind::UnknownKey, range);
+      }
+    } else {
+      let is_known = if is_button {
+        ButtonEvent::from_name(&name).is_some()
+      } else {
+        ClipEvent::from_name(&name).is_some()
+      };
+      if !is_known {
+        self.error(SyntaxErrorKind::UnknownEvent, range);
+      }
+    }
+    self.finish_node();
+  }
+
+  /// Parses a class or interface declaration, with its metadata.
+  ///
+  /// At the start of a statement, an opening bracket is always parsed as
+  /// metadata: array literals are not supported there.
+  fn type_decl(&mut self) {
+    let cp = self.checkpoint();
+    let start = self.offset;
+    self.metadata_list();
+    if self.lexer.peek_kind() == Some(SyntaxKind::TokenInterface) {
+      self.interface_decl(cp);
+    } else {
+      self.class_decl(cp);
+    }
+    self.require_as2(TextRange::from_to(start, self.offset));
+  }
+
+  fn class_decl(&mut self, cp: Checkpoint) {
+    self.start_node_at(cp, SyntaxKind::NodeClass);
+    let mut context = MemberContext::Class;
+    while let Some(kind @ SyntaxKind::TokenDynamic) | Some(kind @ SyntaxKind::TokenIntrinsic) = self.lexer.peek_kind() {
+      if kind == SyntaxKind::TokenIntrinsic {
+        context = MemberContext::IntrinsicClass;
+      }
+      self.bump();
+      self.eat_trivia();
+    }
+    self.expect(SyntaxKind::TokenClass);
+    self.eat_trivia();
+    self.qualified_name(false);
+    if self.next_kind() == Some(SyntaxKind::TokenExtends) {
+      self.eat_trivia();
+      self.start_node(SyntaxKind::NodeExtends);
+      self.bump();
+      self.eat_trivia();
+      self.qualified_name(false);
+      self.finish_node();
+    }
+    if self.next_kind() == Some(SyntaxKind::TokenImplements) {
+      self.eat_trivia();
+      self.start_node(SyntaxKind::NodeImplements);
+      self.bump();
+      loop {
+        self.eat_trivia();
+        self.qualified_name(false);
+        if self.next_kind() != Some(SyntaxKind::TokenComma) {
+          break;
+        }
+        self.expect(SyntaxKind::TokenComma);
+      }
+      self.finish_node();
+    }
+    self.class_body(context);
+    self.finish_node();
+  }
+
+  /// Parses an interface declaration.
+  ///
+  /// Its members are method signatures, without bodies.
+  fn interface_decl(&mut self, cp: Checkpoint) {
+    self.start_node_at(cp, SyntaxKind::NodeInterface);
+    self.expect(SyntaxKind::TokenInterface);
+    self.eat_trivia();
+    self.qualified_name(false);
+    if self.next_kind() == Some(SyntaxKind::TokenExtends) {
+      self.eat_trivia();
+      self.start_node(SyntaxKind::NodeExtends);
+      self.bump();
+      self.eat_trivia();
+      self.qualified_name(false);
+      self.finish_node();
+    }
+    self.class_body(MemberContext::Interface);
+    self.finish_node();
+  }
+
+  /// Parses the members of a class or interface, between braces.
+  fn class_body(&mut self, context: MemberContext) {
+    if !self.expect(SyntaxKind::TokenOpenBrace) {
+      return;
+    }
+    self.eat_trivia();
+    while let Some(kind) = self.lexer.peek_kind() {
+      if kind == SyntaxKind::TokenCloseBrace {
+        break;
+      }
+      self.class_member(context);
+      self.eat_trivia();
+    }
+    self.expect(SyntaxKind::TokenCloseBrace);
+  }
+
+  /// Parses a member variable or method of a class, with its modifiers.
+  ///
+  /// The node kind is only known after the modifiers, so it is started at a
+  /// checkpoint.
+  fn class_member(&mut self, context: MemberContext) {
+    let cp = self.checkpoint();
+    let member_start = self.offset;
+    self.metadata_list();
+    let start = self.offset;
+    while let Some(SyntaxKind::TokenStatic) | Some(SyntaxKind::TokenPublic) | Some(SyntaxKind::TokenPrivate) =
+      self.lexer.peek_kind()
+    {
+      self.bump();
+      self.eat_trivia();
+    }
+    match self.lexer.peek_kind() {
+      Some(SyntaxKind::TokenVar) => {
+        self.start_node_at(cp, SyntaxKind::NodeField);
+        self.var_declarators(false);
+        self.semicolon();
+        self.finish_node();
+        if context == MemberContext::Interface {
+          self.error(
+            SyntaxErrorKind::UnexpectedInterfaceField,
+            TextRange::from_to(start, self.offset),
+          );
+        }
+      }
+      Some(SyntaxKind::TokenFunction) => {
+        self.start_node_at(cp, SyntaxKind::NodeMethod);
+        self.method(context == MemberContext::Class);
+        self.finish_node();
+      }
+      // Skip to the next member. Metadata or modifiers may already have been
+      // consumed, otherwise the unexpected token is not a closing brace.
+      _ => {
+        self.start_node_at(cp, SyntaxKind::NodeError);
+        while let Some(kind) = self.next_kind() {
+          if self.offset > member_start && (kind == SyntaxKind::TokenCloseBrace || is_member_start(kind)) {
+            break;
+          }
+          self.eat_trivia();
+          self.bump();
+        }
+        self.finish_node();
+        self.error(
+          SyntaxErrorKind::UnexpectedToken,
+          TextRange::from_to(member_start, self.offset),
+        );
+      }
+    }
+  }
+
+  /// Parses the metadata tags before a declaration, if any.
+  fn metadata_list(&mut self) {
+    while self.lexer.peek_kind() == Some(SyntaxKind::TokenOpenBracket) {
+      self.metadata();
+      self.eat_trivia();
+    }
+  }
+
+  /// Parses a metadata tag: `[Name]` or `[Name(arg, key=value)]`
+  fn metadata(&mut self) {
+    self.start_node(SyntaxKind::NodeMetadata);
+    self.bump();
+    self.expect(SyntaxKind::TokenIdent);
+    if self.next_kind() == Some(SyntaxKind::TokenOpenParen) {
+      self.expect(SyntaxKind::TokenOpenParen);
+      if self.next_kind() != Some(SyntaxKind::TokenCloseParen) {
+        loop {
+          self.eat_trivia();
+          self.metadata_arg();
+          if self.next_kind() != Some(SyntaxKind::TokenComma) {
+            break;
+          }
+          self.expect(SyntaxKind::TokenComma);
+        }
+      }
+      self.expect(SyntaxKind::TokenCloseParen);
+    }
+    self.expect(SyntaxKind::TokenCloseBracket);
+    self.finish_node();
+  }
+
+  /// Parses a metadata argument: a value, optionally preceded by a key.
+  ///
+  /// Values are kept as single literal or identifier tokens.
+  fn metadata_arg(&mut self) {
+    self.start_node(SyntaxKind::NodeMetadataArg);
+    if self.lexer.peek_kind() == Some(SyntaxKind::TokenIdent) {
+      self.bump();
+      if self.next_kind() != Some(SyntaxKind::TokenEq) {
+        self.finish_node();
+        return;
+      }
+      self.expect(SyntaxKind::TokenEq);
+      self.eat_trivia();
+    }
+    match self.lexer.peek_kind() {
+      Some(SyntaxKind::TokenStrLit)
+      | Some(SyntaxKind::TokenNumLit)
+      | Some(SyntaxKind::TokenIdent)
+      | Some(SyntaxKind::TokenTrue)
+      | Some(SyntaxKind::TokenFalse)
+      | Some(SyntaxKind::TokenNull) => self.bump(),
+      // Negative numbers are the only values with more than one token
+      Some(SyntaxKind::TokenMinus) => {
+        self.bump();
+        self.expect(SyntaxKind::TokenNumLit);
+      }
+      Some(SyntaxKind::TokenComma) | Some(SyntaxKind::TokenCloseParen) | None => self.error(
+        SyntaxErrorKind::MissingExpression,
+        TextRange::offset_len(self.offset, 0.into()),
+      ),
+      Some(_) => self.error_token(),
+    }
+    self.finish_node();
+  }
+
+  /// Parses a method, starting at the `function` keyword.
+  ///
+  /// The body is a block, or a semicolon for methods without body. Methods
+  /// must have a body if and only if `has_body` is true.
+  fn method(&mut self, has_body: bool) {
+    self.expect(SyntaxKind::TokenFunction);
+    self.eat_trivia();
+    // `get` and `set` are accessor markers only if the method name follows
+    let accessor = match self.lexer.peek() {
+      Some(token) if token.kind == SyntaxKind::TokenIdent && token.text == "get" => Some(SyntaxKind::TokenGet),
+      Some(token) if token.kind == SyntaxKind::TokenIdent && token.text == "set" => Some(SyntaxKind::TokenSet),
+      _ => None,
+    };
+    if let Some(accessor) = accessor {
+      if self.lexer.peek_second_kind() == Some(SyntaxKind::TokenIdent) {
+        self.bump_as(accessor);
+        self.eat_trivia();
+      }
+    }
+    self.ident();
+    self.params();
+    if self.next_kind() == Some(SyntaxKind::TokenColon) {
+      self.type_annotation();
+    }
+    match self.next_kind() {
+      Some(SyntaxKind::TokenSemicolon) => {
+        self.eat_trivia();
+        let range = self.bump_range();
+        if has_body {
+          self.error(SyntaxErrorKind::MissingMethodBody, range);
+        }
+      }
+      Some(SyntaxKind::TokenOpenBrace) => {
+        self.eat_trivia();
+        let start = self.offset;
+        self.block();
+        if !has_body {
+          self.error(
+            SyntaxErrorKind::UnexpectedMethodBody,
+            TextRange::from_to(start, self.offset),
+          );
+        }
+      }
+      // Reports the missing brace
+      _ if has_body => self.block(),
+      _ => self.semicolon(),
+    }
+  }
+
+  /// Parses a parenthesized parameter list: `(a:Number, b)`
+  fn params(&mut self) {
+    self.eat_trivia();
+    self.start_node(SyntaxKind::NodeParams);
+    self.expect(SyntaxKind::TokenOpenParen);
+    if self.next_kind() != Some(SyntaxKind::TokenCloseParen) {
+      loop {
+        self.eat_trivia();
+        self.start_node(SyntaxKind::NodeParam);
+        self.ident();
+        if self.next_kind() == Some(SyntaxKind::TokenColon) {
+          self.type_annotation();
+        }
+        self.finish_node();
+        if self.next_kind() != Some(SyntaxKind::TokenComma) {
+          break;
+        }
+        self.expect(SyntaxKind::TokenComma);
+      }
+    }
+    self.expect(SyntaxKind::TokenCloseParen);
+    self.finish_node();
+  }
+
+  /// Parses a type annotation: `:Type`
+  fn type_annotation(&mut self) {
+    self.eat_trivia();
+    let start = self.offset;
+    self.start_node(SyntaxKind::NodeTypeAnnotation);
+    self.expect(SyntaxKind::TokenColon);
+    self.eat_trivia();
+    self.qualified_name(false);
+    self.finish_node();
+    self.require_as2(TextRange::from_to(start, self.offset));
+  }
+
+  /// Parses a possibly dotted name of a type or package: `a.b.C`
+  ///
+  /// If `allow_wildcard` is true, the last segment may be `*`.
+  fn qualified_name(&mut self, allow_wildcard: bool) {
+    self.start_node(SyntaxKind::NodeQualifiedName);
+    self.expect(SyntaxKind::TokenIdent);
+    while self.next_kind() == Some(SyntaxKind::TokenDot) {
+      self.expect(SyntaxKind::TokenDot);
+      self.eat_trivia();
+      if allow_wildcard && self.lexer.peek_kind() == Some(SyntaxKind::TokenStar) {
+        self.bump();
+        break;
+      }
+      self.expect(SyntaxKind::TokenIdent);
+    }
+    self.finish_node();
+  }
+
+  /// Parses an `import` declaration: `import a.b.C;` or `import a.b.*;`
+  fn import_decl(&mut self) {
+    self.start_node(SyntaxKind::NodeImport);
+    let range = self.bump_range();
+    self.require_as2(range);
+    self.eat_trivia();
+    self.qualified_name(true);
+    self.semicolon();
+    self.finish_node();
+  }
+
+  /// Parses an `#include` directive: `#include "lib/util.as"`
+  ///
+  /// Unlike statements, directives are not terminated by a semicolon. The
+  /// included file is only parsed during include expansion.
+  fn include_directive(&mut self) {
+    self.start_node(SyntaxKind::NodeInclude);
+    self.bump();
+    if self.next_kind() == Some(SyntaxKind::TokenStrLit) {
+      self.eat_trivia();
+      self.str_lit();
+    } else {
+      self.expect(SyntaxKind::TokenStrLit);
+    }
+    self.finish_node();
+  }
+
+  /// Parses the colon and statements of a `case` or `default` clause.
+  ///
+  /// The body ends at the next clause: execution falls through unless there
+  /// is an explicit `break`.
+  fn case_body(&mut self) {
+    self.expect(SyntaxKind::TokenColon);
+    while let Some(kind) = self.next_kind() {
+      match kind {
+        SyntaxKind::TokenCase | SyntaxKind::TokenDefault | SyntaxKind::TokenCloseBrace => break,
+        _ => {
+          self.eat_trivia();
+          self.stmt();
+        }
+      }
+    }
+  }
+
+  /// Parses an expression, including comma-separated sequences.
+  ///
+  /// If `no_in` is `true`, `in` is not treated as a binary operator (unless
+  /// nested inside brackets).
+  fn expr(&mut self, no_in: bool) -> Option<SyntaxKind> {
+    self.eat_trivia();
+    let cp = self.checkpoint();
+    let kind = self.assign_expr(no_in);
+    if self.next_kind() != Some(SyntaxKind::TokenComma) {
+      return kind;
+    }
+    self.start_node_at(cp, SyntaxKind::NodeSeqExpr);
+    while self.next_kind() == Some(SyntaxKind::TokenComma) {
+      self.expect(SyntaxKind::TokenComma);
+      self.assign_expr(no_in);
+    }
+    self.finish_node();
+    Some(SyntaxKind::NodeSeqExpr)
+  }
+
+  /// Parses an expression without top-level commas.
+  ///
+  /// This is used where commas are separators: arguments, declarators, etc.
+  fn assign_expr(&mut self, no_in: bool) -> Option<SyntaxKind> {
+    self.expr_bp(ASSIGN_BP, no_in)
+  }
+
+  /// Parses an expression where all the binary operators have a left binding
+  /// power of at least `min_bp`.
+  ///
+  /// If the next token cannot start an expression, it is either reported as
+  /// missing (before a closing delimiter or statement) or wrapped in a
+  /// `NodeError`.
+  ///
+  /// Chains of prefix operators are parsed iteratively: their nodes are all
+  /// started before parsing the innermost operand.
+  ///
+  /// Returns the node kind of the expression, `None` if it is missing.
+  fn expr_bp(&mut self, min_bp: u8, no_in: bool) -> Option<SyntaxKind> {
+    if !self.check_expr_start() {
+      return None;
+    }
+    self.eat_trivia();
+    let depth = self.depth;
+    let cp = self.checkpoint();
+    let start = self.offset;
+    // Node kind of the outermost prefix operator, and number of open prefix nodes
+    let mut prefix: Option<SyntaxKind> = None;
+    let mut prefix_count: usize = 0;
+    // Kind of the node for the innermost operand, `None` if it is missing
+    let mut operand_kind: Option<SyntaxKind> = None;
+    let mut operand_cp = cp;
+    let mut operand_start = start;
+    loop {
+      if self.depth >= self.options.max_depth {
+        self.too_deep(true);
+        operand_kind = Some(SyntaxKind::NodeError);
+        break;
+      }
+      self.depth += 1;
+      operand_cp = self.checkpoint();
+      operand_start = self.offset;
+      let first = match self.lexer.peek() {
+        Some(first) => first.kind,
+        None => break,
+      };
+      let node_kind = match first {
+        SyntaxKind::TokenIdent => {
+          operand_kind = Some(self.ident_expr());
+          break;
+        }
+        SyntaxKind::TokenOpenParen => {
+          self.paren_expr();
+          operand_kind = Some(SyntaxKind::NodeParenExpr);
+          break;
+        }
+        SyntaxKind::TokenTrue
+        | SyntaxKind::TokenFalse
+        | SyntaxKind::TokenNull
+        | SyntaxKind::TokenThis
+        | SyntaxKind::TokenSuper => {
+          let node_kind = match first {
+            SyntaxKind::TokenNull => SyntaxKind::NodeNullLit,
+            SyntaxKind::TokenThis => SyntaxKind::NodeThisExpr,
+            SyntaxKind::TokenSuper => SyntaxKind::NodeSuperExpr,
+            _ => SyntaxKind::NodeBoolLit,
+          };
+          self.start_node(node_kind);
+          self.bump();
+          self.finish_node();
+          operand_kind = Some(node_kind);
+          break;
+        }
+        SyntaxKind::TokenPop => {
+          self.start_node(SyntaxKind::NodePopExpr);
+          self.bump();
+          self.expect(SyntaxKind::TokenOpenParen);
+          self.expect(SyntaxKind::TokenCloseParen);
+          self.finish_node();
+          operand_kind = Some(SyntaxKind::NodePopExpr);
+          break;
+        }
+        SyntaxKind::TokenNumLit => {
+          self.num_lit();
+          operand_kind = Some(SyntaxKind::NodeNumLit);
+          break;
+        }
+        SyntaxKind::TokenStrLit => {
+          self.str_lit();
+          operand_kind = Some(SyntaxKind::NodeStrLit);
+          break;
+        }
+        SyntaxKind::TokenTargetPath => {
+          self.start_node(SyntaxKind::NodeTargetPath);
+          self.bump();
+          self.finish_node();
+          operand_kind = Some(SyntaxKind::NodeTargetPath);
+          break;
+        }
+        SyntaxKind::TokenPlusPlus | SyntaxKind::TokenMinusMinus => SyntaxKind::NodeUpdateExpr,
+        // Remaining expression starts are unary operators
+        _ => SyntaxKind::NodeUnaryExpr,
+      };
+      self.start_node(node_kind);
+      self.bump();
+      prefix = prefix.or(Some(node_kind));
+      prefix_count += 1;
+      if !self.check_expr_start() {
+        break;
+      }
+      self.eat_trivia();
+    }
+    let lhs_kind = match prefix {
+      Some(prefix) => {
+        if let Some(operand_kind) = operand_kind {
+          self.expr_tail(operand_cp, operand_start, operand_kind, PREFIX_BP, no_in);
+        }
+        for _ in 0..prefix_count {
+          self.finish_node();
+        }
+        prefix
+      }
+      None => match operand_kind {
+        Some(operand_kind) => operand_kind,
+        None => {
+          self.depth = depth;
+          return None;
+        }
+      },
+    };
+    self.depth = depth + 1;
+    let kind = self.expr_tail(cp, start, lhs_kind, min_bp, no_in);
+    self.depth = depth;
+    Some(kind)
   }
 
-  fn script(mut self) -> Parsed {
-    self.builder.start_node(SyntaxKind::NodeScript.into());
-    self.stmt_list(None);
-    self.builder.finish_node();
-    let green_node: rowan::GreenNode = self.builder.finish();
-    Parsed { green_node }
+  /// Parses the operators following the expression started at `cp`, whose
+  /// node kind is `lhs_kind`.
+  ///
+  /// Only binary operators with a left binding power of at least `min_bp` are
+  /// consumed.
+  fn expr_tail(
+    &mut self,
+    cp: Checkpoint,
+    start: TextUnit,
+    mut lhs_kind: SyntaxKind,
+    min_bp: u8,
+    no_in: bool,
+  ) -> SyntaxKind {
+    loop {
+      let (trivia, operator) = self.lexer.peek_with_trivia();
+      let operator = match operator {
+        Some(token) if token.kind == SyntaxKind::TokenIdent && token.text == "add" => SyntaxKind::TokenAdd,
+        Some(token) => token.kind,
+        None => break,
+      };
+      lhs_kind = match operator {
+        SyntaxKind::TokenOpenParen => {
+          self.end_call(cp);
+          SyntaxKind::NodeCall
+        }
+        SyntaxKind::TokenDot | SyntaxKind::TokenOpenBracket => {
+          self.end_member(cp);
+          SyntaxKind::NodeMember
+        }
+        // Restricted production: there must be no line terminator before a postfix operator
+        SyntaxKind::TokenPlusPlus | SyntaxKind::TokenMinusMinus if trivia != TriviaKind::Multiline => {
+          self.start_node_at(cp, SyntaxKind::NodeUpdateExpr);
+          self.expect(operator);
+          self.finish_node();
+          SyntaxKind::NodeUpdateExpr
+        }
+        // The conditional operator is right-associative, its branches are
+        // assignment expressions.
+        SyntaxKind::TokenQuestion if CONDITIONAL_BP >= min_bp => {
+          self.start_node_at(cp, SyntaxKind::NodeCondExpr);
+          self.expect(SyntaxKind::TokenQuestion);
+          self.assign_expr(false);
+          self.expect(SyntaxKind::TokenColon);
+          self.assign_expr(no_in);
+          self.finish_node();
+          SyntaxKind::NodeCondExpr
+        }
+        // Assignments are right-associative. The left-hand side was parsed as
+        // an expression: it is reinterpreted as a pattern.
+        kind if is_assign_operator(kind) && ASSIGN_BP >= min_bp => {
+          if !is_pattern(lhs_kind) {
+            let range = TextRange::from_to(start, self.offset);
+            self.error(SyntaxErrorKind::InvalidAssignmentTarget, range);
+          }
+          self.start_node_at(cp, SyntaxKind::NodeAssignExpr);
+          self.expect(kind);
+          self.assign_expr(no_in);
+          self.finish_node();
+          SyntaxKind::NodeAssignExpr
+        }
+        kind => match infix_binding_power(kind, no_in) {
+          Some((left_bp, right_bp)) if left_bp >= min_bp => {
+            self.start_node_at(cp, SyntaxKind::NodeBinExpr);
+            self.eat_trivia();
+            let op_start = self.offset;
+            // The contextual keyword `add` is lexed as an identifier
+            self.bump_as(kind);
+            let range = TextRange::from_to(op_start, self.offset);
+            // Strict equality and `instanceof` were added in Flash Player 6
+            if let SyntaxKind::TokenEqEqEq | SyntaxKind::TokenExclEqEq | SyntaxKind::TokenInstanceOf = kind {
+              self.require_swf_version(6, range);
+            }
+            self.expr_bp(right_bp, no_in);
+            self.finish_node();
+            SyntaxKind::NodeBinExpr
+          }
+          _ => break,
+        },
+      };
+    }
+    lhs_kind
   }
 
-  fn stmt_list(&mut self, end: Option<SyntaxKind>) {
-    self.eat_trivia();
-    while self.lexer.peek_kind() != end {
-      self.stmt();
-      self.eat_trivia();
+  /// Checks that the next token can start an expression.
+  ///
+  /// Otherwise, it is either reported as missing or wrapped in a `NodeError`.
+  fn check_expr_start(&mut self) -> bool {
+    match self.next_kind() {
+      Some(kind) if is_expr_start(kind) => true,
+      Some(kind) if !is_expr_recovery(kind) => {
+        self.eat_trivia();
+        self.error_token();
+        false
+      }
+      _ => {
+        self.error(
+          SyntaxErrorKind::MissingExpression,
+          TextRange::offset_len(self.offset, 0.into()),
+        );
+        false
+      }
     }
   }
 
-  fn stmt(&mut self) {
-    self.builder.start_node(SyntaxKind::NodeStatement.into());
-    let first = match self.lexer.peek() {
-      None => return,
-      Some(token) => token,
-    };
-    match first.kind {
-      kind if is_expr_start(kind) => {
-        self.expr(Some(SyntaxKind::TokenSemicolon));
-        // Labelled statement or expression
-      }
-      kind => unimplemented!("{:?}", kind),
+  /// Parses an identifier, or reports it as missing.
+  fn ident(&mut self) {
+    if self.next_kind() != Some(SyntaxKind::TokenIdent) {
+      self.expect(SyntaxKind::TokenIdent);
+      return;
     }
-    debug_assert!(matches!(
-      self.lexer.peek(),
-      Some(LexerToken {
-        kind: SyntaxKind::TokenSemicolon,
-        ..
-      })
-    ));
     self.eat_trivia();
+    self.start_node(SyntaxKind::NodeIdent);
     self.bump();
-    self.builder.finish_node();
+    self.finish_node();
   }
 
-  fn expr(&mut self, end: Option<SyntaxKind>) {
-    let first = match self.lexer.peek() {
-      None => return,
-      Some(token) => token,
+  /// Parses an identifier reference, returning its node kind.
+  ///
+  /// The contextual keywords `undefined` and `newline` are parsed as literals.
+  /// In the decompiler dialect, register locals such as `_loc3_` are parsed
+  /// as `NodeRegister`. Below SWF 7, these names are matched in any case.
+  fn ident_expr(&mut self) -> SyntaxKind {
+    let kinds = {
+      let name = match self.lexer.peek() {
+        Some(token) => self.options.fold_case(&token.text),
+        None => Cow::Borrowed(""),
+      };
+      match name.as_ref() {
+        "undefined" => Some((SyntaxKind::NodeUndefinedLit, SyntaxKind::TokenUndefined)),
+        "newline" => Some((SyntaxKind::NodeNewlineLit, SyntaxKind::TokenNewline)),
+        name if self.options.dialect == Dialect::Decompiler && register_index(name).is_some() => {
+          Some((SyntaxKind::NodeRegister, SyntaxKind::TokenIdent))
+        }
+        _ => None,
+      }
     };
-    match first.kind {
-      SyntaxKind::TokenIdent => {
-        self.expr_bp(0, end);
-        // Labelled statement or expression
+    let (node_kind, token_kind) = match kinds {
+      Some(kinds) => kinds,
+      None => {
+        self.ident();
+        return SyntaxKind::NodeIdent;
       }
-      _ => unimplemented!(),
-    }
+    };
+    self.start_node(node_kind);
+    self.bump_as(token_kind);
+    self.finish_node();
+    node_kind
   }
 
-  fn expr_bp(&mut self, _bp: u8, end: Option<SyntaxKind>) {
-    let cp = self.builder.checkpoint();
-    let first = match self.lexer.peek() {
-      Some(first) => first,
-      None => return,
-    };
-    match first.kind {
-      SyntaxKind::TokenIdent => self.ident(),
-      SyntaxKind::TokenStrLit => self.str_lit(),
-      _ => unimplemented!(),
-    }
-    let operator = match self.lexer.peek() {
-      e if e.map(|t| t.kind) == end => return,
-      None => panic!("Unexpected end of file"),
-      Some(token) => token,
-    };
-    match operator.kind {
-      SyntaxKind::TokenOpenParen => self.end_call(cp),
-      _ => unimplemented!(),
-    }
+  /// Parses a parenthesized expression.
+  ///
+  /// The parentheses are kept as a `NodeParenExpr`, even when redundant.
+  fn paren_expr(&mut self) {
+    self.start_node(SyntaxKind::NodeParenExpr);
+    self.expect(SyntaxKind::TokenOpenParen);
+    self.expr(false);
+    self.expect(SyntaxKind::TokenCloseParen);
+    self.finish_node();
   }
 
-  fn ident(&mut self) {
-    self.builder.start_node(SyntaxKind::NodeIdent.into());
+  fn num_lit(&mut self) {
+    self.start_node(SyntaxKind::NodeNumLit);
     debug_assert!(matches!(
       self.lexer.peek(),
       Some(LexerToken {
-        kind: SyntaxKind::TokenIdent,
+        kind: SyntaxKind::TokenNumLit,
         ..
       })
     ));
     self.bump();
-    self.builder.finish_node();
+    self.finish_node();
   }
 
   fn str_lit(&mut self) {
-    self.builder.start_node(SyntaxKind::NodeStrLit.into());
+    self.start_node(SyntaxKind::NodeStrLit);
     debug_assert!(matches!(
       self.lexer.peek(),
       Some(LexerToken {
@@ -265,56 +2106,365 @@ impl<'text> Parser<'text> {
       })
     ));
     self.bump();
-    self.builder.finish_node();
+    self.finish_node();
   }
 
-  fn end_call(&mut self, cp: rowan::Checkpoint) {
-    self.builder.start_node_at(cp, SyntaxKind::NodeCall.into());
-    debug_assert!(matches!(
-      self.lexer.peek(),
-      Some(LexerToken {
-        kind: SyntaxKind::TokenOpenParen,
-        ..
-      })
-    ));
+  fn end_call(&mut self, cp: Checkpoint) {
+    self.start_node_at(cp, SyntaxKind::NodeCall);
+    self.expect(SyntaxKind::TokenOpenParen);
+    if self.next_kind() != Some(SyntaxKind::TokenCloseParen) {
+      loop {
+        self.assign_expr(false);
+        if self.next_kind() != Some(SyntaxKind::TokenComma) {
+          break;
+        }
+        self.expect(SyntaxKind::TokenComma);
+      }
+    }
+    self.expect(SyntaxKind::TokenCloseParen);
+    self.finish_node();
+  }
+
+  fn end_member(&mut self, cp: Checkpoint) {
+    self.start_node_at(cp, SyntaxKind::NodeMember);
     self.eat_trivia();
-    self.bump();
-    self.expr_bp(0, Some(SyntaxKind::TokenCloseParen));
-    debug_assert!(matches!(
-      self.lexer.peek(),
-      Some(LexerToken {
-        kind: SyntaxKind::TokenCloseParen,
-        ..
-      })
-    ));
+    if self.lexer.peek_kind() == Some(SyntaxKind::TokenDot) {
+      self.bump();
+      self.eat_trivia();
+      self.ident();
+    } else {
+      self.expect(SyntaxKind::TokenOpenBracket);
+      self.expr(false);
+      self.expect(SyntaxKind::TokenCloseBracket);
+    }
+    self.finish_node();
+  }
+
+  /// Consumes the semicolon ending a statement, or inserts one if allowed.
+  ///
+  /// A semicolon is inserted before `}`, at the end of the input, or before a
+  /// token preceded by a line terminator.
+  fn semicolon(&mut self) {
+    let (trivia, next) = self.lexer.peek_with_trivia();
+    match next.map(|token| token.kind) {
+      Some(SyntaxKind::TokenCloseBrace) | None => self.insert_semicolon(),
+      Some(kind) if kind != SyntaxKind::TokenSemicolon && trivia == TriviaKind::Multiline => self.insert_semicolon(),
+      Some(_) => {
+        self.expect(SyntaxKind::TokenSemicolon);
+      }
+    }
+  }
+
+  /// Adds an empty marker for an automatically inserted semicolon.
+  ///
+  /// It is added before any pending trivia, right after the last token.
+  fn insert_semicolon(&mut self) {
+    self.events.push(Event::Token {
+      kind: SyntaxKind::TokenInsertedSemicolon,
+      text: SmolStr::default(),
+    });
+  }
+
+  /// Returns the kind of the next non-trivia token, without consuming the trivia.
+  fn next_kind(&self) -> Option<SyntaxKind> {
+    self.lexer.peek_with_trivia().1.map(|token| token.kind)
+  }
+
+  /// Consumes the next token, after any trivia, if it has the provided kind.
+  ///
+  /// Otherwise, the token is reported as missing and nothing is consumed.
+  /// Returns `true` if the token was consumed.
+  fn expect(&mut self, kind: SyntaxKind) -> bool {
+    if self.next_kind() != Some(kind) {
+      self.error(
+        SyntaxErrorKind::MissingToken(kind),
+        TextRange::offset_len(self.offset, 0.into()),
+      );
+      return false;
+    }
     self.eat_trivia();
     self.bump();
-    self.builder.finish_node();
+    true
+  }
+
+  /// Wraps the next token in a `NodeError`.
+  ///
+  /// # Precondition
+  ///
+  /// There must be a next token and the trivia before it must be consumed.
+  fn error_token(&mut self) {
+    self.start_node(SyntaxKind::NodeError);
+    let range = self.bump_range();
+    self.finish_node();
+    self.error(SyntaxErrorKind::UnexpectedToken, range);
   }
 
   fn bump(&mut self) {
     let token = self.lexer.pop();
-    self.builder.token(token.kind.into(), token.text);
+    self.offset += TextUnit::of_str(&token.text);
+    self.events.push(Event::Token {
+      kind: token.kind,
+      text: token.text,
+    });
+  }
+
+  /// Consumes the next token, with its kind replaced by the provided one.
+  ///
+  /// This is used for contextual keywords.
+  fn bump_as(&mut self, kind: SyntaxKind) {
+    let token = self.lexer.pop();
+    self.offset += TextUnit::of_str(&token.text);
+    self.events.push(Event::Token { kind, text: token.text });
+  }
+
+  /// Consumes the next token and returns its range.
+  fn bump_range(&mut self) -> TextRange {
+    let start = self.offset;
+    self.bump();
+    TextRange::from_to(start, self.offset)
+  }
+
+  /// Reports syntax only available in ActionScript 2.
+  fn require_as2(&mut self, range: TextRange) {
+    if self.options.language < LanguageLevel::As2 {
+      self.error(SyntaxErrorKind::RequiresAs2, range);
+    }
+  }
+
+  /// Reports syntax unsupported by SWF versions below `version`.
+  fn require_swf_version(&mut self, version: u8, range: TextRange) {
+    if self.options.swf_version < version {
+      self.error(SyntaxErrorKind::RequiresSwfVersion(version), range);
+    }
+  }
+
+  fn error(&mut self, kind: SyntaxErrorKind, range: TextRange) {
+    self.events.push(Event::Error(SyntaxError { kind, range }));
   }
 }
 
-pub fn parse(text: &str) -> Parsed {
-  let lexer = Lexer::new(text);
-  let lexer = PeekableLexer::new(lexer);
-  let builder = rowan::GreenNodeBuilder::new();
-  let parser = Parser { lexer, builder };
-  parser.script()
+/// Parses a script.
+///
+/// Syntax unavailable for the SWF version or language level of the options is
+/// still parsed, but reported.
+pub fn parse(text: &str, options: &ParseOptions) -> Parsed {
+  parse_root(text, SyntaxKind::NodeScript, options)
+}
+
+/// Parses a script, sending the syntax tree and errors to `sink` as events.
+///
+/// This allows to build other trees than `Parsed`, or to inspect parsing
+/// without building any tree.
+pub fn parse_with_sink<S: TreeSink + ?Sized>(text: &str, options: &ParseOptions, sink: &mut S) {
+  let mut parser = Parser::new(new_lexer(text, options), options);
+  parser.script();
+  parser.finish_into(sink);
+}
+
+/// Parses a script in Flash 4 compatibility mode.
+///
+/// Target paths with a variable such as `/clip/sub:score` or `../:counter` are
+/// accepted as expressions and assignment targets. This uses
+/// `ParseOptions::flash4`.
+pub fn parse_flash4(text: &str) -> Parsed {
+  parse(text, &ParseOptions::flash4())
+}
+
+/// Parses a standalone expression, such as a watch expression or a property value.
+///
+/// The root is a `NodeExprRoot`. Tokens after the expression are reported.
+pub fn parse_expr(text: &str, options: &ParseOptions) -> Parsed {
+  parse_root(text, SyntaxKind::NodeExprRoot, options)
+}
+
+/// Parses a single statement.
+///
+/// The root is a `NodeStmtRoot`. Tokens after the statement are reported.
+pub fn parse_stmt(text: &str, options: &ParseOptions) -> Parsed {
+  parse_root(text, SyntaxKind::NodeStmtRoot, options)
+}
+
+/// Parses an AS2 class file.
+///
+/// The root is a `NodeClassFile`. The file must contain exactly one class or
+/// interface declaration, optionally preceded by imports.
+pub fn parse_class_file(text: &str, options: &ParseOptions) -> Parsed {
+  parse_root(text, SyntaxKind::NodeClassFile, options)
+}
+
+/// Parses a script attached to a button or movie clip instance.
+///
+/// These scripts are made of `on` and `onClipEvent` event handlers.
+pub fn parse_instance_script(text: &str, options: &ParseOptions) -> Parsed {
+  parse_root(text, SyntaxKind::NodeInstanceScript, options)
 }
 
 fn is_expr_start(token_kind: SyntaxKind) -> bool {
   debug_assert!(token_kind.is_token());
   use SyntaxKind::*;
   match token_kind {
-    TokenIdent | TokenStrLit | TokenExcl => true,
+    TokenIdent | TokenNumLit | TokenStrLit | TokenTargetPath | TokenTrue | TokenFalse | TokenNull | TokenThis
+    | TokenSuper | TokenOpenParen | TokenPop | TokenPlusPlus | TokenMinusMinus => true,
+    kind => is_unary_operator(kind),
+  }
+}
+
+fn new_lexer<'text>(text: &'text str, options: &ParseOptions) -> Lexer<'text> {
+  let lexer = if options.has_target_paths() {
+    Lexer::flash4(text)
+  } else {
+    Lexer::new(text)
+  };
+  match options.dialect {
+    Dialect::Standard => lexer,
+    Dialect::Decompiler => lexer.with_decompiler_mode(),
+  }
+}
+
+/// Returns the register number of a decompiled register local such as `_loc3_`.
+pub fn register_index(name: &str) -> Option<u8> {
+  if !name.starts_with("_loc") || !name.ends_with('_') || name.len() <= "_loc_".len() {
+    return None;
+  }
+  let digits = &name["_loc".len()..name.len() - 1];
+  if !digits.bytes().all(|b| b.is_ascii_digit()) {
+    return None;
+  }
+  digits.parse().ok()
+}
+
+/// Parses `text` with the entry point producing a root of kind `root_kind`.
+fn parse_root(text: &str, root_kind: SyntaxKind, options: &ParseOptions) -> Parsed {
+  let mut parser = Parser::new(new_lexer(text, options), options);
+  match root_kind {
+    SyntaxKind::NodeClassFile => parser.class_file(),
+    SyntaxKind::NodeExprRoot => parser.expr_root(),
+    SyntaxKind::NodeInstanceScript => parser.instance_script(),
+    SyntaxKind::NodeStmtRoot => parser.stmt_root(),
+    _ => parser.script(),
+  }
+  parser.finish()
+}
+
+/// Returns the maximum number of nodes from `node` to a leaf, inclusive.
+fn tree_height(node: &SyntaxNode) -> usize {
+  let mut height: usize = 0;
+  let mut depth: usize = 0;
+  for event in node.preorder() {
+    match event {
+      rowan::WalkEvent::Enter(_) => {
+        depth += 1;
+        height = height.max(depth);
+      }
+      rowan::WalkEvent::Leave(_) => depth -= 1,
+    }
+  }
+  height
+}
+
+/// Returns `true` if an identifier with this name may be parsed as a keyword
+/// depending on its context.
+fn is_contextual_keyword(name: &str) -> bool {
+  match name {
+    "add" | "get" | "newline" | "on" | "onClipEvent" | "set" | "undefined" => true,
+    _ => false,
+  }
+}
+
+/// Returns `true` if the token can start a statement.
+fn is_stmt_start(token_kind: SyntaxKind) -> bool {
+  use SyntaxKind::*;
+  match token_kind {
+    TokenBreak | TokenClass | TokenContinue | TokenDo | TokenDynamic | TokenFor | TokenIf | TokenIfFrameLoaded
+    | TokenGoto | TokenImport | TokenInclude | TokenInterface | TokenIntrinsic | TokenOpenBrace | TokenOpenBracket
+    | TokenPush | TokenReturn | TokenSemicolon | TokenSwitch | TokenTellTarget | TokenThrow | TokenTry | TokenVar
+    | TokenWhile | TokenWith => true,
+    kind => is_expr_start(kind),
+  }
+}
+
+/// Returns `true` if the token can start a class member, after its metadata.
+fn is_member_start(token_kind: SyntaxKind) -> bool {
+  use SyntaxKind::*;
+  match token_kind {
+    TokenFunction | TokenOpenBracket | TokenPrivate | TokenPublic | TokenStatic | TokenVar => true,
+    _ => false,
+  }
+}
+
+/// Returns `true` if a missing expression should be reported before this token,
+/// instead of wrapping it in a `NodeError`.
+///
+/// These are closing delimiters, separators and statement starts.
+fn is_expr_recovery(token_kind: SyntaxKind) -> bool {
+  use SyntaxKind::*;
+  match token_kind {
+    TokenCloseBrace | TokenCloseBracket | TokenCloseParen | TokenColon | TokenComma => true,
+    kind => is_stmt_start(kind),
+  }
+}
+
+fn is_unary_operator(token_kind: SyntaxKind) -> bool {
+  use SyntaxKind::*;
+  match token_kind {
+    TokenExcl | TokenTilde | TokenMinus | TokenPlus | TokenTypeOf | TokenDelete | TokenVoid => true,
+    _ => false,
+  }
+}
+
+/// Returns `true` if the token is `=` or a compound assignment operator.
+fn is_assign_operator(token_kind: SyntaxKind) -> bool {
+  use SyntaxKind::*;
+  match token_kind {
+    TokenEq | TokenPlusEq | TokenMinusEq | TokenStarEq | TokenSlashEq | TokenPercentEq | TokenLtLtEq | TokenGtGtEq
+    | TokenGtGtGtEq | TokenAmpEq | TokenPipeEq | TokenCaretEq => true,
+    _ => false,
+  }
+}
+
+/// Returns `true` if an expression node can be reinterpreted as an assignment pattern.
+fn is_pattern(node_kind: SyntaxKind) -> bool {
+  match node_kind {
+    SyntaxKind::NodeIdent | SyntaxKind::NodeMember | SyntaxKind::NodeRegister | SyntaxKind::NodeTargetPath => true,
     _ => false,
   }
 }
 
+/// Left binding power of the assignment operators.
+///
+/// It is the lowest binding power: the right-hand side is parsed with it.
+const ASSIGN_BP: u8 = 1;
+
+/// Left binding power of the conditional operator `?`.
+const CONDITIONAL_BP: u8 = 2;
+
+/// Binding power of the operand of prefix operators.
+///
+/// It is higher than the binding power of any binary operator.
+const PREFIX_BP: u8 = 23;
+
+/// Returns the left and right binding powers of a binary operator.
+///
+/// All the binary operators are left-associative.
+fn infix_binding_power(token_kind: SyntaxKind, no_in: bool) -> Option<(u8, u8)> {
+  use SyntaxKind::*;
+  let left_bp: u8 = match token_kind {
+    TokenPipePipe => 3,
+    TokenAmpAmp => 5,
+    TokenPipe => 7,
+    TokenCaret => 9,
+    TokenAmp => 11,
+    TokenEqEq | TokenExclEq | TokenEqEqEq | TokenExclEqEq => 13,
+    TokenLt | TokenLtEq | TokenGt | TokenGtEq | TokenInstanceOf => 15,
+    TokenIn if !no_in => 15,
+    TokenLtLt | TokenGtGt | TokenGtGtGt => 17,
+    TokenPlus | TokenMinus | TokenAdd => 19,
+    TokenStar | TokenSlash | TokenPercent => 21,
+    _ => return None,
+  };
+  Some((left_bp, left_bp + 1))
+}
+
 //
 // struct Parser<'i> {
 //   input: &'i str,
@@ -348,11 +2498,21 @@ fn is_expr_start(token_kind: SyntaxKind) -> bool {
 //   }
 // }
 
-pub fn parse_script(_input: &str) -> owned::StrLit {
-  owned::StrLit {
-    loc: (),
-    value: String::new(),
-  }
+/// Parses a script and lowers its syntax tree to the owned AST.
+pub fn parse_script(text: &str, options: &ParseOptions) -> owned::Script {
+  lower_script(&parse(text, options).syntax())
+}
+
+/// Parses a script straight to the owned AST.
+///
+/// No syntax tree is built and trivia is skipped by the lexer, which makes it
+/// faster than `parse_script` for batch analysis. The result is identical.
+pub fn parse_script_fast(text: &str, options: &ParseOptions) -> owned::Script {
+  let mut parser = Parser::without_trivia(new_lexer(text, options), options);
+  parser.script();
+  let mut sink = OwnedSink::default();
+  parser.finish_into(&mut sink);
+  sink.finish()
 }
 
 // pub fn eval_expr(input: &owned::Expr) -> f64 {
@@ -375,10 +2535,13 @@ pub fn parse_script(_input: &str) -> owned::StrLit {
 
 #[cfg(test)]
 mod parser_tests {
-  use crate::parser::parse;
-  use crate::types::syntax::SyntaxNode;
+  use crate::parser::{
+    parse, parse_class_file, parse_expr, parse_flash4, parse_instance_script, parse_script, parse_script_fast,
+    parse_stmt, register_index, LanguageLevel, ParseOptions, Parsed, SyntaxErrorKind, TextEdit,
+  };
+  use crate::types::syntax::{SyntaxKind, SyntaxNode};
   use ::test_generator::test_resources;
-  use rowan::WalkEvent;
+  use rowan::{TextRange, TextUnit, WalkEvent};
   use std::fs;
   use std::io;
   use std::path::Path;
@@ -401,7 +2564,22 @@ mod parser_tests {
     let as2_path = path.join("main.as2");
     let as2_text: String = ::std::fs::read_to_string(as2_path).expect("Failed to read input");
 
-    let parsed = parse(&as2_text);
+    // Samples in the `instance` group are button or movie clip scripts, samples
+    // in the `class-file` group are class files and samples in the `flash4`
+    // group use the Flash 4 compatibility mode. Samples in the `decompiler`
+    // group are JPEXS/FFDec exports.
+    let group = path
+      .parent()
+      .and_then(|parent| parent.file_name())
+      .and_then(|group| group.to_str())
+      .expect("Failed to retrieve sample group");
+    let parsed = match group {
+      "instance" => parse_instance_script(&as2_text, &ParseOptions::default()),
+      "class-file" => parse_class_file(&as2_text, &ParseOptions::default()),
+      "flash4" => parse_flash4(&as2_text),
+      "decompiler" => parse(&as2_text, &ParseOptions::decompiler()),
+      _ => parse(&as2_text, &ParseOptions::default()),
+    };
 
     let actual_cst = SyntaxNode::new_root(parsed.green_node);
 
@@ -418,6 +2596,584 @@ mod parser_tests {
     assert_eq!(&actual_cst_text, &cst_text);
   }
 
+  #[test]
+  fn test_duplicate_default_clause() {
+    let parsed = parse(
+      "switch (x) { default: a(); case 1: default: b(); }",
+      &ParseOptions::default(),
+    );
+    let errors: Vec<(SyntaxErrorKind, TextRange)> = parsed.errors().iter().map(|e| (e.kind, e.range)).collect();
+    assert_eq!(
+      errors,
+      vec![(
+        SyntaxErrorKind::DuplicateDefaultClause,
+        TextRange::from_to(TextUnit::from(35), TextUnit::from(42))
+      )]
+    );
+  }
+
+  #[test]
+  fn test_invalid_assignment_target() {
+    let parsed = parse("1 = x;\na + b += c;\na.b = f() = c;", &ParseOptions::default());
+    let errors: Vec<(SyntaxErrorKind, TextRange)> = parsed.errors().iter().map(|e| (e.kind, e.range)).collect();
+    assert_eq!(
+      errors,
+      vec![
+        (
+          SyntaxErrorKind::InvalidAssignmentTarget,
+          TextRange::from_to(TextUnit::from(0), TextUnit::from(1))
+        ),
+        (
+          SyntaxErrorKind::InvalidAssignmentTarget,
+          TextRange::from_to(TextUnit::from(7), TextUnit::from(12))
+        ),
+        (
+          SyntaxErrorKind::InvalidAssignmentTarget,
+          TextRange::from_to(TextUnit::from(25), TextUnit::from(28))
+        ),
+      ]
+    );
+  }
+
+  #[test]
+  fn test_invalid_for_in_head() {
+    let parsed = parse(
+      "for (a + b in c) {}\nfor (var a = 1, b in o) {}\nfor (var a in o) {}\nfor (a.b in o) {}",
+      &ParseOptions::default(),
+    );
+    let errors: Vec<(SyntaxErrorKind, TextRange)> = parsed.errors().iter().map(|e| (e.kind, e.range)).collect();
+    assert_eq!(
+      errors,
+      vec![
+        (
+          SyntaxErrorKind::InvalidAssignmentTarget,
+          TextRange::from_to(TextUnit::from(5), TextUnit::from(10))
+        ),
+        (
+          SyntaxErrorKind::MultipleForInDeclarators,
+          TextRange::from_to(TextUnit::from(25), TextUnit::from(37))
+        ),
+      ]
+    );
+  }
+
+  #[test]
+  fn test_line_terminator_after_throw() {
+    let parsed = parse("throw\ne;", &ParseOptions::default());
+    let errors: Vec<(SyntaxErrorKind, TextRange)> = parsed.errors().iter().map(|e| (e.kind, e.range)).collect();
+    assert_eq!(
+      errors,
+      vec![(
+        SyntaxErrorKind::LineTerminatorAfterThrow,
+        TextRange::from_to(TextUnit::from(0), TextUnit::from(5))
+      )]
+    );
+  }
+
+  #[test]
+  fn test_try_without_handler() {
+    let parsed = parse("a();\ntry {}\nb();", &ParseOptions::default());
+    let errors: Vec<(SyntaxErrorKind, TextRange)> = parsed.errors().iter().map(|e| (e.kind, e.range)).collect();
+    assert_eq!(
+      errors,
+      vec![(
+        SyntaxErrorKind::MissingCatchOrFinally,
+        TextRange::from_to(TextUnit::from(5), TextUnit::from(8))
+      )]
+    );
+  }
+
+  #[test]
+  fn test_recovery_errors() {
+    let parsed = parse("f(;\nif (a b;\n) x = 1;", &ParseOptions::default());
+    let errors: Vec<(SyntaxErrorKind, TextRange)> = parsed.errors().iter().map(|e| (e.kind, e.range)).collect();
+    assert_eq!(
+      errors,
+      vec![
+        (
+          SyntaxErrorKind::MissingExpression,
+          TextRange::from_to(TextUnit::from(2), TextUnit::from(2))
+        ),
+        (
+          SyntaxErrorKind::MissingToken(SyntaxKind::TokenCloseParen),
+          TextRange::from_to(TextUnit::from(2), TextUnit::from(2))
+        ),
+        (
+          SyntaxErrorKind::MissingToken(SyntaxKind::TokenCloseParen),
+          TextRange::from_to(TextUnit::from(9), TextUnit::from(9))
+        ),
+        (
+          SyntaxErrorKind::UnexpectedToken,
+          TextRange::from_to(TextUnit::from(13), TextUnit::from(14))
+        ),
+      ]
+    );
+  }
+
+  #[test]
+  fn test_never_panics() {
+    let samples = [
+      "var a = ; if (a) ) b(); f(1, ); x = * 2; }",
+      "class A extends { static ) function f( { var } [Meta(a=, ] }",
+      "interface I { function f() var x; } import a.;",
+      "on (press, , keyPress) { } onClipEvent { x = ?",
+      "switch (a) { case : default: default } try catch (e) {} finally",
+      "for (var i = 0 in ; i < ; i++ ) do while ( with (a, ) { tellTarget (",
+      "a ? b : ; c = d = ; (e) @ # \"unterminated",
+      "/clip/sub:score = ../:x + /:; _root/:",
+      "§§push(_loc1_ §§pop( §§goto(; §§ lbl: } §§goto(a) _loc2_ = §§pop()",
+      "((a) + (b, ; true false) null. super(( newline = undefined (",
+    ];
+    let decompiler = ParseOptions::decompiler();
+    for sample in samples.iter() {
+      for end in (0..=sample.len()).filter(|end| sample.is_char_boundary(*end)) {
+        let text = &sample[..end];
+        let all = [
+          parse(text, &ParseOptions::default()),
+          parse_instance_script(text, &ParseOptions::default()),
+          parse_flash4(text),
+          parse(text, &decompiler),
+        ];
+        for parsed in all.iter() {
+          assert_eq!(parsed.syntax().text().to_string(), text);
+        }
+        assert_eq!(
+          parse_script_fast(text, &ParseOptions::default()),
+          parse_script(text, &ParseOptions::default())
+        );
+      }
+    }
+  }
+
+  #[test]
+  fn test_nesting_too_deep() {
+    const LEVELS: usize = 100_000;
+    let samples = [
+      format!("{}{}", "{".repeat(LEVELS), "}".repeat(LEVELS)),
+      format!("{}x();", "if (a) ".repeat(LEVELS)),
+      format!("x = {}a;", "-".repeat(LEVELS)),
+      format!("x = {}a;", "!++".repeat(LEVELS)),
+      format!("{}a{};", "f(".repeat(LEVELS), ")".repeat(LEVELS)),
+      format!("{}a;", "a[".repeat(LEVELS)),
+      format!("{}b;", "a = ".repeat(LEVELS)),
+      format!("{}c;", "a ? b : ".repeat(LEVELS)),
+    ];
+    for sample in samples.iter() {
+      let parsed = parse(sample, &ParseOptions::default());
+      assert_eq!(parsed.syntax().text().len(), TextUnit::of_str(sample));
+      assert!(parsed
+        .errors()
+        .iter()
+        .any(|e| e.kind == SyntaxErrorKind::NestingTooDeep));
+    }
+
+    let options = ParseOptions {
+      max_depth: 5,
+      ..ParseOptions::default()
+    };
+    let parsed = parse("{ { f(g(h(x), y)); } } z;", &options);
+    let errors: Vec<(SyntaxErrorKind, TextRange)> = parsed.errors().iter().map(|e| (e.kind, e.range)).collect();
+    assert_eq!(
+      errors,
+      vec![
+        (
+          SyntaxErrorKind::NestingTooDeep,
+          TextRange::from_to(TextUnit::from(8), TextUnit::from(12))
+        ),
+        (
+          SyntaxErrorKind::NestingTooDeep,
+          TextRange::from_to(TextUnit::from(14), TextUnit::from(15))
+        ),
+      ]
+    );
+    let parsed = parse("{ { f(x); } }", &options);
+    assert_eq!(parsed.errors(), &[]);
+  }
+
+  #[test]
+  fn test_parse_options() {
+    let text = "import a.B; var x:Number = 1; class C {} try {} finally {} a === b;";
+    let errors_with = |options: &ParseOptions| -> Vec<(SyntaxErrorKind, TextRange)> {
+      let parsed = parse(text, options);
+      parsed.errors().iter().map(|e| (e.kind, e.range)).collect()
+    };
+    let range = |start: u32, end: u32| TextRange::from_to(TextUnit::from(start), TextUnit::from(end));
+
+    assert_eq!(errors_with(&ParseOptions::default()), vec![]);
+    let as1 = ParseOptions {
+      language: LanguageLevel::As1,
+      ..ParseOptions::default()
+    };
+    assert_eq!(
+      errors_with(&as1),
+      vec![
+        (SyntaxErrorKind::RequiresAs2, range(0, 6)),
+        (SyntaxErrorKind::RequiresAs2, range(17, 24)),
+        (SyntaxErrorKind::RequiresAs2, range(30, 40)),
+      ]
+    );
+    let swf5 = ParseOptions {
+      swf_version: 5,
+      ..ParseOptions::default()
+    };
+    assert_eq!(
+      errors_with(&swf5),
+      vec![
+        (SyntaxErrorKind::RequiresSwfVersion(7), range(41, 44)),
+        (SyntaxErrorKind::RequiresSwfVersion(6), range(61, 64)),
+      ]
+    );
+    assert!(!swf5.is_case_sensitive());
+    assert!(ParseOptions::default().is_case_sensitive());
+    let kinds = |parsed: Parsed| -> Vec<SyntaxKind> {
+      parsed
+        .syntax()
+        .descendants()
+        .filter(|node| node.kind() == SyntaxKind::NodeBinExpr)
+        .flat_map(|node| node.children().map(|child| child.kind()).collect::<Vec<_>>())
+        .collect()
+    };
+    assert_eq!(
+      kinds(parse("x = UNDEFINED + NewLine;", &swf5)),
+      vec![SyntaxKind::NodeUndefinedLit, SyntaxKind::NodeNewlineLit]
+    );
+    assert_eq!(
+      kinds(parse("x = UNDEFINED + NewLine;", &ParseOptions::default())),
+      vec![SyntaxKind::NodeIdent, SyntaxKind::NodeIdent]
+    );
+    let decompiler_swf6 = ParseOptions {
+      swf_version: 6,
+      ..ParseOptions::decompiler()
+    };
+    assert_eq!(
+      kinds(parse("x = _LOC3_ + _loc3_;", &decompiler_swf6)),
+      vec![SyntaxKind::NodeRegister, SyntaxKind::NodeRegister]
+    );
+    assert_eq!(
+      parse_script_fast("_LOC3_;", &decompiler_swf6),
+      parse_script("_loc3_;", &decompiler_swf6)
+    );
+
+    let parsed = parse("x = /clip:y;", &swf5);
+    assert!(!parsed.errors().is_empty());
+    let parsed = parse("x = /clip:y;", &ParseOptions::flash4());
+    assert_eq!(parsed.errors(), &[]);
+    // Target paths are only lexed when requested, not for any SWF 4 script
+    let swf4 = ParseOptions {
+      swf_version: 4,
+      language: LanguageLevel::As1,
+      ..ParseOptions::default()
+    };
+    let parsed = parse("x = c ? a/b:c;", &swf4);
+    assert_eq!(parsed.errors(), &[]);
+    assert!(parsed
+      .syntax()
+      .descendants()
+      .all(|node| node.kind() != SyntaxKind::NodeTargetPath));
+  }
+
+  #[test]
+  fn test_parse_decompiler_dialect() {
+    let text = "§§push(_loc2_);\nloop0:\nwhile (§§pop()) { §§goto(addr8); addr8: }";
+    let kinds = |options: &ParseOptions| -> Vec<SyntaxErrorKind> {
+      let parsed = parse(text, options);
+      parsed.errors().iter().map(|e| e.kind).collect()
+    };
+    assert!(kinds(&ParseOptions::default()).contains(&SyntaxErrorKind::UnexpectedToken));
+    assert!(kinds(&ParseOptions::default()).contains(&SyntaxErrorKind::MissingStatement));
+    assert_eq!(kinds(&ParseOptions::decompiler()), vec![]);
+
+    let parsed = parse("var _loc3_ = _loc3_;", &ParseOptions::decompiler());
+    let kinds: Vec<SyntaxKind> = parsed.syntax().descendants().map(|node| node.kind()).collect();
+    assert!(kinds.contains(&SyntaxKind::NodeIdent));
+    assert!(kinds.contains(&SyntaxKind::NodeRegister));
+
+    assert_eq!(register_index("_loc3_"), Some(3));
+    assert_eq!(register_index("_loc255_"), Some(255));
+    assert_eq!(register_index("_loc256_"), None);
+    assert_eq!(register_index("_loc_"), None);
+    assert_eq!(register_index("_locx_"), None);
+    assert_eq!(register_index("_loc3"), None);
+  }
+
+  #[test]
+  fn test_parse_expr() {
+    let parsed = parse_expr(" a + b ", &ParseOptions::default());
+    assert_eq!(parsed.syntax().kind(), SyntaxKind::NodeExprRoot);
+    assert_eq!(parsed.errors(), &[]);
+
+    let parsed = parse_expr("a b;", &ParseOptions::default());
+    let errors: Vec<(SyntaxErrorKind, TextRange)> = parsed.errors().iter().map(|e| (e.kind, e.range)).collect();
+    assert_eq!(
+      errors,
+      vec![(
+        SyntaxErrorKind::UnexpectedToken,
+        TextRange::from_to(TextUnit::from(2), TextUnit::from(4))
+      )]
+    );
+  }
+
+  #[test]
+  fn test_metadata_values() {
+    let parsed = parse(
+      "class A { [Inspectable(defaultValue=false, minValue=-1, format=null)] var a; }",
+      &ParseOptions::default(),
+    );
+    assert_eq!(parsed.errors(), &[]);
+
+    let parsed = parse(
+      "class A { [Inspectable(minValue=-a)] var a; }",
+      &ParseOptions::default(),
+    );
+    let first_error = parsed.errors().first().map(|e| (e.kind, e.range));
+    assert_eq!(
+      first_error,
+      Some((
+        SyntaxErrorKind::MissingToken(SyntaxKind::TokenNumLit),
+        TextRange::from_to(TextUnit::from(33), TextUnit::from(33))
+      ))
+    );
+  }
+
+  #[test]
+  fn test_contextual_add() {
+    let parsed = parse("obj.add(x);\nadd(1);\nvar add = a add b;", &ParseOptions::default());
+    assert_eq!(parsed.errors(), &[]);
+    let ops: Vec<SyntaxKind> = parsed
+      .syntax()
+      .descendants()
+      .filter(|node| node.kind() == SyntaxKind::NodeBinExpr)
+      .flat_map(|node| node.children_with_tokens().filter_map(|symbol| symbol.into_token()))
+      .map(|token| token.kind())
+      .filter(|kind| !kind.is_trivia())
+      .collect();
+    assert_eq!(ops, vec![SyntaxKind::TokenAdd]);
+    let idents = parsed
+      .syntax()
+      .descendants_with_tokens()
+      .filter_map(|symbol| symbol.into_token())
+      .filter(|token| token.kind() == SyntaxKind::TokenIdent && token.text() == "add")
+      .count();
+    assert_eq!(idents, 3);
+  }
+
+  #[test]
+  fn test_parse_stmt() {
+    let parsed = parse_stmt("if (a) { b(); }\n", &ParseOptions::default());
+    assert_eq!(parsed.syntax().kind(), SyntaxKind::NodeStmtRoot);
+    assert_eq!(parsed.errors(), &[]);
+
+    let parsed = parse_stmt("", &ParseOptions::default());
+    let errors: Vec<(SyntaxErrorKind, TextRange)> = parsed.errors().iter().map(|e| (e.kind, e.range)).collect();
+    assert_eq!(
+      errors,
+      vec![(
+        SyntaxErrorKind::MissingStatement,
+        TextRange::from_to(TextUnit::from(0), TextUnit::from(0))
+      )]
+    );
+
+    let parsed = parse_stmt("a();\nb();", &ParseOptions::default());
+    let errors: Vec<(SyntaxErrorKind, TextRange)> = parsed.errors().iter().map(|e| (e.kind, e.range)).collect();
+    assert_eq!(
+      errors,
+      vec![(
+        SyntaxErrorKind::UnexpectedToken,
+        TextRange::from_to(TextUnit::from(5), TextUnit::from(9))
+      )]
+    );
+  }
+
+  #[test]
+  fn test_class_file_errors() {
+    let parsed = parse_class_file(
+      "import a.B;\nf();\nclass C {}\ninterface D {}\n",
+      &ParseOptions::default(),
+    );
+    assert_eq!(parsed.syntax().kind(), SyntaxKind::NodeClassFile);
+    let errors: Vec<(SyntaxErrorKind, TextRange)> = parsed.errors().iter().map(|e| (e.kind, e.range)).collect();
+    assert_eq!(
+      errors,
+      vec![
+        (
+          SyntaxErrorKind::StatementOutsideClass,
+          TextRange::from_to(TextUnit::from(12), TextUnit::from(16))
+        ),
+        (
+          SyntaxErrorKind::DuplicateTypeDecl,
+          TextRange::from_to(TextUnit::from(28), TextUnit::from(42))
+        ),
+      ]
+    );
+
+    let parsed = parse_class_file("import a.B;\n", &ParseOptions::default());
+    let errors: Vec<(SyntaxErrorKind, TextRange)> = parsed.errors().iter().map(|e| (e.kind, e.range)).collect();
+    assert_eq!(
+      errors,
+      vec![(
+        SyntaxErrorKind::MissingTypeDecl,
+        TextRange::from_to(TextUnit::from(12), TextUnit::from(12))
+      )]
+    );
+  }
+
+  #[test]
+  fn test_instance_script_errors() {
+    let parsed = parse_instance_script(
+      "f();\non (press, hover, keyPress \"<Nope>\") {}\nonClipEvent (press) {}",
+      &ParseOptions::default(),
+    );
+    let errors: Vec<(SyntaxErrorKind, TextRange)> = parsed.errors().iter().map(|e| (e.kind, e.range)).collect();
+    assert_eq!(
+      errors,
+      vec![
+        (
+          SyntaxErrorKind::StatementOutsideHandler,
+          TextRange::from_to(TextUnit::from(0), TextUnit::from(4))
+        ),
+        (
+          SyntaxErrorKind::UnknownEvent,
+          TextRange::from_to(TextUnit::from(16), TextUnit::from(21))
+        ),
+        (
+          SyntaxErrorKind::UnknownKey,
+          TextRange::from_to(TextUnit::from(32), TextUnit::from(40))
+        ),
+        (
+          SyntaxErrorKind::UnknownEvent,
+          TextRange::from_to(TextUnit::from(58), TextUnit::from(63))
+        ),
+      ]
+    );
+  }
+
+  #[test]
+  fn test_member_body_errors() {
+    let parsed = parse(
+      concat!(
+        "class A { function f(); }\n",
+        "intrinsic class B { function f() {} }\n",
+        "interface C { var a; function f() {} }",
+      ),
+      &ParseOptions::default(),
+    );
+    let errors: Vec<(SyntaxErrorKind, TextRange)> = parsed.errors().iter().map(|e| (e.kind, e.range)).collect();
+    assert_eq!(
+      errors,
+      vec![
+        (
+          SyntaxErrorKind::MissingMethodBody,
+          TextRange::from_to(TextUnit::from(22), TextUnit::from(23))
+        ),
+        (
+          SyntaxErrorKind::UnexpectedMethodBody,
+          TextRange::from_to(TextUnit::from(59), TextUnit::from(61))
+        ),
+        (
+          SyntaxErrorKind::UnexpectedInterfaceField,
+          TextRange::from_to(TextUnit::from(78), TextUnit::from(84))
+        ),
+        (
+          SyntaxErrorKind::UnexpectedMethodBody,
+          TextRange::from_to(TextUnit::from(98), TextUnit::from(100))
+        ),
+      ]
+    );
+  }
+
+  /// Returns the dump of the tree with the errors, to compare parse results.
+  fn dump_parsed(parsed: &Parsed) -> String {
+    let mut dump: Vec<u8> = Vec::new();
+    dump_node(&mut dump, &parsed.syntax()).unwrap();
+    format!("{}{:?}", String::from_utf8(dump).unwrap(), parsed.errors())
+  }
+
+  #[test]
+  fn test_reparse_paths() {
+    let text = "a = 1;\nif (b) { c = \"x\"; }\n";
+    let parsed = parse(text, &ParseOptions::default());
+    let edit = |start: u32, end: u32, insert: &str| TextEdit {
+      range: TextRange::from_to(TextUnit::from(start), TextUnit::from(end)),
+      insert: insert.to_string(),
+    };
+
+    // Identifier and string literal
+    for edit in [edit(16, 17, "cd"), edit(21, 22, "yz")].iter() {
+      let reparsed = parsed.reparse_token(edit).unwrap();
+      assert_eq!(
+        dump_parsed(&reparsed),
+        dump_parsed(&parse(&edit.apply(text), &ParseOptions::default()))
+      );
+    }
+    // The identifier would become a keyword or the number would merge with the identifier
+    assert!(parsed.reparse_token(&edit(16, 17, "if")).is_none());
+    assert!(parsed.reparse_token(&edit(0, 1, "1")).is_none());
+
+    // Block
+    let edit = edit(24, 24, " d(); e = ;");
+    assert!(parsed.reparse_token(&edit).is_none());
+    let reparsed = parsed.reparse_block(&edit).unwrap();
+    assert_eq!(
+      dump_parsed(&reparsed),
+      dump_parsed(&parse(&edit.apply(text), &ParseOptions::default()))
+    );
+    assert_eq!(reparsed.errors().len(), 1);
+  }
+
+  #[test]
+  fn test_reparse_random_edits() {
+    let samples: [(&str, fn(&str, &ParseOptions) -> Parsed); 4] = [
+      (
+        "if (a) {\n  b = \"str\" + 12;\n  while (c) { d(e, 3.5); }\n} else {\n  f();\n}\n",
+        parse,
+      ),
+      (
+        "on (press, keyPress \"<Left>\") {\n  gotoAndPlay(\"frame\");\n  x += 1;\n}\n",
+        parse_instance_script,
+      ),
+      (
+        "class A {\n  var x:Number;\n  function f(a) {\n    if (a) { return a * 2; }\n    return -a;\n  }\n}\n",
+        parse_class_file,
+      ),
+      ("try { a(); } catch (e) { trace(e); } finally { b = c ? 1 : 2; }", parse),
+    ];
+    let inserts = [
+      "", "a", "b1", "2", "\"", "\"s\"", " ", "\n", ";", "{", "}", "(", ")", "if (x) {", "/*", "*/", "get", "on",
+      "add", "=", "++", ".5",
+    ];
+    // Xorshift generator, so failures are reproducible
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut random = |bound: usize| -> usize {
+      state ^= state << 13;
+      state ^= state >> 7;
+      state ^= state << 17;
+      (state % bound as u64) as usize
+    };
+    for (sample, parse_fn) in samples.iter() {
+      for _ in 0..20 {
+        let mut text = sample.to_string();
+        let mut parsed = parse_fn(&text, &ParseOptions::default());
+        for _ in 0..20 {
+          let start = random(text.len() + 1);
+          let end = start + random(4).min(text.len() - start);
+          let edit = TextEdit {
+            range: TextRange::from_to(TextUnit::from_usize(start), TextUnit::from_usize(end)),
+            insert: inserts[random(inserts.len())].to_string(),
+          };
+          let reparsed = parsed.reparse(&edit);
+          text = edit.apply(&text);
+          assert_eq!(
+            dump_parsed(&reparsed),
+            dump_parsed(&parse_fn(&text, &ParseOptions::default())),
+            "{:?}",
+            text
+          );
+          parsed = reparsed;
+        }
+      }
+    }
+  }
+
   fn dump_node<W: io::Write>(writer: &mut W, node: &SyntaxNode) -> Result<(), io::Error> {
     let mut indent = 0;
     for event in node.preorder_with_tokens() {
@@ -8,18 +8,74 @@ pub trait Syntax: Sized {
   type Script: Script<Self>;
 
   type Stmt: Stmt<Self>;
-  type TraceStmt: TraceStmt<Self>;
+  type BlockStmt: BlockStmt<Self>;
+  type BreakStmt: BreakStmt;
+  type ClassDecl: ClassDecl<Self>;
+  type ClipEventHandler: ClipEventHandler<Self>;
+  type ContinueStmt: ContinueStmt;
+  type DoWhileStmt: DoWhileStmt<Self>;
+  type EmptyStmt: EmptyStmt;
   type ExprStmt: ExprStmt<Self>;
+  type ForInStmt: ForInStmt<Self>;
+  type ForStmt: ForStmt<Self>;
+  type GotoStmt: GotoStmt;
+  type IfStmt: IfStmt<Self>;
+  type ImportDecl: ImportDecl;
+  type IfFrameLoadedStmt: IfFrameLoadedStmt<Self>;
+  type InterfaceDecl: InterfaceDecl<Self>;
+  type LabelledStmt: LabelledStmt<Self>;
+  type OnHandler: OnHandler<Self>;
+  type PushStmt: PushStmt<Self>;
+  type ReturnStmt: ReturnStmt<Self>;
+  type SwitchStmt: SwitchStmt<Self>;
+  type TellTargetStmt: TellTargetStmt<Self>;
+  type ThrowStmt: ThrowStmt<Self>;
+  type TraceStmt: TraceStmt<Self>;
+  type TryStmt: TryStmt<Self>;
+  type VarDecl: VarDecl<Self>;
+  type WhileStmt: WhileStmt<Self>;
+  type WithStmt: WithStmt<Self>;
+
+  type ForInit: ForInit<Self>;
+  type ForInTarget: ForInTarget<Self>;
+  type CaseClause: CaseClause<Self>;
+  type CatchClause: CatchClause<Self>;
+  type ClassMember: ClassMember<Self>;
+  type FieldDecl: FieldDecl<Self>;
+  type MethodDecl: MethodDecl<Self>;
+  type Param: Param<Self>;
+  type Metadata: Metadata<Self>;
+  type MetadataArg: MetadataArg;
+  type VarDeclarator: VarDeclarator<Self>;
 
   type Expr: Expr<Self>;
   type SeqExpr: SeqExpr<Self>;
   type AssignExpr: AssignExpr<Self>;
+  type CondExpr: CondExpr<Self>;
   type BinExpr: BinExpr<Self>;
+  type CallExpr: CallExpr<Self>;
+  type IdentExpr: IdentExpr;
+  type MemberExpr: MemberExpr<Self>;
+  type NumLit: NumLit;
+  type PopExpr: PopExpr;
   type StrLit: StrLit;
+  type BoolLit: BoolLit;
+  type NullLit: NullLit;
+  type UndefinedLit: UndefinedLit;
+  type NewlineLit: NewlineLit;
+  type ThisExpr: ThisExpr;
+  type SuperExpr: SuperExpr;
+  type UnaryExpr: UnaryExpr<Self>;
+  type UpdateExpr: UpdateExpr<Self>;
 
   type Pat: Pat<Self>;
   type MemberPat: MemberPat<Self>;
   type IdentPat: IdentPat;
+
+  type TargetPath: TargetPath;
+  type Register: Register;
+
+  type TypeRef: TypeRef;
 }
 
 /// Script root node
@@ -42,11 +98,601 @@ pub trait Stmt<S: Syntax> {
 
 /// Represents the result of downcasting an expression.
 pub enum StmtCast<'a, S: Syntax> {
-  Trace(&'a S::TraceStmt),
+  Block(&'a S::BlockStmt),
+  Break(&'a S::BreakStmt),
+  Class(&'a S::ClassDecl),
+  ClipEventHandler(&'a S::ClipEventHandler),
+  Continue(&'a S::ContinueStmt),
+  DoWhile(&'a S::DoWhileStmt),
+  Empty(&'a S::EmptyStmt),
   Expr(&'a S::ExprStmt),
+  For(&'a S::ForStmt),
+  ForIn(&'a S::ForInStmt),
+  Goto(&'a S::GotoStmt),
+  If(&'a S::IfStmt),
+  Import(&'a S::ImportDecl),
+  IfFrameLoaded(&'a S::IfFrameLoadedStmt),
+  Interface(&'a S::InterfaceDecl),
+  Labelled(&'a S::LabelledStmt),
+  OnHandler(&'a S::OnHandler),
+  Push(&'a S::PushStmt),
+  Return(&'a S::ReturnStmt),
+  Switch(&'a S::SwitchStmt),
+  TellTarget(&'a S::TellTargetStmt),
+  Throw(&'a S::ThrowStmt),
+  Trace(&'a S::TraceStmt),
+  Try(&'a S::TryStmt),
+  VarDecl(&'a S::VarDecl),
+  While(&'a S::WhileStmt),
+  With(&'a S::WithStmt),
   SyntaxError,
 }
 
+/// Block statement
+///
+/// Corresponds to a list of statements between braces.
+pub trait BlockStmt<S: Syntax> {
+  #[cfg(not(feature = "gat"))]
+  fn stmts<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a S::Stmt> + 'a>;
+
+  #[cfg(feature = "gat")]
+  type Stmts<'a>: ExactSizeIterator<Item = &'a S::Stmt>;
+
+  #[cfg(feature = "gat")]
+  fn stmts(&self) -> Self::Stmts<'_>;
+}
+
+/// Empty statement: a lone semicolon
+pub trait EmptyStmt {}
+
+/// Decompiler jump: `§§goto(label);`
+pub trait GotoStmt {
+  /// Label of the target statement
+  fn label(&self) -> &str;
+}
+
+/// `if` statement, with an optional `else` branch
+pub trait IfStmt<S: Syntax> {
+  fn test(&self) -> &S::Expr;
+  fn consequent(&self) -> &S::Stmt;
+  fn alternate(&self) -> Option<&S::Stmt>;
+}
+
+/// `while` loop
+pub trait WhileStmt<S: Syntax> {
+  fn test(&self) -> &S::Expr;
+  fn body(&self) -> &S::Stmt;
+}
+
+/// `do ... while` loop
+pub trait DoWhileStmt<S: Syntax> {
+  fn body(&self) -> &S::Stmt;
+  fn test(&self) -> &S::Expr;
+}
+
+/// `for (init; test; update)` loop
+///
+/// Each clause of the head may be omitted.
+pub trait ForStmt<S: Syntax> {
+  fn init(&self) -> Option<&S::ForInit>;
+  fn test(&self) -> Option<&S::Expr>;
+  fn update(&self) -> Option<&S::Expr>;
+  fn body(&self) -> &S::Stmt;
+}
+
+/// Initializer clause of a `for` loop
+pub trait ForInit<S: Syntax> {
+  /// Downcast the initializer to its concrete type.
+  fn cast(&self) -> ForInitCast<S>;
+}
+
+/// Represents the result of downcasting a `for` loop initializer.
+pub enum ForInitCast<'a, S: Syntax> {
+  VarDecl(&'a S::VarDecl),
+  Expr(&'a S::Expr),
+}
+
+/// `for (target in object)` loop
+pub trait ForInStmt<S: Syntax> {
+  fn target(&self) -> &S::ForInTarget;
+  fn object(&self) -> &S::Expr;
+  fn body(&self) -> &S::Stmt;
+}
+
+/// Target of a `for in` loop: a variable declaration or a pattern
+pub trait ForInTarget<S: Syntax> {
+  /// Downcast the target to its concrete type.
+  fn cast(&self) -> ForInTargetCast<S>;
+}
+
+/// Represents the result of downcasting a `for in` loop target.
+pub enum ForInTargetCast<'a, S: Syntax> {
+  VarDecl(&'a S::VarDecl),
+  Pat(&'a S::Pat),
+}
+
+/// Variable declaration: `var a = 1, b`
+pub trait VarDecl<S: Syntax> {
+  #[cfg(not(feature = "gat"))]
+  fn declarators<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a S::VarDeclarator> + 'a>;
+
+  #[cfg(feature = "gat")]
+  type Declarators<'a>: ExactSizeIterator<Item = &'a S::VarDeclarator>;
+
+  #[cfg(feature = "gat")]
+  fn declarators(&self) -> Self::Declarators<'_>;
+}
+
+/// Single variable declarator, with an optional type and initial value
+pub trait VarDeclarator<S: Syntax> {
+  fn name(&self) -> &str;
+  fn type_annotation(&self) -> Option<&S::TypeRef>;
+  fn init(&self) -> Option<&S::Expr>;
+}
+
+/// Class declaration
+pub trait ClassDecl<S: Syntax> {
+  /// Metadata tags decorating the declaration, in source order
+  #[cfg(not(feature = "gat"))]
+  fn metadata<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a S::Metadata> + 'a>;
+
+  #[cfg(feature = "gat")]
+  type Metadata<'a>: ExactSizeIterator<Item = &'a S::Metadata>;
+
+  #[cfg(feature = "gat")]
+  fn metadata(&self) -> Self::Metadata<'_>;
+
+  /// Fully qualified name of the class
+  fn name(&self) -> &S::TypeRef;
+
+  fn is_dynamic(&self) -> bool;
+
+  fn is_intrinsic(&self) -> bool;
+
+  fn extends(&self) -> Option<&S::TypeRef>;
+
+  /// Implemented interfaces, in source order
+  #[cfg(not(feature = "gat"))]
+  fn implements<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a S::TypeRef> + 'a>;
+
+  #[cfg(feature = "gat")]
+  type Implements<'a>: ExactSizeIterator<Item = &'a S::TypeRef>;
+
+  #[cfg(feature = "gat")]
+  fn implements(&self) -> Self::Implements<'_>;
+
+  /// Member variables and methods, in source order
+  #[cfg(not(feature = "gat"))]
+  fn members<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a S::ClassMember> + 'a>;
+
+  #[cfg(feature = "gat")]
+  type Members<'a>: ExactSizeIterator<Item = &'a S::ClassMember>;
+
+  #[cfg(feature = "gat")]
+  fn members(&self) -> Self::Members<'_>;
+}
+
+/// `import` declaration: `import a.b.C;` or `import a.b.*;`
+pub trait ImportDecl {
+  /// Segments of the package path, from the outermost package
+  #[cfg(not(feature = "gat"))]
+  fn package<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a str> + 'a>;
+
+  #[cfg(feature = "gat")]
+  type Package<'a>: ExactSizeIterator<Item = &'a str>;
+
+  #[cfg(feature = "gat")]
+  fn package(&self) -> Self::Package<'_>;
+
+  /// Imported name, or `None` for a wildcard import
+  fn name(&self) -> Option<&str>;
+}
+
+/// Interface declaration
+pub trait InterfaceDecl<S: Syntax> {
+  /// Metadata tags decorating the declaration, in source order
+  #[cfg(not(feature = "gat"))]
+  fn metadata<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a S::Metadata> + 'a>;
+
+  #[cfg(feature = "gat")]
+  type Metadata<'a>: ExactSizeIterator<Item = &'a S::Metadata>;
+
+  #[cfg(feature = "gat")]
+  fn metadata(&self) -> Self::Metadata<'_>;
+
+  /// Fully qualified name of the interface
+  fn name(&self) -> &S::TypeRef;
+
+  fn extends(&self) -> Option<&S::TypeRef>;
+
+  /// Method signatures, in source order
+  #[cfg(not(feature = "gat"))]
+  fn methods<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a S::MethodDecl> + 'a>;
+
+  #[cfg(feature = "gat")]
+  type Methods<'a>: ExactSizeIterator<Item = &'a S::MethodDecl>;
+
+  #[cfg(feature = "gat")]
+  fn methods(&self) -> Self::Methods<'_>;
+}
+
+/// Member of a class
+pub trait ClassMember<S: Syntax> {
+  /// Downcast the member to its concrete type.
+  fn cast(&self) -> ClassMemberCast<S>;
+}
+
+/// Represents the result of downcasting a class member.
+pub enum ClassMemberCast<'a, S: Syntax> {
+  Field(&'a S::FieldDecl),
+  Method(&'a S::MethodDecl),
+}
+
+/// Member variable declaration: `static var a:Number = 1, b;`
+pub trait FieldDecl<S: Syntax> {
+  /// Metadata tags decorating the declaration, in source order
+  #[cfg(not(feature = "gat"))]
+  fn metadata<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a S::Metadata> + 'a>;
+
+  #[cfg(feature = "gat")]
+  type Metadata<'a>: ExactSizeIterator<Item = &'a S::Metadata>;
+
+  #[cfg(feature = "gat")]
+  fn metadata(&self) -> Self::Metadata<'_>;
+
+  fn modifiers(&self) -> Modifiers;
+
+  #[cfg(not(feature = "gat"))]
+  fn declarators<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a S::VarDeclarator> + 'a>;
+
+  #[cfg(feature = "gat")]
+  type Declarators<'a>: ExactSizeIterator<Item = &'a S::VarDeclarator>;
+
+  #[cfg(feature = "gat")]
+  fn declarators(&self) -> Self::Declarators<'_>;
+}
+
+/// Method declaration, including accessors and the constructor
+pub trait MethodDecl<S: Syntax> {
+  /// Metadata tags decorating the declaration, in source order
+  #[cfg(not(feature = "gat"))]
+  fn metadata<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a S::Metadata> + 'a>;
+
+  #[cfg(feature = "gat")]
+  type Metadata<'a>: ExactSizeIterator<Item = &'a S::Metadata>;
+
+  #[cfg(feature = "gat")]
+  fn metadata(&self) -> Self::Metadata<'_>;
+
+  fn modifiers(&self) -> Modifiers;
+
+  fn kind(&self) -> MethodKind;
+
+  fn name(&self) -> &str;
+
+  #[cfg(not(feature = "gat"))]
+  fn params<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a S::Param> + 'a>;
+
+  #[cfg(feature = "gat")]
+  type Params<'a>: ExactSizeIterator<Item = &'a S::Param>;
+
+  #[cfg(feature = "gat")]
+  fn params(&self) -> Self::Params<'_>;
+
+  fn return_type(&self) -> Option<&S::TypeRef>;
+
+  /// Body of the method, or `None` for methods declared without body
+  fn body(&self) -> Option<&S::BlockStmt>;
+}
+
+/// Parameter of a method, with an optional type
+pub trait Param<S: Syntax> {
+  fn name(&self) -> &str;
+  fn type_annotation(&self) -> Option<&S::TypeRef>;
+}
+
+/// Metadata tag: `[Inspectable(defaultValue="left")]`
+pub trait Metadata<S: Syntax> {
+  fn name(&self) -> &str;
+
+  #[cfg(not(feature = "gat"))]
+  fn args<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a S::MetadataArg> + 'a>;
+
+  #[cfg(feature = "gat")]
+  type Args<'a>: ExactSizeIterator<Item = &'a S::MetadataArg>;
+
+  #[cfg(feature = "gat")]
+  fn args(&self) -> Self::Args<'_>;
+}
+
+/// Argument of a metadata tag: `"click"` or `defaultValue="left"`
+pub trait MetadataArg {
+  fn key(&self) -> Option<&str>;
+
+  /// Value of the argument: the content of a string literal, or the raw text of other values
+  fn value(&self) -> &str;
+}
+
+/// Modifiers of a class member
+#[derive(Copy, Clone, Debug, Default, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct Modifiers {
+  /// `static` modifier
+  pub is_static: bool,
+  /// `public` or `private` modifier, if any
+  pub access: Option<Access>,
+}
+
+/// Represents the access modifiers of class members.
+#[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum Access {
+  /// Access modifier `private`
+  Private,
+  /// Access modifier `public`
+  Public,
+}
+
+/// Represents the kinds of methods.
+#[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum MethodKind {
+  /// Method with the same name as its class
+  Constructor,
+  /// Getter: `function get name()`
+  Get,
+  /// Regular method
+  Method,
+  /// Setter: `function set name(value)`
+  Set,
+}
+
+/// `break` statement
+pub trait BreakStmt {
+  /// Label of the statement to exit, or `None` for the innermost loop or `switch`
+  fn label(&self) -> Option<&str>;
+}
+
+/// `continue` statement
+pub trait ContinueStmt {
+  /// Label of the loop to continue, or `None` for the innermost loop
+  fn label(&self) -> Option<&str>;
+}
+
+/// Labelled statement: `label: body`
+pub trait LabelledStmt<S: Syntax> {
+  fn label(&self) -> &str;
+  fn body(&self) -> &S::Stmt;
+}
+
+/// `ifFrameLoaded` statement: runs its body if the frame is loaded
+pub trait IfFrameLoadedStmt<S: Syntax> {
+  fn scene(&self) -> Option<&S::Expr>;
+  fn frame(&self) -> &S::Expr;
+  fn body(&self) -> &S::Stmt;
+}
+
+/// `tellTarget` statement: runs its body with the target clip as the current timeline
+pub trait TellTargetStmt<S: Syntax> {
+  fn scene(&self) -> Option<&S::Expr>;
+  fn target(&self) -> &S::Expr;
+  fn body(&self) -> &S::Stmt;
+}
+
+/// `with` statement: runs its body with the object added to the scope chain
+pub trait WithStmt<S: Syntax> {
+  fn object(&self) -> &S::Expr;
+  fn body(&self) -> &S::Stmt;
+}
+
+/// Button event handler: `on (press, release) { ... }`
+///
+/// It is only valid at the top level of button instance scripts.
+pub trait OnHandler<S: Syntax> {
+  fn events(&self) -> &[ButtonEvent];
+  fn body(&self) -> &S::BlockStmt;
+}
+
+/// Movie clip event handler: `onClipEvent (enterFrame) { ... }`
+///
+/// It is only valid at the top level of movie clip instance scripts.
+pub trait ClipEventHandler<S: Syntax> {
+  fn events(&self) -> &[ClipEvent];
+  fn body(&self) -> &S::BlockStmt;
+}
+
+/// Represents the events of button handlers.
+///
+/// They correspond to the conditions of the SWF `ButtonCondAction` record.
+#[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum ButtonEvent {
+  /// `dragOut`: `OverDownToOutDown` and `OverDownToIdle`
+  DragOut,
+  /// `dragOver`: `OutDownToOverDown` and `IdleToOverDown`
+  DragOver,
+  /// `keyPress "<Key>"`, with the SWF key code
+  KeyPress(u8),
+  /// `press`: `OverUpToOverDown`
+  Press,
+  /// `release`: `OverDownToOverUp`
+  Release,
+  /// `releaseOutside`: `OutDownToIdle`
+  ReleaseOutside,
+  /// `rollOut`: `OverUpToIdle`
+  RollOut,
+  /// `rollOver`: `IdleToOverUp`
+  RollOver,
+}
+
+impl ButtonEvent {
+  /// Returns the event with the provided name, except `keyPress` which needs a key.
+  pub fn from_name(name: &str) -> Option<Self> {
+    let event = match name {
+      "dragOut" => Self::DragOut,
+      "dragOver" => Self::DragOver,
+      "press" => Self::Press,
+      "release" => Self::Release,
+      "releaseOutside" => Self::ReleaseOutside,
+      "rollOut" => Self::RollOut,
+      "rollOver" => Self::RollOver,
+      _ => return None,
+    };
+    Some(event)
+  }
+
+  /// Returns the SWF key code for the key of a `keyPress` event.
+  ///
+  /// The key is either a printable ASCII character, or a special key name
+  /// between angle brackets such as `<Enter>`.
+  pub fn key_code(key: &str) -> Option<u8> {
+    let code = match key {
+      "<Left>" => 1,
+      "<Right>" => 2,
+      "<Home>" => 3,
+      "<End>" => 4,
+      "<Insert>" => 5,
+      "<Delete>" => 6,
+      "<Backspace>" => 8,
+      "<Enter>" => 13,
+      "<Up>" => 14,
+      "<Down>" => 15,
+      "<PageUp>" => 16,
+      "<PageDown>" => 17,
+      "<Tab>" => 18,
+      "<Escape>" => 19,
+      "<Space>" => 32,
+      _ => {
+        let mut chars = key.chars();
+        return match (chars.next(), chars.next()) {
+          (Some(c @ ' '..='~'), None) => Some(c as u8),
+          _ => None,
+        };
+      }
+    };
+    Some(code)
+  }
+}
+
+/// Represents the events of movie clip handlers.
+///
+/// They correspond to the flags of the SWF `ClipEventFlags` record.
+#[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum ClipEvent {
+  /// `construct`: `Construct`
+  Construct,
+  /// `data`: `Data`
+  Data,
+  /// `enterFrame`: `EnterFrame`
+  EnterFrame,
+  /// `initialize`: `Initialize`
+  Initialize,
+  /// `keyDown`: `KeyDown`
+  KeyDown,
+  /// `keyUp`: `KeyUp`
+  KeyUp,
+  /// `load`: `Load`
+  Load,
+  /// `mouseDown`: `MouseDown`
+  MouseDown,
+  /// `mouseMove`: `MouseMove`
+  MouseMove,
+  /// `mouseUp`: `MouseUp`
+  MouseUp,
+  /// `unload`: `Unload`
+  Unload,
+}
+
+impl ClipEvent {
+  pub fn from_name(name: &str) -> Option<Self> {
+    let event = match name {
+      "construct" => Self::Construct,
+      "data" => Self::Data,
+      "enterFrame" => Self::EnterFrame,
+      "initialize" => Self::Initialize,
+      "keyDown" => Self::KeyDown,
+      "keyUp" => Self::KeyUp,
+      "load" => Self::Load,
+      "mouseDown" => Self::MouseDown,
+      "mouseMove" => Self::MouseMove,
+      "mouseUp" => Self::MouseUp,
+      "unload" => Self::Unload,
+      _ => return None,
+    };
+    Some(event)
+  }
+}
+
+/// Decompiler stack push: `§§push(value);`
+pub trait PushStmt<S: Syntax> {
+  fn value(&self) -> &S::Expr;
+}
+
+/// `return` statement
+pub trait ReturnStmt<S: Syntax> {
+  fn value(&self) -> Option<&S::Expr>;
+}
+
+/// `switch` statement
+pub trait SwitchStmt<S: Syntax> {
+  fn discriminant(&self) -> &S::Expr;
+
+  /// Clauses, in source order
+  #[cfg(not(feature = "gat"))]
+  fn clauses<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a S::CaseClause> + 'a>;
+
+  #[cfg(feature = "gat")]
+  type Clauses<'a>: ExactSizeIterator<Item = &'a S::CaseClause>;
+
+  #[cfg(feature = "gat")]
+  fn clauses(&self) -> Self::Clauses<'_>;
+}
+
+/// `case` or `default` clause of a `switch` statement
+pub trait CaseClause<S: Syntax> {
+  /// Value tested by a `case` clause, or `None` for the `default` clause
+  fn test(&self) -> Option<&S::Expr>;
+
+  #[cfg(not(feature = "gat"))]
+  fn body<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a S::Stmt> + 'a>;
+
+  #[cfg(feature = "gat")]
+  type Body<'a>: ExactSizeIterator<Item = &'a S::Stmt>;
+
+  #[cfg(feature = "gat")]
+  fn body(&self) -> Self::Body<'_>;
+}
+
+/// `throw` statement
+pub trait ThrowStmt<S: Syntax> {
+  fn value(&self) -> &S::Expr;
+}
+
+/// `try` statement
+///
+/// It has at least one `catch` clause or a `finally` block.
+pub trait TryStmt<S: Syntax> {
+  fn block(&self) -> &S::BlockStmt;
+
+  /// `catch` clauses, in source order
+  #[cfg(not(feature = "gat"))]
+  fn handlers<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a S::CatchClause> + 'a>;
+
+  #[cfg(feature = "gat")]
+  type Handlers<'a>: ExactSizeIterator<Item = &'a S::CatchClause>;
+
+  #[cfg(feature = "gat")]
+  fn handlers(&self) -> Self::Handlers<'_>;
+
+  fn finalizer(&self) -> Option<&S::BlockStmt>;
+}
+
+/// `catch` clause of a `try` statement
+pub trait CatchClause<S: Syntax> {
+  fn param(&self) -> &S::IdentPat;
+
+  /// Type of the caught values handled by this clause, or `None` to catch everything
+  fn param_type(&self) -> Option<&S::TypeRef>;
+
+  fn body(&self) -> &S::BlockStmt;
+}
+
 pub trait TraceStmt<S: Syntax> {
   fn value(&self) -> &S::Expr;
 }
@@ -63,8 +709,26 @@ pub trait Expr<S: Syntax> {
 
 /// Represents the result of downcasting an expression.
 pub enum ExprCast<'a, S: Syntax> {
+  Assign(&'a S::AssignExpr),
+  Bin(&'a S::BinExpr),
+  BoolLit(&'a S::BoolLit),
+  Call(&'a S::CallExpr),
+  Cond(&'a S::CondExpr),
+  Ident(&'a S::IdentExpr),
+  Member(&'a S::MemberExpr),
+  NewlineLit(&'a S::NewlineLit),
+  NullLit(&'a S::NullLit),
+  NumLit(&'a S::NumLit),
+  Pop(&'a S::PopExpr),
+  Register(&'a S::Register),
   Seq(&'a S::SeqExpr),
   StrLit(&'a S::StrLit),
+  Super(&'a S::SuperExpr),
+  TargetPath(&'a S::TargetPath),
+  This(&'a S::ThisExpr),
+  UndefinedLit(&'a S::UndefinedLit),
+  Unary(&'a S::UnaryExpr),
+  Update(&'a S::UpdateExpr),
   Error,
 }
 
@@ -82,12 +746,53 @@ pub trait SeqExpr<S: Syntax> {
   fn exprs(&self) -> Self::Iter<'_>;
 }
 
+/// Assignment expression
+///
+/// Compound assignments such as `a += 1` are represented with their operator.
 pub trait AssignExpr<S: Syntax> {
+  fn op(&self) -> AssignOp;
   fn target(&self) -> &S::Pat;
   fn value(&self) -> &S::Expr;
 }
 
+/// Represents all the assignment operators.
+#[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum AssignOp {
+  /// Assignment operator `=`
+  Assign,
+  /// Assignment operator `+=`
+  Add,
+  /// Assignment operator `&=`
+  BitAnd,
+  /// Assignment operator `|=`
+  BitOr,
+  /// Assignment operator `^=`
+  BitXor,
+  /// Assignment operator `/=`
+  Divide,
+  /// Assignment operator `<<=`
+  LeftShift,
+  /// Assignment operator `*=`
+  Multiply,
+  /// Assignment operator `%=`
+  Remainder,
+  /// Assignment operator `>>=`
+  SignedRightShift,
+  /// Assignment operator `-=`
+  Subtract,
+  /// Assignment operator `>>>=`
+  UnsignedRightShift,
+}
+
+/// Conditional expression: `test ? consequent : alternate`
+pub trait CondExpr<S: Syntax> {
+  fn test(&self) -> &S::Expr;
+  fn consequent(&self) -> &S::Expr;
+  fn alternate(&self) -> &S::Expr;
+}
+
 pub trait BinExpr<S: Syntax> {
+  fn op(&self) -> BinOp;
   fn left(&self) -> &S::Expr;
   fn right(&self) -> &S::Expr;
 }
@@ -109,6 +814,10 @@ pub enum BinOp {
   Equals,
   /// Binary operator `>`
   Greater,
+  /// Binary operator `>=`
+  GreaterOrEquals,
+  /// Binary operator `in`
+  In,
   /// Binary operator `instanceof`
   InstanceOf,
   /// Binary operator `add`
@@ -117,6 +826,12 @@ pub enum BinOp {
   LeftShift,
   /// Binary operator `<`
   Less,
+  /// Binary operator `<=`
+  LessOrEquals,
+  /// Binary operator `&&`
+  LogicalAnd,
+  /// Binary operator `||`
+  LogicalOr,
   /// Binary operator `*`
   Multiply,
   /// Binary operator `!=`
@@ -135,10 +850,106 @@ pub enum BinOp {
   UnsignedRightShift,
 }
 
+/// Represents all the prefix unary operators.
+#[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum UnaryOp {
+  /// Unary operator `~`
+  BitNot,
+  /// Unary operator `delete`
+  Delete,
+  /// Unary operator `!`
+  LogicalNot,
+  /// Unary operator `-`
+  Minus,
+  /// Unary operator `+`
+  Plus,
+  /// Unary operator `typeof`
+  TypeOf,
+  /// Unary operator `void`
+  Void,
+}
+
+pub trait UnaryExpr<S: Syntax> {
+  fn op(&self) -> UnaryOp;
+  fn arg(&self) -> &S::Expr;
+}
+
+/// Represents the increment and decrement operators.
+#[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum UpdateOp {
+  /// Update operator `--`
+  Decrement,
+  /// Update operator `++`
+  Increment,
+}
+
+/// Increment or decrement expression
+pub trait UpdateExpr<S: Syntax> {
+  fn op(&self) -> UpdateOp;
+  /// `true` for `++a`, `false` for `a++`.
+  fn prefix(&self) -> bool;
+  fn arg(&self) -> &S::Expr;
+}
+
+pub trait CallExpr<S: Syntax> {
+  fn callee(&self) -> &S::Expr;
+
+  #[cfg(not(feature = "gat"))]
+  fn args<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a S::Expr> + 'a>;
+
+  #[cfg(feature = "gat")]
+  type Args<'a>: ExactSizeIterator<Item = &'a S::Expr>;
+
+  #[cfg(feature = "gat")]
+  fn args(&self) -> Self::Args<'_>;
+}
+
+/// Member access expression
+///
+/// `base.key` is represented with a string literal key, the same way as `base["key"]`.
+pub trait MemberExpr<S: Syntax> {
+  fn base(&self) -> &S::Expr;
+  fn key(&self) -> &S::Expr;
+}
+
+/// Identifier reference expression
+pub trait IdentExpr {
+  fn name(&self) -> &str;
+}
+
+pub trait NumLit {
+  fn value(&self) -> f64;
+}
+
+/// Decompiler stack pop: `§§pop()`
+pub trait PopExpr {}
+
 pub trait StrLit {
   fn value(&self) -> Cow<str>;
 }
 
+/// Boolean literal: `true` or `false`
+pub trait BoolLit {
+  fn value(&self) -> bool;
+}
+
+/// `null` literal
+pub trait NullLit {}
+
+/// `undefined` literal
+pub trait UndefinedLit {}
+
+/// `newline` literal, the legacy constant for `"\n"`
+pub trait NewlineLit {}
+
+/// `this` expression
+pub trait ThisExpr {}
+
+/// `super` expression
+///
+/// It is used to call the parent constructor or to access the parent methods.
+pub trait SuperExpr {}
+
 /// Trait representing any ActionScript pattern (assignment left-hand side)
 pub trait Pat<S: Syntax> {
   /// Downcast the pattern to its concrete type.
@@ -149,6 +960,8 @@ pub trait Pat<S: Syntax> {
 pub enum PatCast<'a, S: Syntax> {
   Member(&'a S::MemberPat),
   Ident(&'a S::IdentPat),
+  Register(&'a S::Register),
+  TargetPath(&'a S::TargetPath),
   SyntaxError,
 }
 
@@ -160,3 +973,48 @@ pub trait MemberPat<S: Syntax> {
 pub trait IdentPat {
   fn name(&self) -> &str;
 }
+
+/// Flash 4 target path with a variable: `/clip/sub:score`, `../:counter`
+///
+/// It is both an expression and an assignment pattern.
+pub trait TargetPath {
+  /// `true` if the path starts with a slash, from the root timeline
+  fn is_absolute(&self) -> bool;
+
+  /// Segments of the path to the timeline: names, `.` or `..`
+  #[cfg(not(feature = "gat"))]
+  fn segments<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a str> + 'a>;
+
+  #[cfg(feature = "gat")]
+  type Segments<'a>: ExactSizeIterator<Item = &'a str>;
+
+  #[cfg(feature = "gat")]
+  fn segments(&self) -> Self::Segments<'_>;
+
+  /// Name of the variable, after the colon
+  fn name(&self) -> &str;
+}
+
+/// Decompiler register local: `_loc3_`
+///
+/// It is both an expression and an assignment pattern.
+pub trait Register {
+  /// Number of the register
+  fn index(&self) -> u8;
+}
+
+/// Reference to a possibly qualified type: `Error` or `mx.core.UIObject`
+pub trait TypeRef {
+  /// Segments of the package path, from the outermost package
+  #[cfg(not(feature = "gat"))]
+  fn package<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a str> + 'a>;
+
+  #[cfg(feature = "gat")]
+  type Package<'a>: ExactSizeIterator<Item = &'a str>;
+
+  #[cfg(feature = "gat")]
+  fn package(&self) -> Self::Package<'_>;
+
+  /// Name of the type, without its package
+  fn name(&self) -> &str;
+}
@@ -1,5 +1,7 @@
 use crate::types::ast::traits;
 use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
 use std::marker::PhantomData;
 
 pub struct BorrowedSyntax<'a> {
@@ -10,55 +12,971 @@ impl<'a> traits::Syntax for BorrowedSyntax<'a> {
   type Script = Script<'a>;
 
   type Stmt = Stmt<'a>;
-  type TraceStmt = TraceStmt<'a>;
+  type BlockStmt = BlockStmt<'a>;
+  type BreakStmt = BreakStmt<'a>;
+  type ClassDecl = ClassDecl<'a>;
+  type ClipEventHandler = ClipEventHandler<'a>;
+  type ContinueStmt = ContinueStmt<'a>;
+  type DoWhileStmt = DoWhileStmt<'a>;
+  type EmptyStmt = EmptyStmt;
   type ExprStmt = ExprStmt<'a>;
+  type ForInStmt = ForInStmt<'a>;
+  type ForStmt = ForStmt<'a>;
+  type GotoStmt = GotoStmt<'a>;
+  type IfStmt = IfStmt<'a>;
+  type ImportDecl = ImportDecl<'a>;
+  type IfFrameLoadedStmt = IfFrameLoadedStmt<'a>;
+  type InterfaceDecl = InterfaceDecl<'a>;
+  type LabelledStmt = LabelledStmt<'a>;
+  type OnHandler = OnHandler<'a>;
+  type PushStmt = PushStmt<'a>;
+  type ReturnStmt = ReturnStmt<'a>;
+  type SwitchStmt = SwitchStmt<'a>;
+  type TellTargetStmt = TellTargetStmt<'a>;
+  type ThrowStmt = ThrowStmt<'a>;
+  type TraceStmt = TraceStmt<'a>;
+  type TryStmt = TryStmt<'a>;
+  type VarDecl = VarDecl<'a>;
+  type WhileStmt = WhileStmt<'a>;
+  type WithStmt = WithStmt<'a>;
+
+  type ForInit = ForInit<'a>;
+  type ForInTarget = ForInTarget<'a>;
+  type CaseClause = CaseClause<'a>;
+  type CatchClause = CatchClause<'a>;
+  type ClassMember = ClassMember<'a>;
+  type FieldDecl = FieldDecl<'a>;
+  type MethodDecl = MethodDecl<'a>;
+  type Param = Param<'a>;
+  type Metadata = Metadata<'a>;
+  type MetadataArg = MetadataArg<'a>;
+  type VarDeclarator = VarDeclarator<'a>;
+
+  type Expr = Expr<'a>;
+  type SeqExpr = SeqExpr<'a>;
+  type AssignExpr = AssignExpr<'a>;
+  type CondExpr = CondExpr<'a>;
+  type BinExpr = BinExpr<'a>;
+  type CallExpr = CallExpr<'a>;
+  type IdentExpr = IdentExpr<'a>;
+  type MemberExpr = MemberExpr<'a>;
+  type NumLit = NumLit;
+  type PopExpr = PopExpr;
+  type StrLit = StrLit<'a>;
+  type BoolLit = BoolLit;
+  type NullLit = NullLit;
+  type UndefinedLit = UndefinedLit;
+  type NewlineLit = NewlineLit;
+  type ThisExpr = ThisExpr;
+  type SuperExpr = SuperExpr;
+  type UnaryExpr = UnaryExpr<'a>;
+  type UpdateExpr = UpdateExpr<'a>;
+
+  type Pat = Pat<'a>;
+  type MemberPat = MemberPat<'a>;
+  type IdentPat = IdentPat<'a>;
+
+  type TargetPath = TargetPath<'a>;
+  type Register = Register;
+
+  type TypeRef = TypeRef<'a>;
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct Script<'a> {
+  pub loc: (),
+  pub stmts: &'a [Stmt<'a>],
+}
+
+impl<'s> traits::Script<BorrowedSyntax<'s>> for Script<'s> {
+  #[cfg(not(feature = "gat"))]
+  fn stmts<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a Stmt<'s>> + 'a> {
+    Box::new(self.stmts.iter())
+  }
+
+  #[cfg(feature = "gat")]
+  type Stmts<'a> = core::slice::Iter<'a, Stmt<'a>>;
+
+  #[cfg(feature = "gat")]
+  fn stmts(&self) -> Self::Stmts<'_> {
+    self.stmts.iter()
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub enum Stmt<'a> {
+  Block(BlockStmt<'a>),
+  Break(BreakStmt<'a>),
+  Class(ClassDecl<'a>),
+  ClipEventHandler(ClipEventHandler<'a>),
+  Continue(ContinueStmt<'a>),
+  DoWhile(DoWhileStmt<'a>),
+  Empty(EmptyStmt),
+  Expr(ExprStmt<'a>),
+  For(ForStmt<'a>),
+  ForIn(ForInStmt<'a>),
+  Goto(GotoStmt<'a>),
+  If(IfStmt<'a>),
+  Import(ImportDecl<'a>),
+  IfFrameLoaded(IfFrameLoadedStmt<'a>),
+  Interface(InterfaceDecl<'a>),
+  Labelled(LabelledStmt<'a>),
+  OnHandler(OnHandler<'a>),
+  Push(PushStmt<'a>),
+  Return(ReturnStmt<'a>),
+  Switch(SwitchStmt<'a>),
+  TellTarget(TellTargetStmt<'a>),
+  Throw(ThrowStmt<'a>),
+  Trace(TraceStmt<'a>),
+  Try(TryStmt<'a>),
+  VarDecl(VarDecl<'a>),
+  While(WhileStmt<'a>),
+  With(WithStmt<'a>),
+  SyntaxError,
+}
+
+impl<'a> traits::Stmt<BorrowedSyntax<'a>> for Stmt<'a> {
+  fn cast<'b>(&'b self) -> traits::StmtCast<'b, BorrowedSyntax<'a>> {
+    match self {
+      Stmt::Block(ref e) => traits::StmtCast::Block(e),
+      Stmt::Break(ref e) => traits::StmtCast::Break(e),
+      Stmt::Class(ref e) => traits::StmtCast::Class(e),
+      Stmt::ClipEventHandler(ref e) => traits::StmtCast::ClipEventHandler(e),
+      Stmt::Continue(ref e) => traits::StmtCast::Continue(e),
+      Stmt::DoWhile(ref e) => traits::StmtCast::DoWhile(e),
+      Stmt::Empty(ref e) => traits::StmtCast::Empty(e),
+      Stmt::Expr(ref e) => traits::StmtCast::Expr(e),
+      Stmt::For(ref e) => traits::StmtCast::For(e),
+      Stmt::ForIn(ref e) => traits::StmtCast::ForIn(e),
+      Stmt::Goto(ref e) => traits::StmtCast::Goto(e),
+      Stmt::If(ref e) => traits::StmtCast::If(e),
+      Stmt::Import(ref e) => traits::StmtCast::Import(e),
+      Stmt::IfFrameLoaded(ref e) => traits::StmtCast::IfFrameLoaded(e),
+      Stmt::Interface(ref e) => traits::StmtCast::Interface(e),
+      Stmt::Labelled(ref e) => traits::StmtCast::Labelled(e),
+      Stmt::OnHandler(ref e) => traits::StmtCast::OnHandler(e),
+      Stmt::Push(ref e) => traits::StmtCast::Push(e),
+      Stmt::Return(ref e) => traits::StmtCast::Return(e),
+      Stmt::Switch(ref e) => traits::StmtCast::Switch(e),
+      Stmt::TellTarget(ref e) => traits::StmtCast::TellTarget(e),
+      Stmt::Throw(ref e) => traits::StmtCast::Throw(e),
+      Stmt::Trace(ref e) => traits::StmtCast::Trace(e),
+      Stmt::Try(ref e) => traits::StmtCast::Try(e),
+      Stmt::VarDecl(ref e) => traits::StmtCast::VarDecl(e),
+      Stmt::While(ref e) => traits::StmtCast::While(e),
+      Stmt::With(ref e) => traits::StmtCast::With(e),
+      Stmt::SyntaxError => traits::StmtCast::SyntaxError,
+    }
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct BlockStmt<'a> {
+  pub loc: (),
+  pub stmts: &'a [Stmt<'a>],
+}
+
+impl<'s> traits::BlockStmt<BorrowedSyntax<'s>> for BlockStmt<'s> {
+  #[cfg(not(feature = "gat"))]
+  fn stmts<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a Stmt<'s>> + 'a> {
+    Box::new(self.stmts.iter())
+  }
+
+  #[cfg(feature = "gat")]
+  type Stmts<'a> = core::slice::Iter<'a, Stmt<'a>>;
+
+  #[cfg(feature = "gat")]
+  fn stmts(&self) -> Self::Stmts<'_> {
+    self.stmts.iter()
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct EmptyStmt {
+  pub loc: (),
+}
+
+impl traits::EmptyStmt for EmptyStmt {}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct GotoStmt<'a> {
+  pub loc: (),
+  pub label: &'a str,
+}
+
+impl traits::GotoStmt for GotoStmt<'_> {
+  fn label(&self) -> &str {
+    self.label
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct IfStmt<'a> {
+  pub loc: (),
+  pub test: &'a Expr<'a>,
+  pub consequent: &'a Stmt<'a>,
+  pub alternate: Option<&'a Stmt<'a>>,
+}
+
+impl<'a> traits::IfStmt<BorrowedSyntax<'a>> for IfStmt<'a> {
+  fn test(&self) -> &Expr<'a> {
+    self.test
+  }
+
+  fn consequent(&self) -> &Stmt<'a> {
+    self.consequent
+  }
+
+  fn alternate(&self) -> Option<&Stmt<'a>> {
+    self.alternate
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct WhileStmt<'a> {
+  pub loc: (),
+  pub test: &'a Expr<'a>,
+  pub body: &'a Stmt<'a>,
+}
+
+impl<'a> traits::WhileStmt<BorrowedSyntax<'a>> for WhileStmt<'a> {
+  fn test(&self) -> &Expr<'a> {
+    self.test
+  }
+
+  fn body(&self) -> &Stmt<'a> {
+    self.body
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct DoWhileStmt<'a> {
+  pub loc: (),
+  pub body: &'a Stmt<'a>,
+  pub test: &'a Expr<'a>,
+}
+
+impl<'a> traits::DoWhileStmt<BorrowedSyntax<'a>> for DoWhileStmt<'a> {
+  fn body(&self) -> &Stmt<'a> {
+    self.body
+  }
+
+  fn test(&self) -> &Expr<'a> {
+    self.test
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct ForStmt<'a> {
+  pub loc: (),
+  pub init: Option<&'a ForInit<'a>>,
+  pub test: Option<&'a Expr<'a>>,
+  pub update: Option<&'a Expr<'a>>,
+  pub body: &'a Stmt<'a>,
+}
+
+impl<'a> traits::ForStmt<BorrowedSyntax<'a>> for ForStmt<'a> {
+  fn init(&self) -> Option<&ForInit<'a>> {
+    self.init
+  }
+
+  fn test(&self) -> Option<&Expr<'a>> {
+    self.test
+  }
+
+  fn update(&self) -> Option<&Expr<'a>> {
+    self.update
+  }
+
+  fn body(&self) -> &Stmt<'a> {
+    self.body
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub enum ForInit<'a> {
+  VarDecl(VarDecl<'a>),
+  Expr(&'a Expr<'a>),
+}
+
+impl<'a> traits::ForInit<BorrowedSyntax<'a>> for ForInit<'a> {
+  fn cast<'b>(&'b self) -> traits::ForInitCast<'b, BorrowedSyntax<'a>> {
+    match self {
+      ForInit::VarDecl(ref e) => traits::ForInitCast::VarDecl(e),
+      ForInit::Expr(e) => traits::ForInitCast::Expr(*e),
+    }
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct ForInStmt<'a> {
+  pub loc: (),
+  pub target: &'a ForInTarget<'a>,
+  pub object: &'a Expr<'a>,
+  pub body: &'a Stmt<'a>,
+}
+
+impl<'a> traits::ForInStmt<BorrowedSyntax<'a>> for ForInStmt<'a> {
+  fn target(&self) -> &ForInTarget<'a> {
+    self.target
+  }
+
+  fn object(&self) -> &Expr<'a> {
+    self.object
+  }
+
+  fn body(&self) -> &Stmt<'a> {
+    self.body
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub enum ForInTarget<'a> {
+  VarDecl(VarDecl<'a>),
+  Pat(&'a Pat<'a>),
+}
+
+impl<'a> traits::ForInTarget<BorrowedSyntax<'a>> for ForInTarget<'a> {
+  fn cast<'b>(&'b self) -> traits::ForInTargetCast<'b, BorrowedSyntax<'a>> {
+    match self {
+      ForInTarget::VarDecl(ref e) => traits::ForInTargetCast::VarDecl(e),
+      ForInTarget::Pat(e) => traits::ForInTargetCast::Pat(*e),
+    }
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct VarDecl<'a> {
+  pub loc: (),
+  pub declarators: &'a [VarDeclarator<'a>],
+}
+
+impl<'s> traits::VarDecl<BorrowedSyntax<'s>> for VarDecl<'s> {
+  #[cfg(not(feature = "gat"))]
+  fn declarators<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a VarDeclarator<'s>> + 'a> {
+    Box::new(self.declarators.iter())
+  }
+
+  #[cfg(feature = "gat")]
+  type Declarators<'a> = core::slice::Iter<'a, VarDeclarator<'a>>;
+
+  #[cfg(feature = "gat")]
+  fn declarators(&self) -> Self::Declarators<'_> {
+    self.declarators.iter()
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct VarDeclarator<'a> {
+  pub loc: (),
+  pub name: &'a str,
+  pub type_annotation: Option<TypeRef<'a>>,
+  pub init: Option<&'a Expr<'a>>,
+}
+
+impl<'a> traits::VarDeclarator<BorrowedSyntax<'a>> for VarDeclarator<'a> {
+  fn name(&self) -> &str {
+    self.name
+  }
+
+  fn type_annotation(&self) -> Option<&TypeRef<'a>> {
+    self.type_annotation.as_ref()
+  }
+
+  fn init(&self) -> Option<&Expr<'a>> {
+    self.init
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct ClassDecl<'a> {
+  pub loc: (),
+  pub metadata: &'a [Metadata<'a>],
+  pub name: TypeRef<'a>,
+  pub is_dynamic: bool,
+  pub is_intrinsic: bool,
+  pub extends: Option<TypeRef<'a>>,
+  pub implements: &'a [TypeRef<'a>],
+  pub members: &'a [ClassMember<'a>],
+}
+
+impl<'s> traits::ClassDecl<BorrowedSyntax<'s>> for ClassDecl<'s> {
+  #[cfg(not(feature = "gat"))]
+  fn metadata<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a Metadata<'s>> + 'a> {
+    Box::new(self.metadata.iter())
+  }
+
+  #[cfg(feature = "gat")]
+  type Metadata<'a> = core::slice::Iter<'a, Metadata<'a>>;
+
+  #[cfg(feature = "gat")]
+  fn metadata(&self) -> Self::Metadata<'_> {
+    self.metadata.iter()
+  }
+
+  fn name(&self) -> &TypeRef<'s> {
+    &self.name
+  }
+
+  fn is_dynamic(&self) -> bool {
+    self.is_dynamic
+  }
+
+  fn is_intrinsic(&self) -> bool {
+    self.is_intrinsic
+  }
+
+  fn extends(&self) -> Option<&TypeRef<'s>> {
+    self.extends.as_ref()
+  }
+
+  #[cfg(not(feature = "gat"))]
+  fn implements<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a TypeRef<'s>> + 'a> {
+    Box::new(self.implements.iter())
+  }
+
+  #[cfg(feature = "gat")]
+  type Implements<'a> = core::slice::Iter<'a, TypeRef<'a>>;
+
+  #[cfg(feature = "gat")]
+  fn implements(&self) -> Self::Implements<'_> {
+    self.implements.iter()
+  }
+
+  #[cfg(not(feature = "gat"))]
+  fn members<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a ClassMember<'s>> + 'a> {
+    Box::new(self.members.iter())
+  }
+
+  #[cfg(feature = "gat")]
+  type Members<'a> = core::slice::Iter<'a, ClassMember<'a>>;
+
+  #[cfg(feature = "gat")]
+  fn members(&self) -> Self::Members<'_> {
+    self.members.iter()
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct ImportDecl<'a> {
+  pub loc: (),
+  pub package: &'a [&'a str],
+  pub name: Option<&'a str>,
+}
+
+impl traits::ImportDecl for ImportDecl<'_> {
+  #[cfg(not(feature = "gat"))]
+  fn package<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a str> + 'a> {
+    Box::new(self.package.iter().copied())
+  }
+
+  #[cfg(feature = "gat")]
+  type Package<'a> = core::iter::Copied<core::slice::Iter<'a, &'a str>>;
+
+  #[cfg(feature = "gat")]
+  fn package(&self) -> Self::Package<'_> {
+    self.package.iter().copied()
+  }
+
+  fn name(&self) -> Option<&str> {
+    self.name
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct InterfaceDecl<'a> {
+  pub loc: (),
+  pub metadata: &'a [Metadata<'a>],
+  pub name: TypeRef<'a>,
+  pub extends: Option<TypeRef<'a>>,
+  pub methods: &'a [MethodDecl<'a>],
+}
+
+impl<'s> traits::InterfaceDecl<BorrowedSyntax<'s>> for InterfaceDecl<'s> {
+  #[cfg(not(feature = "gat"))]
+  fn metadata<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a Metadata<'s>> + 'a> {
+    Box::new(self.metadata.iter())
+  }
+
+  #[cfg(feature = "gat")]
+  type Metadata<'a> = core::slice::Iter<'a, Metadata<'a>>;
+
+  #[cfg(feature = "gat")]
+  fn metadata(&self) -> Self::Metadata<'_> {
+    self.metadata.iter()
+  }
+
+  fn name(&self) -> &TypeRef<'s> {
+    &self.name
+  }
+
+  fn extends(&self) -> Option<&TypeRef<'s>> {
+    self.extends.as_ref()
+  }
+
+  #[cfg(not(feature = "gat"))]
+  fn methods<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a MethodDecl<'s>> + 'a> {
+    Box::new(self.methods.iter())
+  }
+
+  #[cfg(feature = "gat")]
+  type Methods<'a> = core::slice::Iter<'a, MethodDecl<'a>>;
+
+  #[cfg(feature = "gat")]
+  fn methods(&self) -> Self::Methods<'_> {
+    self.methods.iter()
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub enum ClassMember<'a> {
+  Field(FieldDecl<'a>),
+  Method(MethodDecl<'a>),
+}
+
+impl<'a> traits::ClassMember<BorrowedSyntax<'a>> for ClassMember<'a> {
+  fn cast<'b>(&'b self) -> traits::ClassMemberCast<'b, BorrowedSyntax<'a>> {
+    match self {
+      ClassMember::Field(ref e) => traits::ClassMemberCast::Field(e),
+      ClassMember::Method(ref e) => traits::ClassMemberCast::Method(e),
+    }
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct FieldDecl<'a> {
+  pub loc: (),
+  pub metadata: &'a [Metadata<'a>],
+  pub modifiers: traits::Modifiers,
+  pub declarators: &'a [VarDeclarator<'a>],
+}
+
+impl<'s> traits::FieldDecl<BorrowedSyntax<'s>> for FieldDecl<'s> {
+  #[cfg(not(feature = "gat"))]
+  fn metadata<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a Metadata<'s>> + 'a> {
+    Box::new(self.metadata.iter())
+  }
+
+  #[cfg(feature = "gat")]
+  type Metadata<'a> = core::slice::Iter<'a, Metadata<'a>>;
+
+  #[cfg(feature = "gat")]
+  fn metadata(&self) -> Self::Metadata<'_> {
+    self.metadata.iter()
+  }
+
+  fn modifiers(&self) -> traits::Modifiers {
+    self.modifiers
+  }
+
+  #[cfg(not(feature = "gat"))]
+  fn declarators<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a VarDeclarator<'s>> + 'a> {
+    Box::new(self.declarators.iter())
+  }
+
+  #[cfg(feature = "gat")]
+  type Declarators<'a> = core::slice::Iter<'a, VarDeclarator<'a>>;
+
+  #[cfg(feature = "gat")]
+  fn declarators(&self) -> Self::Declarators<'_> {
+    self.declarators.iter()
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct MethodDecl<'a> {
+  pub loc: (),
+  pub metadata: &'a [Metadata<'a>],
+  pub modifiers: traits::Modifiers,
+  pub kind: traits::MethodKind,
+  pub name: &'a str,
+  pub params: &'a [Param<'a>],
+  pub return_type: Option<TypeRef<'a>>,
+  pub body: Option<&'a BlockStmt<'a>>,
+}
+
+impl<'s> traits::MethodDecl<BorrowedSyntax<'s>> for MethodDecl<'s> {
+  #[cfg(not(feature = "gat"))]
+  fn metadata<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a Metadata<'s>> + 'a> {
+    Box::new(self.metadata.iter())
+  }
+
+  #[cfg(feature = "gat")]
+  type Metadata<'a> = core::slice::Iter<'a, Metadata<'a>>;
+
+  #[cfg(feature = "gat")]
+  fn metadata(&self) -> Self::Metadata<'_> {
+    self.metadata.iter()
+  }
+
+  fn modifiers(&self) -> traits::Modifiers {
+    self.modifiers
+  }
+
+  fn kind(&self) -> traits::MethodKind {
+    self.kind
+  }
+
+  fn name(&self) -> &str {
+    self.name
+  }
+
+  #[cfg(not(feature = "gat"))]
+  fn params<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a Param<'s>> + 'a> {
+    Box::new(self.params.iter())
+  }
+
+  #[cfg(feature = "gat")]
+  type Params<'a> = core::slice::Iter<'a, Param<'a>>;
+
+  #[cfg(feature = "gat")]
+  fn params(&self) -> Self::Params<'_> {
+    self.params.iter()
+  }
+
+  fn return_type(&self) -> Option<&TypeRef<'s>> {
+    self.return_type.as_ref()
+  }
+
+  fn body(&self) -> Option<&BlockStmt<'s>> {
+    self.body
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct Metadata<'a> {
+  pub loc: (),
+  pub name: &'a str,
+  pub args: &'a [MetadataArg<'a>],
+}
+
+impl<'s> traits::Metadata<BorrowedSyntax<'s>> for Metadata<'s> {
+  fn name(&self) -> &str {
+    self.name
+  }
+
+  #[cfg(not(feature = "gat"))]
+  fn args<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a MetadataArg<'s>> + 'a> {
+    Box::new(self.args.iter())
+  }
+
+  #[cfg(feature = "gat")]
+  type Args<'a> = core::slice::Iter<'a, MetadataArg<'a>>;
+
+  #[cfg(feature = "gat")]
+  fn args(&self) -> Self::Args<'_> {
+    self.args.iter()
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct MetadataArg<'a> {
+  pub loc: (),
+  pub key: Option<&'a str>,
+  pub value: &'a str,
+}
+
+impl traits::MetadataArg for MetadataArg<'_> {
+  fn key(&self) -> Option<&str> {
+    self.key
+  }
+
+  fn value(&self) -> &str {
+    self.value
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct Param<'a> {
+  pub loc: (),
+  pub name: &'a str,
+  pub type_annotation: Option<TypeRef<'a>>,
+}
+
+impl<'s> traits::Param<BorrowedSyntax<'s>> for Param<'s> {
+  fn name(&self) -> &str {
+    self.name
+  }
+
+  fn type_annotation(&self) -> Option<&TypeRef<'s>> {
+    self.type_annotation.as_ref()
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct BreakStmt<'a> {
+  pub loc: (),
+  pub label: Option<&'a str>,
+}
+
+impl traits::BreakStmt for BreakStmt<'_> {
+  fn label(&self) -> Option<&str> {
+    self.label
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct ContinueStmt<'a> {
+  pub loc: (),
+  pub label: Option<&'a str>,
+}
+
+impl traits::ContinueStmt for ContinueStmt<'_> {
+  fn label(&self) -> Option<&str> {
+    self.label
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct LabelledStmt<'a> {
+  pub loc: (),
+  pub label: &'a str,
+  pub body: &'a Stmt<'a>,
+}
+
+impl<'s> traits::LabelledStmt<BorrowedSyntax<'s>> for LabelledStmt<'s> {
+  fn label(&self) -> &str {
+    self.label
+  }
+
+  fn body(&self) -> &Stmt<'s> {
+    self.body
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct IfFrameLoadedStmt<'a> {
+  pub loc: (),
+  pub scene: Option<&'a Expr<'a>>,
+  pub frame: &'a Expr<'a>,
+  pub body: &'a Stmt<'a>,
+}
+
+impl<'s> traits::IfFrameLoadedStmt<BorrowedSyntax<'s>> for IfFrameLoadedStmt<'s> {
+  fn scene(&self) -> Option<&Expr<'s>> {
+    self.scene
+  }
+
+  fn frame(&self) -> &Expr<'s> {
+    self.frame
+  }
+
+  fn body(&self) -> &Stmt<'s> {
+    self.body
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct TellTargetStmt<'a> {
+  pub loc: (),
+  pub scene: Option<&'a Expr<'a>>,
+  pub target: &'a Expr<'a>,
+  pub body: &'a Stmt<'a>,
+}
+
+impl<'s> traits::TellTargetStmt<BorrowedSyntax<'s>> for TellTargetStmt<'s> {
+  fn scene(&self) -> Option<&Expr<'s>> {
+    self.scene
+  }
+
+  fn target(&self) -> &Expr<'s> {
+    self.target
+  }
+
+  fn body(&self) -> &Stmt<'s> {
+    self.body
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct WithStmt<'a> {
+  pub loc: (),
+  pub object: &'a Expr<'a>,
+  pub body: &'a Stmt<'a>,
+}
+
+impl<'s> traits::WithStmt<BorrowedSyntax<'s>> for WithStmt<'s> {
+  fn object(&self) -> &Expr<'s> {
+    self.object
+  }
+
+  fn body(&self) -> &Stmt<'s> {
+    self.body
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct OnHandler<'a> {
+  pub loc: (),
+  pub events: &'a [traits::ButtonEvent],
+  pub body: &'a BlockStmt<'a>,
+}
+
+impl<'s> traits::OnHandler<BorrowedSyntax<'s>> for OnHandler<'s> {
+  fn events(&self) -> &[traits::ButtonEvent] {
+    self.events
+  }
+
+  fn body(&self) -> &BlockStmt<'s> {
+    self.body
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct ClipEventHandler<'a> {
+  pub loc: (),
+  pub events: &'a [traits::ClipEvent],
+  pub body: &'a BlockStmt<'a>,
+}
+
+impl<'s> traits::ClipEventHandler<BorrowedSyntax<'s>> for ClipEventHandler<'s> {
+  fn events(&self) -> &[traits::ClipEvent] {
+    self.events
+  }
+
+  fn body(&self) -> &BlockStmt<'s> {
+    self.body
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct PushStmt<'a> {
+  pub loc: (),
+  pub value: &'a Expr<'a>,
+}
+
+impl<'a> traits::PushStmt<BorrowedSyntax<'a>> for PushStmt<'a> {
+  fn value(&self) -> &Expr<'a> {
+    self.value
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct ReturnStmt<'a> {
+  pub loc: (),
+  pub value: Option<&'a Expr<'a>>,
+}
 
-  type Expr = Expr<'a>;
-  type SeqExpr = SeqExpr<'a>;
-  type AssignExpr = AssignExpr<'a>;
-  type BinExpr = BinExpr<'a>;
-  type StrLit = StrLit<'a>;
+impl<'s> traits::ReturnStmt<BorrowedSyntax<'s>> for ReturnStmt<'s> {
+  fn value(&self) -> Option<&Expr<'s>> {
+    self.value
+  }
+}
 
-  type Pat = Pat<'a>;
-  type MemberPat = MemberPat<'a>;
-  type IdentPat = IdentPat<'a>;
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct SwitchStmt<'a> {
+  pub loc: (),
+  pub discriminant: &'a Expr<'a>,
+  pub clauses: &'a [CaseClause<'a>],
+}
+
+impl<'s> traits::SwitchStmt<BorrowedSyntax<'s>> for SwitchStmt<'s> {
+  fn discriminant(&self) -> &Expr<'s> {
+    self.discriminant
+  }
+
+  #[cfg(not(feature = "gat"))]
+  fn clauses<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a CaseClause<'s>> + 'a> {
+    Box::new(self.clauses.iter())
+  }
+
+  #[cfg(feature = "gat")]
+  type Clauses<'a> = core::slice::Iter<'a, CaseClause<'a>>;
+
+  #[cfg(feature = "gat")]
+  fn clauses(&self) -> Self::Clauses<'_> {
+    self.clauses.iter()
+  }
 }
 
 #[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
-pub struct Script<'a> {
+pub struct CaseClause<'a> {
   pub loc: (),
-  pub stmts: &'a [Stmt<'a>],
+  pub test: Option<&'a Expr<'a>>,
+  pub body: &'a [Stmt<'a>],
 }
 
-impl<'s> traits::Script<BorrowedSyntax<'s>> for Script<'s> {
+impl<'s> traits::CaseClause<BorrowedSyntax<'s>> for CaseClause<'s> {
+  fn test(&self) -> Option<&Expr<'s>> {
+    self.test
+  }
+
   #[cfg(not(feature = "gat"))]
-  fn stmts<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a Stmt<'s>> + 'a> {
-    Box::new(self.stmts.iter())
+  fn body<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a Stmt<'s>> + 'a> {
+    Box::new(self.body.iter())
   }
 
   #[cfg(feature = "gat")]
-  type Stmts<'a> = core::slice::Iter<'a, Stmt<'a>>;
+  type Body<'a> = core::slice::Iter<'a, Stmt<'a>>;
 
   #[cfg(feature = "gat")]
-  fn stmts(&self) -> Self::Stmts<'_> {
-    self.stmts.iter()
+  fn body(&self) -> Self::Body<'_> {
+    self.body.iter()
   }
 }
 
 #[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
-pub enum Stmt<'a> {
-  Trace(TraceStmt<'a>),
-  Expr(ExprStmt<'a>),
-  SyntaxError,
+pub struct ThrowStmt<'a> {
+  pub loc: (),
+  pub value: &'a Expr<'a>,
 }
 
-impl<'a> traits::Stmt<BorrowedSyntax<'a>> for Stmt<'a> {
-  fn cast<'b>(&'b self) -> traits::StmtCast<'b, BorrowedSyntax<'a>> {
-    match self {
-      Stmt::Trace(ref e) => traits::StmtCast::Trace(e),
-      Stmt::Expr(ref e) => traits::StmtCast::Expr(e),
-      Stmt::SyntaxError => traits::StmtCast::SyntaxError,
-    }
+impl<'s> traits::ThrowStmt<BorrowedSyntax<'s>> for ThrowStmt<'s> {
+  fn value(&self) -> &Expr<'s> {
+    self.value
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct TryStmt<'a> {
+  pub loc: (),
+  pub block: &'a BlockStmt<'a>,
+  pub handlers: &'a [CatchClause<'a>],
+  pub finalizer: Option<&'a BlockStmt<'a>>,
+}
+
+impl<'s> traits::TryStmt<BorrowedSyntax<'s>> for TryStmt<'s> {
+  fn block(&self) -> &BlockStmt<'s> {
+    self.block
+  }
+
+  #[cfg(not(feature = "gat"))]
+  fn handlers<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a CatchClause<'s>> + 'a> {
+    Box::new(self.handlers.iter())
+  }
+
+  #[cfg(feature = "gat")]
+  type Handlers<'a> = core::slice::Iter<'a, CatchClause<'a>>;
+
+  #[cfg(feature = "gat")]
+  fn handlers(&self) -> Self::Handlers<'_> {
+    self.handlers.iter()
+  }
+
+  fn finalizer(&self) -> Option<&BlockStmt<'s>> {
+    self.finalizer
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct CatchClause<'a> {
+  pub loc: (),
+  pub param: IdentPat<'a>,
+  pub param_type: Option<TypeRef<'a>>,
+  pub body: &'a BlockStmt<'a>,
+}
+
+impl<'s> traits::CatchClause<BorrowedSyntax<'s>> for CatchClause<'s> {
+  fn param(&self) -> &IdentPat<'s> {
+    &self.param
+  }
+
+  fn param_type(&self) -> Option<&TypeRef<'s>> {
+    self.param_type.as_ref()
+  }
+
+  fn body(&self) -> &BlockStmt<'s> {
+    self.body
   }
 }
 
@@ -88,14 +1006,52 @@ impl<'a> traits::ExprStmt<BorrowedSyntax<'a>> for ExprStmt<'a> {
 
 #[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
 pub enum Expr<'a> {
+  Assign(AssignExpr<'a>),
+  Bin(BinExpr<'a>),
+  BoolLit(BoolLit),
+  Call(CallExpr<'a>),
+  Cond(CondExpr<'a>),
+  Ident(IdentExpr<'a>),
+  Member(MemberExpr<'a>),
+  NewlineLit(NewlineLit),
+  NullLit(NullLit),
+  NumLit(NumLit),
+  Pop(PopExpr),
+  Register(Register),
+  Seq(SeqExpr<'a>),
   StrLit(StrLit<'a>),
+  Super(SuperExpr),
+  TargetPath(TargetPath<'a>),
+  This(ThisExpr),
+  UndefinedLit(UndefinedLit),
+  Unary(UnaryExpr<'a>),
+  Update(UpdateExpr<'a>),
   Error,
 }
 
 impl<'a> traits::Expr<BorrowedSyntax<'a>> for Expr<'a> {
   fn cast<'b>(&'b self) -> traits::ExprCast<'b, BorrowedSyntax<'a>> {
     match self {
+      Expr::Assign(ref e) => traits::ExprCast::Assign(e),
+      Expr::Bin(ref e) => traits::ExprCast::Bin(e),
+      Expr::BoolLit(ref e) => traits::ExprCast::BoolLit(e),
+      Expr::Call(ref e) => traits::ExprCast::Call(e),
+      Expr::Cond(ref e) => traits::ExprCast::Cond(e),
+      Expr::Ident(ref e) => traits::ExprCast::Ident(e),
+      Expr::Member(ref e) => traits::ExprCast::Member(e),
+      Expr::NewlineLit(ref e) => traits::ExprCast::NewlineLit(e),
+      Expr::NullLit(ref e) => traits::ExprCast::NullLit(e),
+      Expr::NumLit(ref e) => traits::ExprCast::NumLit(e),
+      Expr::Pop(ref e) => traits::ExprCast::Pop(e),
+      Expr::Register(ref e) => traits::ExprCast::Register(e),
+      Expr::Seq(ref e) => traits::ExprCast::Seq(e),
       Expr::StrLit(ref e) => traits::ExprCast::StrLit(e),
+      Expr::Super(ref e) => traits::ExprCast::Super(e),
+      Expr::TargetPath(ref e) => traits::ExprCast::TargetPath(e),
+      Expr::This(ref e) => traits::ExprCast::This(e),
+      Expr::UndefinedLit(ref e) => traits::ExprCast::UndefinedLit(e),
+      Expr::Unary(ref e) => traits::ExprCast::Unary(e),
+      Expr::Update(ref e) => traits::ExprCast::Update(e),
       Expr::Error => traits::ExprCast::Error,
     }
   }
@@ -125,11 +1081,16 @@ impl<'s> traits::SeqExpr<BorrowedSyntax<'s>> for SeqExpr<'s> {
 #[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
 pub struct AssignExpr<'a> {
   pub loc: (),
+  pub op: traits::AssignOp,
   pub target: &'a Pat<'a>,
   pub value: &'a Expr<'a>,
 }
 
 impl<'a> traits::AssignExpr<BorrowedSyntax<'a>> for AssignExpr<'a> {
+  fn op(&self) -> traits::AssignOp {
+    self.op
+  }
+
   fn target(&self) -> &Pat<'a> {
     self.target
   }
@@ -139,14 +1100,41 @@ impl<'a> traits::AssignExpr<BorrowedSyntax<'a>> for AssignExpr<'a> {
   }
 }
 
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct CondExpr<'a> {
+  pub loc: (),
+  pub test: &'a Expr<'a>,
+  pub consequent: &'a Expr<'a>,
+  pub alternate: &'a Expr<'a>,
+}
+
+impl<'a> traits::CondExpr<BorrowedSyntax<'a>> for CondExpr<'a> {
+  fn test(&self) -> &Expr<'a> {
+    self.test
+  }
+
+  fn consequent(&self) -> &Expr<'a> {
+    self.consequent
+  }
+
+  fn alternate(&self) -> &Expr<'a> {
+    self.alternate
+  }
+}
+
 #[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
 pub struct BinExpr<'a> {
   pub loc: (),
+  pub op: traits::BinOp,
   pub left: &'a Expr<'a>,
   pub right: &'a Expr<'a>,
 }
 
 impl<'a> traits::BinExpr<BorrowedSyntax<'a>> for BinExpr<'a> {
+  fn op(&self) -> traits::BinOp {
+    self.op
+  }
+
   fn left(&self) -> &Expr<'a> {
     self.left
   }
@@ -156,6 +1144,149 @@ impl<'a> traits::BinExpr<BorrowedSyntax<'a>> for BinExpr<'a> {
   }
 }
 
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct UnaryExpr<'a> {
+  pub loc: (),
+  pub op: traits::UnaryOp,
+  pub arg: &'a Expr<'a>,
+}
+
+impl<'a> traits::UnaryExpr<BorrowedSyntax<'a>> for UnaryExpr<'a> {
+  fn op(&self) -> traits::UnaryOp {
+    self.op
+  }
+
+  fn arg(&self) -> &Expr<'a> {
+    self.arg
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct UpdateExpr<'a> {
+  pub loc: (),
+  pub op: traits::UpdateOp,
+  pub prefix: bool,
+  pub arg: &'a Expr<'a>,
+}
+
+impl<'a> traits::UpdateExpr<BorrowedSyntax<'a>> for UpdateExpr<'a> {
+  fn op(&self) -> traits::UpdateOp {
+    self.op
+  }
+
+  fn prefix(&self) -> bool {
+    self.prefix
+  }
+
+  fn arg(&self) -> &Expr<'a> {
+    self.arg
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct CallExpr<'a> {
+  pub loc: (),
+  pub callee: &'a Expr<'a>,
+  pub args: &'a [Expr<'a>],
+}
+
+impl<'s> traits::CallExpr<BorrowedSyntax<'s>> for CallExpr<'s> {
+  fn callee(&self) -> &Expr<'s> {
+    self.callee
+  }
+
+  #[cfg(not(feature = "gat"))]
+  fn args<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a Expr<'s>> + 'a> {
+    Box::new(self.args.iter())
+  }
+
+  #[cfg(feature = "gat")]
+  type Args<'a> = core::slice::Iter<'a, Expr<'a>>;
+
+  #[cfg(feature = "gat")]
+  fn args(&self) -> Self::Args<'_> {
+    self.args.iter()
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct MemberExpr<'a> {
+  pub loc: (),
+  pub base: &'a Expr<'a>,
+  pub key: &'a Expr<'a>,
+}
+
+impl<'a> traits::MemberExpr<BorrowedSyntax<'a>> for MemberExpr<'a> {
+  fn base(&self) -> &Expr<'a> {
+    self.base
+  }
+
+  fn key(&self) -> &Expr<'a> {
+    self.key
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct IdentExpr<'a> {
+  pub loc: (),
+  pub name: &'a str,
+}
+
+impl traits::IdentExpr for IdentExpr<'_> {
+  fn name(&self) -> &str {
+    self.name
+  }
+}
+
+/// Number literal
+///
+/// Values are compared by their bit representation, so the literal
+/// can be used as a key (e.g. `NaN` is equal to itself).
+#[derive(Debug, Clone)]
+pub struct NumLit {
+  pub loc: (),
+  pub value: f64,
+}
+
+impl traits::NumLit for NumLit {
+  fn value(&self) -> f64 {
+    self.value
+  }
+}
+
+impl PartialEq for NumLit {
+  fn eq(&self, other: &Self) -> bool {
+    self.value.to_bits() == other.value.to_bits()
+  }
+}
+
+impl Eq for NumLit {}
+
+impl PartialOrd for NumLit {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl Ord for NumLit {
+  fn cmp(&self, other: &Self) -> Ordering {
+    self.value.to_bits().cmp(&other.value.to_bits())
+  }
+}
+
+impl Hash for NumLit {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.value.to_bits().hash(state);
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct PopExpr {
+  pub loc: (),
+}
+
+impl traits::PopExpr for PopExpr {}
+
 #[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
 pub struct StrLit<'a> {
   pub loc: (),
@@ -168,10 +1299,59 @@ impl traits::StrLit for StrLit<'_> {
   }
 }
 
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct BoolLit {
+  pub loc: (),
+  pub value: bool,
+}
+
+impl traits::BoolLit for BoolLit {
+  fn value(&self) -> bool {
+    self.value
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct NullLit {
+  pub loc: (),
+}
+
+impl traits::NullLit for NullLit {}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct UndefinedLit {
+  pub loc: (),
+}
+
+impl traits::UndefinedLit for UndefinedLit {}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct NewlineLit {
+  pub loc: (),
+}
+
+impl traits::NewlineLit for NewlineLit {}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct ThisExpr {
+  pub loc: (),
+}
+
+impl traits::ThisExpr for ThisExpr {}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct SuperExpr {
+  pub loc: (),
+}
+
+impl traits::SuperExpr for SuperExpr {}
+
 #[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
 pub enum Pat<'a> {
   Member(MemberPat<'a>),
   Ident(IdentPat<'a>),
+  Register(Register),
+  TargetPath(TargetPath<'a>),
   SyntaxError,
 }
 
@@ -180,6 +1360,8 @@ impl<'a> traits::Pat<BorrowedSyntax<'a>> for Pat<'a> {
     match self {
       Pat::Member(ref e) => traits::PatCast::Member(e),
       Pat::Ident(ref e) => traits::PatCast::Ident(e),
+      Pat::Register(ref e) => traits::PatCast::Register(e),
+      Pat::TargetPath(ref e) => traits::PatCast::TargetPath(e),
       Pat::SyntaxError => traits::PatCast::SyntaxError,
     }
   }
@@ -214,6 +1396,75 @@ impl traits::IdentPat for IdentPat<'_> {
   }
 }
 
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct TargetPath<'a> {
+  pub loc: (),
+  pub is_absolute: bool,
+  pub segments: &'a [&'a str],
+  pub name: &'a str,
+}
+
+impl traits::TargetPath for TargetPath<'_> {
+  fn is_absolute(&self) -> bool {
+    self.is_absolute
+  }
+
+  #[cfg(not(feature = "gat"))]
+  fn segments<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a str> + 'a> {
+    Box::new(self.segments.iter().copied())
+  }
+
+  #[cfg(feature = "gat")]
+  type Segments<'a> = core::iter::Copied<core::slice::Iter<'a, &'a str>>;
+
+  #[cfg(feature = "gat")]
+  fn segments(&self) -> Self::Segments<'_> {
+    self.segments.iter().copied()
+  }
+
+  fn name(&self) -> &str {
+    self.name
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct Register {
+  pub loc: (),
+  pub index: u8,
+}
+
+impl traits::Register for Register {
+  fn index(&self) -> u8 {
+    self.index
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct TypeRef<'a> {
+  pub loc: (),
+  pub package: &'a [&'a str],
+  pub name: &'a str,
+}
+
+impl traits::TypeRef for TypeRef<'_> {
+  #[cfg(not(feature = "gat"))]
+  fn package<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a str> + 'a> {
+    Box::new(self.package.iter().copied())
+  }
+
+  #[cfg(feature = "gat")]
+  type Package<'a> = core::iter::Copied<core::slice::Iter<'a, &'a str>>;
+
+  #[cfg(feature = "gat")]
+  fn package(&self) -> Self::Package<'_> {
+    self.package.iter().copied()
+  }
+
+  fn name(&self) -> &str {
+    self.name
+  }
+}
+
 #[cfg(test)]
 mod seq_expr_tests {
   use super::{Expr, SeqExpr, StrLit};
@@ -1,5 +1,7 @@
 use crate::types::ast::traits;
 use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
 
 #[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
 pub enum OwnedSyntax {}
@@ -8,100 +10,1051 @@ impl traits::Syntax for OwnedSyntax {
   type Script = Script;
 
   type Stmt = Stmt;
+  type BlockStmt = BlockStmt;
+  type BreakStmt = BreakStmt;
+  type ClassDecl = ClassDecl;
+  type ClipEventHandler = ClipEventHandler;
+  type ContinueStmt = ContinueStmt;
+  type DoWhileStmt = DoWhileStmt;
+  type EmptyStmt = EmptyStmt;
   type ExprStmt = ExprStmt;
+  type ForInStmt = ForInStmt;
+  type ForStmt = ForStmt;
+  type GotoStmt = GotoStmt;
+  type IfStmt = IfStmt;
+  type ImportDecl = ImportDecl;
+  type IfFrameLoadedStmt = IfFrameLoadedStmt;
+  type InterfaceDecl = InterfaceDecl;
+  type LabelledStmt = LabelledStmt;
+  type OnHandler = OnHandler;
+  type PushStmt = PushStmt;
+  type ReturnStmt = ReturnStmt;
+  type SwitchStmt = SwitchStmt;
+  type TellTargetStmt = TellTargetStmt;
+  type ThrowStmt = ThrowStmt;
   type TraceStmt = TraceStmt;
+  type TryStmt = TryStmt;
+  type VarDecl = VarDecl;
+  type WhileStmt = WhileStmt;
+  type WithStmt = WithStmt;
+
+  type ForInit = ForInit;
+  type ForInTarget = ForInTarget;
+  type CaseClause = CaseClause;
+  type CatchClause = CatchClause;
+  type ClassMember = ClassMember;
+  type FieldDecl = FieldDecl;
+  type MethodDecl = MethodDecl;
+  type Param = Param;
+  type Metadata = Metadata;
+  type MetadataArg = MetadataArg;
+  type VarDeclarator = VarDeclarator;
 
   type Expr = Expr;
   type SeqExpr = SeqExpr;
   type AssignExpr = AssignExpr;
+  type CondExpr = CondExpr;
   type BinExpr = BinExpr;
+  type CallExpr = CallExpr;
+  type IdentExpr = IdentExpr;
+  type MemberExpr = MemberExpr;
+  type NumLit = NumLit;
+  type PopExpr = PopExpr;
   type StrLit = StrLit;
+  type BoolLit = BoolLit;
+  type NullLit = NullLit;
+  type UndefinedLit = UndefinedLit;
+  type NewlineLit = NewlineLit;
+  type ThisExpr = ThisExpr;
+  type SuperExpr = SuperExpr;
+  type UnaryExpr = UnaryExpr;
+  type UpdateExpr = UpdateExpr;
+
+  type Pat = Pat;
+  type MemberPat = MemberPat;
+  type IdentPat = IdentPat;
+
+  type TargetPath = TargetPath;
+  type Register = Register;
+
+  type TypeRef = TypeRef;
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct Script {
+  pub loc: (),
+  pub stmts: Vec<Stmt>,
+}
+
+impl traits::Script<OwnedSyntax> for Script {
+  #[cfg(not(feature = "gat"))]
+  fn stmts<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a Stmt> + 'a> {
+    Box::new(self.stmts.iter())
+  }
+
+  #[cfg(feature = "gat")]
+  type Stmts<'a> = core::slice::Iter<'a, Stmt>;
+
+  #[cfg(feature = "gat")]
+  fn stmts(&self) -> Self::Stmts<'_> {
+    self.stmts.iter()
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub enum Stmt {
+  Block(BlockStmt),
+  Break(BreakStmt),
+  Class(ClassDecl),
+  ClipEventHandler(ClipEventHandler),
+  Continue(ContinueStmt),
+  DoWhile(DoWhileStmt),
+  Empty(EmptyStmt),
+  Expr(ExprStmt),
+  For(ForStmt),
+  ForIn(ForInStmt),
+  Goto(GotoStmt),
+  If(IfStmt),
+  Import(ImportDecl),
+  IfFrameLoaded(IfFrameLoadedStmt),
+  Interface(InterfaceDecl),
+  Labelled(LabelledStmt),
+  OnHandler(OnHandler),
+  Push(PushStmt),
+  Return(ReturnStmt),
+  Switch(SwitchStmt),
+  TellTarget(TellTargetStmt),
+  /// Abstract Trace Statement
+  ///
+  /// ```aas2
+  /// @trace("Hello, World!");
+  /// ```
+  Throw(ThrowStmt),
+  Trace(TraceStmt),
+  Try(TryStmt),
+  VarDecl(VarDecl),
+  While(WhileStmt),
+  With(WithStmt),
+  SyntaxError,
+}
+
+impl traits::Stmt<OwnedSyntax> for Stmt {
+  fn cast(&self) -> traits::StmtCast<OwnedSyntax> {
+    match self {
+      Stmt::Block(ref e) => traits::StmtCast::Block(e),
+      Stmt::Break(ref e) => traits::StmtCast::Break(e),
+      Stmt::Class(ref e) => traits::StmtCast::Class(e),
+      Stmt::ClipEventHandler(ref e) => traits::StmtCast::ClipEventHandler(e),
+      Stmt::Continue(ref e) => traits::StmtCast::Continue(e),
+      Stmt::DoWhile(ref e) => traits::StmtCast::DoWhile(e),
+      Stmt::Empty(ref e) => traits::StmtCast::Empty(e),
+      Stmt::Expr(ref e) => traits::StmtCast::Expr(e),
+      Stmt::For(ref e) => traits::StmtCast::For(e),
+      Stmt::ForIn(ref e) => traits::StmtCast::ForIn(e),
+      Stmt::Goto(ref e) => traits::StmtCast::Goto(e),
+      Stmt::If(ref e) => traits::StmtCast::If(e),
+      Stmt::Import(ref e) => traits::StmtCast::Import(e),
+      Stmt::IfFrameLoaded(ref e) => traits::StmtCast::IfFrameLoaded(e),
+      Stmt::Interface(ref e) => traits::StmtCast::Interface(e),
+      Stmt::Labelled(ref e) => traits::StmtCast::Labelled(e),
+      Stmt::OnHandler(ref e) => traits::StmtCast::OnHandler(e),
+      Stmt::Push(ref e) => traits::StmtCast::Push(e),
+      Stmt::Return(ref e) => traits::StmtCast::Return(e),
+      Stmt::Switch(ref e) => traits::StmtCast::Switch(e),
+      Stmt::TellTarget(ref e) => traits::StmtCast::TellTarget(e),
+      Stmt::Throw(ref e) => traits::StmtCast::Throw(e),
+      Stmt::Trace(ref e) => traits::StmtCast::Trace(e),
+      Stmt::Try(ref e) => traits::StmtCast::Try(e),
+      Stmt::VarDecl(ref e) => traits::StmtCast::VarDecl(e),
+      Stmt::While(ref e) => traits::StmtCast::While(e),
+      Stmt::With(ref e) => traits::StmtCast::With(e),
+      Stmt::SyntaxError => traits::StmtCast::SyntaxError,
+    }
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct BlockStmt {
+  pub loc: (),
+  pub stmts: Vec<Stmt>,
+}
+
+impl traits::BlockStmt<OwnedSyntax> for BlockStmt {
+  #[cfg(not(feature = "gat"))]
+  fn stmts<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a Stmt> + 'a> {
+    Box::new(self.stmts.iter())
+  }
+
+  #[cfg(feature = "gat")]
+  type Stmts<'a> = core::slice::Iter<'a, Stmt>;
+
+  #[cfg(feature = "gat")]
+  fn stmts(&self) -> Self::Stmts<'_> {
+    self.stmts.iter()
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct EmptyStmt {
+  pub loc: (),
+}
+
+impl traits::EmptyStmt for EmptyStmt {}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct ExprStmt {
+  pub loc: (),
+  pub expr: Box<Expr>,
+}
+
+impl traits::ExprStmt<OwnedSyntax> for ExprStmt {
+  fn expr(&self) -> &Expr {
+    &self.expr
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct GotoStmt {
+  pub loc: (),
+  pub label: String,
+}
+
+impl traits::GotoStmt for GotoStmt {
+  fn label(&self) -> &str {
+    &self.label
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct IfStmt {
+  pub loc: (),
+  pub test: Box<Expr>,
+  pub consequent: Box<Stmt>,
+  pub alternate: Option<Box<Stmt>>,
+}
+
+impl traits::IfStmt<OwnedSyntax> for IfStmt {
+  fn test(&self) -> &Expr {
+    &self.test
+  }
+
+  fn consequent(&self) -> &Stmt {
+    &self.consequent
+  }
+
+  fn alternate(&self) -> Option<&Stmt> {
+    self.alternate.as_deref()
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct ClassDecl {
+  pub loc: (),
+  pub metadata: Vec<Metadata>,
+  pub name: TypeRef,
+  pub is_dynamic: bool,
+  pub is_intrinsic: bool,
+  pub extends: Option<TypeRef>,
+  pub implements: Vec<TypeRef>,
+  pub members: Vec<ClassMember>,
+}
+
+impl traits::ClassDecl<OwnedSyntax> for ClassDecl {
+  #[cfg(not(feature = "gat"))]
+  fn metadata<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a Metadata> + 'a> {
+    Box::new(self.metadata.iter())
+  }
+
+  #[cfg(feature = "gat")]
+  type Metadata<'a> = core::slice::Iter<'a, Metadata>;
+
+  #[cfg(feature = "gat")]
+  fn metadata(&self) -> Self::Metadata<'_> {
+    self.metadata.iter()
+  }
+
+  fn name(&self) -> &TypeRef {
+    &self.name
+  }
+
+  fn is_dynamic(&self) -> bool {
+    self.is_dynamic
+  }
+
+  fn is_intrinsic(&self) -> bool {
+    self.is_intrinsic
+  }
+
+  fn extends(&self) -> Option<&TypeRef> {
+    self.extends.as_ref()
+  }
+
+  #[cfg(not(feature = "gat"))]
+  fn implements<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a TypeRef> + 'a> {
+    Box::new(self.implements.iter())
+  }
+
+  #[cfg(feature = "gat")]
+  type Implements<'a> = core::slice::Iter<'a, TypeRef>;
+
+  #[cfg(feature = "gat")]
+  fn implements(&self) -> Self::Implements<'_> {
+    self.implements.iter()
+  }
+
+  #[cfg(not(feature = "gat"))]
+  fn members<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a ClassMember> + 'a> {
+    Box::new(self.members.iter())
+  }
+
+  #[cfg(feature = "gat")]
+  type Members<'a> = core::slice::Iter<'a, ClassMember>;
+
+  #[cfg(feature = "gat")]
+  fn members(&self) -> Self::Members<'_> {
+    self.members.iter()
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct ImportDecl {
+  pub loc: (),
+  pub package: Vec<String>,
+  pub name: Option<String>,
+}
+
+impl traits::ImportDecl for ImportDecl {
+  #[cfg(not(feature = "gat"))]
+  fn package<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a str> + 'a> {
+    Box::new(self.package.iter().map(String::as_str))
+  }
+
+  #[cfg(feature = "gat")]
+  type Package<'a> = core::iter::Map<core::slice::Iter<'a, String>, fn(&String) -> &str>;
+
+  #[cfg(feature = "gat")]
+  fn package(&self) -> Self::Package<'_> {
+    self.package.iter().map(String::as_str)
+  }
+
+  fn name(&self) -> Option<&str> {
+    self.name.as_deref()
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct InterfaceDecl {
+  pub loc: (),
+  pub metadata: Vec<Metadata>,
+  pub name: TypeRef,
+  pub extends: Option<TypeRef>,
+  pub methods: Vec<MethodDecl>,
+}
+
+impl traits::InterfaceDecl<OwnedSyntax> for InterfaceDecl {
+  #[cfg(not(feature = "gat"))]
+  fn metadata<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a Metadata> + 'a> {
+    Box::new(self.metadata.iter())
+  }
+
+  #[cfg(feature = "gat")]
+  type Metadata<'a> = core::slice::Iter<'a, Metadata>;
+
+  #[cfg(feature = "gat")]
+  fn metadata(&self) -> Self::Metadata<'_> {
+    self.metadata.iter()
+  }
+
+  fn name(&self) -> &TypeRef {
+    &self.name
+  }
+
+  fn extends(&self) -> Option<&TypeRef> {
+    self.extends.as_ref()
+  }
+
+  #[cfg(not(feature = "gat"))]
+  fn methods<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a MethodDecl> + 'a> {
+    Box::new(self.methods.iter())
+  }
+
+  #[cfg(feature = "gat")]
+  type Methods<'a> = core::slice::Iter<'a, MethodDecl>;
+
+  #[cfg(feature = "gat")]
+  fn methods(&self) -> Self::Methods<'_> {
+    self.methods.iter()
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub enum ClassMember {
+  Field(FieldDecl),
+  Method(MethodDecl),
+}
+
+impl traits::ClassMember<OwnedSyntax> for ClassMember {
+  fn cast(&self) -> traits::ClassMemberCast<OwnedSyntax> {
+    match self {
+      ClassMember::Field(ref e) => traits::ClassMemberCast::Field(e),
+      ClassMember::Method(ref e) => traits::ClassMemberCast::Method(e),
+    }
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct FieldDecl {
+  pub loc: (),
+  pub metadata: Vec<Metadata>,
+  pub modifiers: traits::Modifiers,
+  pub declarators: Vec<VarDeclarator>,
+}
+
+impl traits::FieldDecl<OwnedSyntax> for FieldDecl {
+  #[cfg(not(feature = "gat"))]
+  fn metadata<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a Metadata> + 'a> {
+    Box::new(self.metadata.iter())
+  }
+
+  #[cfg(feature = "gat")]
+  type Metadata<'a> = core::slice::Iter<'a, Metadata>;
+
+  #[cfg(feature = "gat")]
+  fn metadata(&self) -> Self::Metadata<'_> {
+    self.metadata.iter()
+  }
+
+  fn modifiers(&self) -> traits::Modifiers {
+    self.modifiers
+  }
+
+  #[cfg(not(feature = "gat"))]
+  fn declarators<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a VarDeclarator> + 'a> {
+    Box::new(self.declarators.iter())
+  }
+
+  #[cfg(feature = "gat")]
+  type Declarators<'a> = core::slice::Iter<'a, VarDeclarator>;
+
+  #[cfg(feature = "gat")]
+  fn declarators(&self) -> Self::Declarators<'_> {
+    self.declarators.iter()
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct MethodDecl {
+  pub loc: (),
+  pub metadata: Vec<Metadata>,
+  pub modifiers: traits::Modifiers,
+  pub kind: traits::MethodKind,
+  pub name: String,
+  pub params: Vec<Param>,
+  pub return_type: Option<TypeRef>,
+  pub body: Option<BlockStmt>,
+}
+
+impl traits::MethodDecl<OwnedSyntax> for MethodDecl {
+  #[cfg(not(feature = "gat"))]
+  fn metadata<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a Metadata> + 'a> {
+    Box::new(self.metadata.iter())
+  }
+
+  #[cfg(feature = "gat")]
+  type Metadata<'a> = core::slice::Iter<'a, Metadata>;
+
+  #[cfg(feature = "gat")]
+  fn metadata(&self) -> Self::Metadata<'_> {
+    self.metadata.iter()
+  }
+
+  fn modifiers(&self) -> traits::Modifiers {
+    self.modifiers
+  }
+
+  fn kind(&self) -> traits::MethodKind {
+    self.kind
+  }
+
+  fn name(&self) -> &str {
+    &self.name
+  }
+
+  #[cfg(not(feature = "gat"))]
+  fn params<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a Param> + 'a> {
+    Box::new(self.params.iter())
+  }
+
+  #[cfg(feature = "gat")]
+  type Params<'a> = core::slice::Iter<'a, Param>;
+
+  #[cfg(feature = "gat")]
+  fn params(&self) -> Self::Params<'_> {
+    self.params.iter()
+  }
+
+  fn return_type(&self) -> Option<&TypeRef> {
+    self.return_type.as_ref()
+  }
+
+  fn body(&self) -> Option<&BlockStmt> {
+    self.body.as_ref()
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct Metadata {
+  pub loc: (),
+  pub name: String,
+  pub args: Vec<MetadataArg>,
+}
+
+impl traits::Metadata<OwnedSyntax> for Metadata {
+  fn name(&self) -> &str {
+    &self.name
+  }
+
+  #[cfg(not(feature = "gat"))]
+  fn args<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a MetadataArg> + 'a> {
+    Box::new(self.args.iter())
+  }
+
+  #[cfg(feature = "gat")]
+  type Args<'a> = core::slice::Iter<'a, MetadataArg>;
+
+  #[cfg(feature = "gat")]
+  fn args(&self) -> Self::Args<'_> {
+    self.args.iter()
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct MetadataArg {
+  pub loc: (),
+  pub key: Option<String>,
+  pub value: String,
+}
+
+impl traits::MetadataArg for MetadataArg {
+  fn key(&self) -> Option<&str> {
+    self.key.as_deref()
+  }
+
+  fn value(&self) -> &str {
+    &self.value
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct Param {
+  pub loc: (),
+  pub name: String,
+  pub type_annotation: Option<TypeRef>,
+}
+
+impl traits::Param<OwnedSyntax> for Param {
+  fn name(&self) -> &str {
+    &self.name
+  }
+
+  fn type_annotation(&self) -> Option<&TypeRef> {
+    self.type_annotation.as_ref()
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct BreakStmt {
+  pub loc: (),
+  pub label: Option<String>,
+}
+
+impl traits::BreakStmt for BreakStmt {
+  fn label(&self) -> Option<&str> {
+    self.label.as_deref()
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct ContinueStmt {
+  pub loc: (),
+  pub label: Option<String>,
+}
+
+impl traits::ContinueStmt for ContinueStmt {
+  fn label(&self) -> Option<&str> {
+    self.label.as_deref()
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct LabelledStmt {
+  pub loc: (),
+  pub label: String,
+  pub body: Box<Stmt>,
+}
+
+impl traits::LabelledStmt<OwnedSyntax> for LabelledStmt {
+  fn label(&self) -> &str {
+    &self.label
+  }
+
+  fn body(&self) -> &Stmt {
+    &self.body
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct IfFrameLoadedStmt {
+  pub loc: (),
+  pub scene: Option<Box<Expr>>,
+  pub frame: Box<Expr>,
+  pub body: Box<Stmt>,
+}
+
+impl traits::IfFrameLoadedStmt<OwnedSyntax> for IfFrameLoadedStmt {
+  fn scene(&self) -> Option<&Expr> {
+    self.scene.as_deref()
+  }
+
+  fn frame(&self) -> &Expr {
+    &self.frame
+  }
+
+  fn body(&self) -> &Stmt {
+    &self.body
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct TellTargetStmt {
+  pub loc: (),
+  pub scene: Option<Box<Expr>>,
+  pub target: Box<Expr>,
+  pub body: Box<Stmt>,
+}
+
+impl traits::TellTargetStmt<OwnedSyntax> for TellTargetStmt {
+  fn scene(&self) -> Option<&Expr> {
+    self.scene.as_deref()
+  }
+
+  fn target(&self) -> &Expr {
+    &self.target
+  }
+
+  fn body(&self) -> &Stmt {
+    &self.body
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct WithStmt {
+  pub loc: (),
+  pub object: Box<Expr>,
+  pub body: Box<Stmt>,
+}
+
+impl traits::WithStmt<OwnedSyntax> for WithStmt {
+  fn object(&self) -> &Expr {
+    &self.object
+  }
+
+  fn body(&self) -> &Stmt {
+    &self.body
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct OnHandler {
+  pub loc: (),
+  pub events: Vec<traits::ButtonEvent>,
+  pub body: BlockStmt,
+}
+
+impl traits::OnHandler<OwnedSyntax> for OnHandler {
+  fn events(&self) -> &[traits::ButtonEvent] {
+    &self.events
+  }
+
+  fn body(&self) -> &BlockStmt {
+    &self.body
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct ClipEventHandler {
+  pub loc: (),
+  pub events: Vec<traits::ClipEvent>,
+  pub body: BlockStmt,
+}
+
+impl traits::ClipEventHandler<OwnedSyntax> for ClipEventHandler {
+  fn events(&self) -> &[traits::ClipEvent] {
+    &self.events
+  }
+
+  fn body(&self) -> &BlockStmt {
+    &self.body
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct PushStmt {
+  pub loc: (),
+  pub value: Box<Expr>,
+}
+
+impl traits::PushStmt<OwnedSyntax> for PushStmt {
+  fn value(&self) -> &Expr {
+    &self.value
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct ReturnStmt {
+  pub loc: (),
+  pub value: Option<Box<Expr>>,
+}
+
+impl traits::ReturnStmt<OwnedSyntax> for ReturnStmt {
+  fn value(&self) -> Option<&Expr> {
+    self.value.as_deref()
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct SwitchStmt {
+  pub loc: (),
+  pub discriminant: Box<Expr>,
+  pub clauses: Vec<CaseClause>,
+}
+
+impl traits::SwitchStmt<OwnedSyntax> for SwitchStmt {
+  fn discriminant(&self) -> &Expr {
+    &self.discriminant
+  }
+
+  #[cfg(not(feature = "gat"))]
+  fn clauses<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a CaseClause> + 'a> {
+    Box::new(self.clauses.iter())
+  }
+
+  #[cfg(feature = "gat")]
+  type Clauses<'a> = core::slice::Iter<'a, CaseClause>;
+
+  #[cfg(feature = "gat")]
+  fn clauses(&self) -> Self::Clauses<'_> {
+    self.clauses.iter()
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct CaseClause {
+  pub loc: (),
+  pub test: Option<Box<Expr>>,
+  pub body: Vec<Stmt>,
+}
+
+impl traits::CaseClause<OwnedSyntax> for CaseClause {
+  fn test(&self) -> Option<&Expr> {
+    self.test.as_deref()
+  }
+
+  #[cfg(not(feature = "gat"))]
+  fn body<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a Stmt> + 'a> {
+    Box::new(self.body.iter())
+  }
+
+  #[cfg(feature = "gat")]
+  type Body<'a> = core::slice::Iter<'a, Stmt>;
+
+  #[cfg(feature = "gat")]
+  fn body(&self) -> Self::Body<'_> {
+    self.body.iter()
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct ThrowStmt {
+  pub loc: (),
+  pub value: Box<Expr>,
+}
+
+impl traits::ThrowStmt<OwnedSyntax> for ThrowStmt {
+  fn value(&self) -> &Expr {
+    &self.value
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct TryStmt {
+  pub loc: (),
+  pub block: BlockStmt,
+  pub handlers: Vec<CatchClause>,
+  pub finalizer: Option<BlockStmt>,
+}
+
+impl traits::TryStmt<OwnedSyntax> for TryStmt {
+  fn block(&self) -> &BlockStmt {
+    &self.block
+  }
+
+  #[cfg(not(feature = "gat"))]
+  fn handlers<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a CatchClause> + 'a> {
+    Box::new(self.handlers.iter())
+  }
+
+  #[cfg(feature = "gat")]
+  type Handlers<'a> = core::slice::Iter<'a, CatchClause>;
+
+  #[cfg(feature = "gat")]
+  fn handlers(&self) -> Self::Handlers<'_> {
+    self.handlers.iter()
+  }
+
+  fn finalizer(&self) -> Option<&BlockStmt> {
+    self.finalizer.as_ref()
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct CatchClause {
+  pub loc: (),
+  pub param: IdentPat,
+  pub param_type: Option<TypeRef>,
+  pub body: BlockStmt,
+}
+
+impl traits::CatchClause<OwnedSyntax> for CatchClause {
+  fn param(&self) -> &IdentPat {
+    &self.param
+  }
+
+  fn param_type(&self) -> Option<&TypeRef> {
+    self.param_type.as_ref()
+  }
+
+  fn body(&self) -> &BlockStmt {
+    &self.body
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct TraceStmt {
+  pub loc: (),
+  pub value: Box<Expr>,
+}
+
+impl traits::TraceStmt<OwnedSyntax> for TraceStmt {
+  fn value(&self) -> &Expr {
+    &self.value
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct WhileStmt {
+  pub loc: (),
+  pub test: Box<Expr>,
+  pub body: Box<Stmt>,
+}
+
+impl traits::WhileStmt<OwnedSyntax> for WhileStmt {
+  fn test(&self) -> &Expr {
+    &self.test
+  }
+
+  fn body(&self) -> &Stmt {
+    &self.body
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct DoWhileStmt {
+  pub loc: (),
+  pub body: Box<Stmt>,
+  pub test: Box<Expr>,
+}
 
-  type Pat = Pat;
-  type MemberPat = MemberPat;
-  type IdentPat = IdentPat;
+impl traits::DoWhileStmt<OwnedSyntax> for DoWhileStmt {
+  fn body(&self) -> &Stmt {
+    &self.body
+  }
+
+  fn test(&self) -> &Expr {
+    &self.test
+  }
 }
 
 #[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
-pub struct Script {
+pub struct ForStmt {
   pub loc: (),
-  pub stmts: Vec<Stmt>,
+  pub init: Option<ForInit>,
+  pub test: Option<Box<Expr>>,
+  pub update: Option<Box<Expr>>,
+  pub body: Box<Stmt>,
 }
 
-impl traits::Script<OwnedSyntax> for Script {
-  #[cfg(not(feature = "gat"))]
-  fn stmts<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a Stmt> + 'a> {
-    Box::new(self.stmts.iter())
+impl traits::ForStmt<OwnedSyntax> for ForStmt {
+  fn init(&self) -> Option<&ForInit> {
+    self.init.as_ref()
   }
 
-  #[cfg(feature = "gat")]
-  type Stmts<'a> = core::slice::Iter<'a, Stmt>;
+  fn test(&self) -> Option<&Expr> {
+    self.test.as_deref()
+  }
 
-  #[cfg(feature = "gat")]
-  fn stmts(&self) -> Self::Stmts<'_> {
-    self.stmts.iter()
+  fn update(&self) -> Option<&Expr> {
+    self.update.as_deref()
+  }
+
+  fn body(&self) -> &Stmt {
+    &self.body
   }
 }
 
 #[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
-pub enum Stmt {
-  Expr(ExprStmt),
-  /// Abstract Trace Statement
-  ///
-  /// ```aas2
-  /// @trace("Hello, World!");
-  /// ```
-  Trace(TraceStmt),
-  SyntaxError,
+pub enum ForInit {
+  VarDecl(VarDecl),
+  Expr(Box<Expr>),
 }
 
-impl traits::Stmt<OwnedSyntax> for Stmt {
-  fn cast(&self) -> traits::StmtCast<OwnedSyntax> {
+impl traits::ForInit<OwnedSyntax> for ForInit {
+  fn cast(&self) -> traits::ForInitCast<OwnedSyntax> {
     match self {
-      Stmt::Expr(ref e) => traits::StmtCast::Expr(e),
-      Stmt::Trace(ref e) => traits::StmtCast::Trace(e),
-      Stmt::SyntaxError => traits::StmtCast::SyntaxError,
+      ForInit::VarDecl(ref e) => traits::ForInitCast::VarDecl(e),
+      ForInit::Expr(ref e) => traits::ForInitCast::Expr(&**e),
     }
   }
 }
 
 #[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
-pub struct ExprStmt {
+pub struct ForInStmt {
   pub loc: (),
-  pub expr: Box<Expr>,
+  pub target: ForInTarget,
+  pub object: Box<Expr>,
+  pub body: Box<Stmt>,
 }
 
-impl traits::ExprStmt<OwnedSyntax> for ExprStmt {
-  fn expr(&self) -> &Expr {
-    &self.expr
+impl traits::ForInStmt<OwnedSyntax> for ForInStmt {
+  fn target(&self) -> &ForInTarget {
+    &self.target
+  }
+
+  fn object(&self) -> &Expr {
+    &self.object
+  }
+
+  fn body(&self) -> &Stmt {
+    &self.body
   }
 }
 
 #[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
-pub struct TraceStmt {
+pub enum ForInTarget {
+  VarDecl(VarDecl),
+  Pat(Box<Pat>),
+}
+
+impl traits::ForInTarget<OwnedSyntax> for ForInTarget {
+  fn cast(&self) -> traits::ForInTargetCast<OwnedSyntax> {
+    match self {
+      ForInTarget::VarDecl(ref e) => traits::ForInTargetCast::VarDecl(e),
+      ForInTarget::Pat(ref e) => traits::ForInTargetCast::Pat(&**e),
+    }
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct VarDecl {
   pub loc: (),
-  pub value: Box<Expr>,
+  pub declarators: Vec<VarDeclarator>,
 }
 
-impl traits::TraceStmt<OwnedSyntax> for TraceStmt {
-  fn value(&self) -> &Expr {
-    &self.value
+impl traits::VarDecl<OwnedSyntax> for VarDecl {
+  #[cfg(not(feature = "gat"))]
+  fn declarators<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a VarDeclarator> + 'a> {
+    Box::new(self.declarators.iter())
+  }
+
+  #[cfg(feature = "gat")]
+  type Declarators<'a> = core::slice::Iter<'a, VarDeclarator>;
+
+  #[cfg(feature = "gat")]
+  fn declarators(&self) -> Self::Declarators<'_> {
+    self.declarators.iter()
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct VarDeclarator {
+  pub loc: (),
+  pub name: String,
+  pub type_annotation: Option<TypeRef>,
+  pub init: Option<Box<Expr>>,
+}
+
+impl traits::VarDeclarator<OwnedSyntax> for VarDeclarator {
+  fn name(&self) -> &str {
+    &self.name
+  }
+
+  fn type_annotation(&self) -> Option<&TypeRef> {
+    self.type_annotation.as_ref()
+  }
+
+  fn init(&self) -> Option<&Expr> {
+    self.init.as_deref()
   }
 }
 
 #[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
 pub enum Expr {
+  Assign(AssignExpr),
+  Bin(BinExpr),
+  BoolLit(BoolLit),
+  Call(CallExpr),
+  Cond(CondExpr),
+  Ident(IdentExpr),
+  Member(MemberExpr),
+  NewlineLit(NewlineLit),
+  NullLit(NullLit),
+  NumLit(NumLit),
+  Pop(PopExpr),
+  Register(Register),
   Seq(SeqExpr),
-  // Assign(AssignExpr),
   StrLit(StrLit),
+  Super(SuperExpr),
+  TargetPath(TargetPath),
+  This(ThisExpr),
+  UndefinedLit(UndefinedLit),
+  Unary(UnaryExpr),
+  Update(UpdateExpr),
   SyntaxError,
 }
 
 impl traits::Expr<OwnedSyntax> for Expr {
   fn cast(&self) -> traits::ExprCast<OwnedSyntax> {
     match self {
+      Expr::Assign(ref e) => traits::ExprCast::Assign(e),
+      Expr::Bin(ref e) => traits::ExprCast::Bin(e),
+      Expr::BoolLit(ref e) => traits::ExprCast::BoolLit(e),
+      Expr::Call(ref e) => traits::ExprCast::Call(e),
+      Expr::Cond(ref e) => traits::ExprCast::Cond(e),
+      Expr::Ident(ref e) => traits::ExprCast::Ident(e),
+      Expr::Member(ref e) => traits::ExprCast::Member(e),
+      Expr::NewlineLit(ref e) => traits::ExprCast::NewlineLit(e),
+      Expr::NullLit(ref e) => traits::ExprCast::NullLit(e),
+      Expr::NumLit(ref e) => traits::ExprCast::NumLit(e),
+      Expr::Pop(ref e) => traits::ExprCast::Pop(e),
+      Expr::Register(ref e) => traits::ExprCast::Register(e),
       Expr::Seq(ref e) => traits::ExprCast::Seq(e),
       Expr::StrLit(ref e) => traits::ExprCast::StrLit(e),
+      Expr::Super(ref e) => traits::ExprCast::Super(e),
+      Expr::TargetPath(ref e) => traits::ExprCast::TargetPath(e),
+      Expr::This(ref e) => traits::ExprCast::This(e),
+      Expr::UndefinedLit(ref e) => traits::ExprCast::UndefinedLit(e),
+      Expr::Unary(ref e) => traits::ExprCast::Unary(e),
+      Expr::Update(ref e) => traits::ExprCast::Update(e),
       Expr::SyntaxError => traits::ExprCast::Error,
     }
   }
@@ -131,11 +1084,16 @@ impl traits::SeqExpr<OwnedSyntax> for SeqExpr {
 #[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
 pub struct AssignExpr {
   pub loc: (),
+  pub op: traits::AssignOp,
   pub target: Box<Pat>,
   pub value: Box<Expr>,
 }
 
 impl traits::AssignExpr<OwnedSyntax> for AssignExpr {
+  fn op(&self) -> traits::AssignOp {
+    self.op
+  }
+
   fn target(&self) -> &Pat {
     &self.target
   }
@@ -145,14 +1103,41 @@ impl traits::AssignExpr<OwnedSyntax> for AssignExpr {
   }
 }
 
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct CondExpr {
+  pub loc: (),
+  pub test: Box<Expr>,
+  pub consequent: Box<Expr>,
+  pub alternate: Box<Expr>,
+}
+
+impl traits::CondExpr<OwnedSyntax> for CondExpr {
+  fn test(&self) -> &Expr {
+    &self.test
+  }
+
+  fn consequent(&self) -> &Expr {
+    &self.consequent
+  }
+
+  fn alternate(&self) -> &Expr {
+    &self.alternate
+  }
+}
+
 #[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
 pub struct BinExpr {
   pub loc: (),
+  pub op: traits::BinOp,
   pub left: Box<Expr>,
   pub right: Box<Expr>,
 }
 
 impl traits::BinExpr<OwnedSyntax> for BinExpr {
+  fn op(&self) -> traits::BinOp {
+    self.op
+  }
+
   fn left(&self) -> &Expr {
     &self.left
   }
@@ -162,6 +1147,149 @@ impl traits::BinExpr<OwnedSyntax> for BinExpr {
   }
 }
 
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct UnaryExpr {
+  pub loc: (),
+  pub op: traits::UnaryOp,
+  pub arg: Box<Expr>,
+}
+
+impl traits::UnaryExpr<OwnedSyntax> for UnaryExpr {
+  fn op(&self) -> traits::UnaryOp {
+    self.op
+  }
+
+  fn arg(&self) -> &Expr {
+    &self.arg
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct UpdateExpr {
+  pub loc: (),
+  pub op: traits::UpdateOp,
+  pub prefix: bool,
+  pub arg: Box<Expr>,
+}
+
+impl traits::UpdateExpr<OwnedSyntax> for UpdateExpr {
+  fn op(&self) -> traits::UpdateOp {
+    self.op
+  }
+
+  fn prefix(&self) -> bool {
+    self.prefix
+  }
+
+  fn arg(&self) -> &Expr {
+    &self.arg
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct CallExpr {
+  pub loc: (),
+  pub callee: Box<Expr>,
+  pub args: Vec<Expr>,
+}
+
+impl traits::CallExpr<OwnedSyntax> for CallExpr {
+  fn callee(&self) -> &Expr {
+    &self.callee
+  }
+
+  #[cfg(not(feature = "gat"))]
+  fn args<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a Expr> + 'a> {
+    Box::new(self.args.iter())
+  }
+
+  #[cfg(feature = "gat")]
+  type Args<'a> = core::slice::Iter<'a, Expr>;
+
+  #[cfg(feature = "gat")]
+  fn args(&self) -> Self::Args<'_> {
+    self.args.iter()
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct MemberExpr {
+  pub loc: (),
+  pub base: Box<Expr>,
+  pub key: Box<Expr>,
+}
+
+impl traits::MemberExpr<OwnedSyntax> for MemberExpr {
+  fn base(&self) -> &Expr {
+    &self.base
+  }
+
+  fn key(&self) -> &Expr {
+    &self.key
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct IdentExpr {
+  pub loc: (),
+  pub name: String,
+}
+
+impl traits::IdentExpr for IdentExpr {
+  fn name(&self) -> &str {
+    &self.name
+  }
+}
+
+/// Number literal
+///
+/// Values are compared by their bit representation, so the literal
+/// can be used as a key (e.g. `NaN` is equal to itself).
+#[derive(Debug, Clone)]
+pub struct NumLit {
+  pub loc: (),
+  pub value: f64,
+}
+
+impl traits::NumLit for NumLit {
+  fn value(&self) -> f64 {
+    self.value
+  }
+}
+
+impl PartialEq for NumLit {
+  fn eq(&self, other: &Self) -> bool {
+    self.value.to_bits() == other.value.to_bits()
+  }
+}
+
+impl Eq for NumLit {}
+
+impl PartialOrd for NumLit {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl Ord for NumLit {
+  fn cmp(&self, other: &Self) -> Ordering {
+    self.value.to_bits().cmp(&other.value.to_bits())
+  }
+}
+
+impl Hash for NumLit {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.value.to_bits().hash(state);
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct PopExpr {
+  pub loc: (),
+}
+
+impl traits::PopExpr for PopExpr {}
+
 #[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
 pub struct StrLit {
   pub loc: (),
@@ -174,10 +1302,59 @@ impl traits::StrLit for StrLit {
   }
 }
 
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct BoolLit {
+  pub loc: (),
+  pub value: bool,
+}
+
+impl traits::BoolLit for BoolLit {
+  fn value(&self) -> bool {
+    self.value
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct NullLit {
+  pub loc: (),
+}
+
+impl traits::NullLit for NullLit {}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct UndefinedLit {
+  pub loc: (),
+}
+
+impl traits::UndefinedLit for UndefinedLit {}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct NewlineLit {
+  pub loc: (),
+}
+
+impl traits::NewlineLit for NewlineLit {}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct ThisExpr {
+  pub loc: (),
+}
+
+impl traits::ThisExpr for ThisExpr {}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct SuperExpr {
+  pub loc: (),
+}
+
+impl traits::SuperExpr for SuperExpr {}
+
 #[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
 pub enum Pat {
   MemberPat(MemberPat),
   IdentPat(IdentPat),
+  Register(Register),
+  TargetPath(TargetPath),
   SyntaxError,
 }
 
@@ -186,6 +1363,8 @@ impl traits::Pat<OwnedSyntax> for Pat {
     match self {
       Pat::MemberPat(ref e) => traits::PatCast::Member(e),
       Pat::IdentPat(ref e) => traits::PatCast::Ident(e),
+      Pat::Register(ref e) => traits::PatCast::Register(e),
+      Pat::TargetPath(ref e) => traits::PatCast::TargetPath(e),
       Pat::SyntaxError => traits::PatCast::SyntaxError,
     }
   }
@@ -220,6 +1399,75 @@ impl traits::IdentPat for IdentPat {
   }
 }
 
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct TargetPath {
+  pub loc: (),
+  pub is_absolute: bool,
+  pub segments: Vec<String>,
+  pub name: String,
+}
+
+impl traits::TargetPath for TargetPath {
+  fn is_absolute(&self) -> bool {
+    self.is_absolute
+  }
+
+  #[cfg(not(feature = "gat"))]
+  fn segments<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a str> + 'a> {
+    Box::new(self.segments.iter().map(String::as_str))
+  }
+
+  #[cfg(feature = "gat")]
+  type Segments<'a> = core::iter::Map<core::slice::Iter<'a, String>, fn(&String) -> &str>;
+
+  #[cfg(feature = "gat")]
+  fn segments(&self) -> Self::Segments<'_> {
+    self.segments.iter().map(String::as_str)
+  }
+
+  fn name(&self) -> &str {
+    &self.name
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct Register {
+  pub loc: (),
+  pub index: u8,
+}
+
+impl traits::Register for Register {
+  fn index(&self) -> u8 {
+    self.index
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
+pub struct TypeRef {
+  pub loc: (),
+  pub package: Vec<String>,
+  pub name: String,
+}
+
+impl traits::TypeRef for TypeRef {
+  #[cfg(not(feature = "gat"))]
+  fn package<'a>(&'a self) -> Box<dyn ExactSizeIterator<Item = &'a str> + 'a> {
+    Box::new(self.package.iter().map(String::as_str))
+  }
+
+  #[cfg(feature = "gat")]
+  type Package<'a> = core::iter::Map<core::slice::Iter<'a, String>, fn(&String) -> &str>;
+
+  #[cfg(feature = "gat")]
+  fn package(&self) -> Self::Package<'_> {
+    self.package.iter().map(String::as_str)
+  }
+
+  fn name(&self) -> &str {
+    &self.name
+  }
+}
+
 #[cfg(test)]
 mod seq_expr_tests {
   use super::{Expr, SeqExpr, StrLit};
@@ -1,4 +1,6 @@
-use crate::types::ast::traits;
+use crate::lower;
+use crate::parser::register_index;
+use crate::types::ast::traits::{self, AssignOp, BinOp, MethodKind, Modifiers, UnaryOp, UpdateOp};
 use std::borrow::Cow;
 use std::convert::TryFrom;
 use std::ops::Range;
@@ -28,6 +30,137 @@ pub enum SyntaxKind {
   TokenUnilineComment,
 
   // Keywords
+  /// The contextual keyword `add`, the Flash 4 string concatenation operator
+  ///
+  /// It is only recognized as a binary operator, after an operand: the lexer
+  /// emits it as an identifier.
+  TokenAdd,
+
+  /// The keyword `break`
+  TokenBreak,
+
+  /// The keyword `case`
+  TokenCase,
+
+  /// The keyword `catch`
+  TokenCatch,
+
+  /// The keyword `class`
+  TokenClass,
+
+  /// The keyword `continue`
+  TokenContinue,
+
+  /// The keyword `default`
+  TokenDefault,
+
+  /// The keyword `delete`
+  TokenDelete,
+
+  /// The keyword `do`
+  TokenDo,
+
+  /// The keyword `dynamic`
+  TokenDynamic,
+
+  /// The keyword `else`
+  TokenElse,
+
+  /// The keyword `extends`
+  TokenExtends,
+
+  /// The keyword `false`
+  TokenFalse,
+
+  /// The keyword `finally`
+  TokenFinally,
+
+  /// The keyword `for`
+  TokenFor,
+
+  /// The keyword `function`
+  TokenFunction,
+
+  /// The contextual keyword `get`
+  ///
+  /// It is only recognized between `function` and the name of a method: the lexer
+  /// emits it as an identifier.
+  TokenGet,
+
+  /// The keyword `if`
+  TokenIf,
+
+  /// The keyword `ifFrameLoaded`
+  TokenIfFrameLoaded,
+
+  /// The keyword `implements`
+  TokenImplements,
+
+  /// The keyword `import`
+  TokenImport,
+
+  /// The directive `#include`
+  TokenInclude,
+
+  /// The keyword `in`
+  TokenIn,
+
+  /// The keyword `instanceof`
+  TokenInstanceOf,
+
+  /// The keyword `interface`
+  TokenInterface,
+
+  /// The keyword `intrinsic`
+  TokenIntrinsic,
+
+  /// The contextual keyword `newline`
+  ///
+  /// It is only recognized as an expression: the lexer emits it as an identifier.
+  TokenNewline,
+
+  /// The keyword `null`
+  TokenNull,
+
+  /// The contextual keyword `on`
+  ///
+  /// It is only recognized at the top level of instance scripts: the lexer emits it
+  /// as an identifier.
+  TokenOn,
+
+  /// The contextual keyword `onClipEvent`
+  ///
+  /// It is only recognized at the top level of instance scripts: the lexer emits it
+  /// as an identifier.
+  TokenOnClipEvent,
+
+  /// The keyword `private`
+  TokenPrivate,
+
+  /// The keyword `public`
+  TokenPublic,
+
+  /// The keyword `return`
+  TokenReturn,
+
+  /// The contextual keyword `set`
+  ///
+  /// It is only recognized between `function` and the name of a method: the lexer
+  /// emits it as an identifier.
+  TokenSet,
+
+  /// The keyword `static`
+  TokenStatic,
+
+  /// The keyword `super`
+  TokenSuper,
+
+  /// The keyword `switch`
+  TokenSwitch,
+
+  /// The keyword `tellTarget`
+  TokenTellTarget,
+
   /// The keyword `throw`
   TokenThrow,
 
@@ -40,6 +173,26 @@ pub enum SyntaxKind {
   /// The keyword `try`
   TokenTry,
 
+  /// The keyword `typeof`
+  TokenTypeOf,
+
+  /// The contextual keyword `undefined`
+  ///
+  /// It is only recognized as an expression: the lexer emits it as an identifier.
+  TokenUndefined,
+
+  /// The keyword `var`
+  TokenVar,
+
+  /// The keyword `void`
+  TokenVoid,
+
+  /// The keyword `while`
+  TokenWhile,
+
+  /// The keyword `with`
+  TokenWith,
+
   // Atoms
   /// Identifier name
   ///
@@ -52,6 +205,15 @@ pub enum SyntaxKind {
   /// - `_foo_$123`
   TokenIdent,
 
+  /// Number literal
+  ///
+  /// Examples:
+  /// - `0`
+  /// - `1.5e3`
+  /// - `.5`
+  /// - `0xff`
+  TokenNumLit,
+
   /// String literal
   ///
   /// Examples:
@@ -62,38 +224,413 @@ pub enum SyntaxKind {
   /// - `"\""`
   TokenStrLit,
 
+  /// Flash 4 target path with a variable
+  ///
+  /// Examples:
+  /// - `/clip/sub:score`
+  /// - `../:counter`
+  /// - `_root/menu:visible`
+  ///
+  /// The lexer only emits it in Flash 4 compatibility mode.
+  TokenTargetPath,
+
+  /// Decompiler pseudo-instruction `§§push`
+  ///
+  /// The lexer only emits it in decompiler mode.
+  TokenPush,
+
+  /// Decompiler pseudo-instruction `§§pop`
+  ///
+  /// The lexer only emits it in decompiler mode.
+  TokenPop,
+
+  /// Decompiler pseudo-instruction `§§goto`
+  ///
+  /// The lexer only emits it in decompiler mode.
+  TokenGoto,
+
   // Punctuators
   /// `;`
   TokenSemicolon,
 
+  /// Empty marker for a semicolon inserted automatically
+  ///
+  /// It is placed right after the last token of the statement, before any trivia.
+  /// The lexer never emits it.
+  TokenInsertedSemicolon,
+
+  /// `,`
+  TokenComma,
+
+  /// `.`
+  TokenDot,
+
+  /// `:`
+  TokenColon,
+
+  /// `?`
+  TokenQuestion,
+
   /// `(`
   TokenOpenParen,
 
   /// `)`
   TokenCloseParen,
 
+  /// `[`
+  TokenOpenBracket,
+
+  /// `]`
+  TokenCloseBracket,
+
+  /// `{`
+  TokenOpenBrace,
+
+  /// `}`
+  TokenCloseBrace,
+
   /// `!`
   TokenExcl,
 
+  /// `!=`
+  TokenExclEq,
+
+  /// `!==`
+  TokenExclEqEq,
+
+  /// `~`
+  TokenTilde,
+
+  /// `=`
+  TokenEq,
+
+  /// `==`
+  TokenEqEq,
+
+  /// `===`
+  TokenEqEqEq,
+
+  /// `<`
+  TokenLt,
+
+  /// `<=`
+  TokenLtEq,
+
+  /// `<<`
+  TokenLtLt,
+
+  /// `<<=`
+  TokenLtLtEq,
+
+  /// `>`
+  TokenGt,
+
+  /// `>=`
+  TokenGtEq,
+
+  /// `>>`
+  TokenGtGt,
+
+  /// `>>=`
+  TokenGtGtEq,
+
+  /// `>>>`
+  TokenGtGtGt,
+
+  /// `>>>=`
+  TokenGtGtGtEq,
+
+  /// `+`
+  TokenPlus,
+
+  /// `+=`
+  TokenPlusEq,
+
+  /// `++`
+  TokenPlusPlus,
+
+  /// `-`
+  TokenMinus,
+
+  /// `-=`
+  TokenMinusEq,
+
+  /// `--`
+  TokenMinusMinus,
+
+  /// `*`
+  TokenStar,
+
+  /// `*=`
+  TokenStarEq,
+
+  /// `/`
+  TokenSlash,
+
+  /// `/=`
+  TokenSlashEq,
+
+  /// `%`
+  TokenPercent,
+
+  /// `%=`
+  TokenPercentEq,
+
+  /// `&`
+  TokenAmp,
+
+  /// `&&`
+  TokenAmpAmp,
+
+  /// `&=`
+  TokenAmpEq,
+
+  /// `|`
+  TokenPipe,
+
+  /// `||`
+  TokenPipePipe,
+
+  /// `|=`
+  TokenPipeEq,
+
+  /// `^`
+  TokenCaret,
+
+  /// `^=`
+  TokenCaretEq,
+
   // Simple nodes
   /// String literal expression
   NodeStrLit,
 
+  /// Number literal expression
+  NodeNumLit,
+
+  /// Boolean literal expression: `true` or `false`
+  NodeBoolLit,
+
+  /// `null` literal expression
+  NodeNullLit,
+
+  /// `undefined` literal expression
+  NodeUndefinedLit,
+
+  /// `newline` literal expression, the Flash constant for `"\n"`
+  NodeNewlineLit,
+
+  /// `this` expression
+  NodeThisExpr,
+
+  /// `super` expression, as a callee or member base
+  NodeSuperExpr,
+
+  /// Parenthesized expression: `(expr)`
+  NodeParenExpr,
+
   /// Identifier reference expression, or identifier pattern, or label identifier
   NodeIdent,
 
   /// Call expression
   NodeCall,
 
+  /// Empty statement: `;`
+  NodeEmptyStmt,
+
   // Composite nodes
-  /// Any statement
+  /// Expression statement
   NodeStatement,
 
+  /// Block statement: `{ ... }`
+  NodeBlock,
+
+  /// `if` statement, with an optional `else` branch
+  NodeIf,
+
+  /// `while` loop
+  NodeWhile,
+
+  /// `do ... while` loop
+  NodeDoWhile,
+
+  /// `for (init; test; update)` loop
+  NodeFor,
+
+  /// `for (target in object)` loop
+  NodeForIn,
+
+  /// `switch` statement
+  NodeSwitch,
+
+  /// `case` or `default` clause of a `switch` statement, with its body
+  NodeCase,
+
+  /// `try` statement with its `catch` and `finally` clauses
+  NodeTry,
+
+  /// `catch (e)` or `catch (e:Type)` clause of a `try` statement
+  NodeCatch,
+
+  /// `finally` clause of a `try` statement
+  NodeFinally,
+
+  /// `throw` statement
+  NodeThrow,
+
+  /// `return` statement, with an optional value
+  NodeReturn,
+
+  /// `break` statement, with an optional label
+  NodeBreak,
+
+  /// `continue` statement, with an optional label
+  NodeContinue,
+
+  /// Labelled statement: `label: stmt`
+  NodeLabelled,
+
+  /// Decompiler stack push: `§§push(value);`
+  NodePush,
+
+  /// Decompiler jump: `§§goto(label);`
+  NodeGoto,
+
+  /// `with (object) body` statement
+  NodeWith,
+
+  /// `tellTarget (target) body` statement
+  ///
+  /// The target may be preceded by a scene: `tellTarget (scene, target) body`.
+  NodeTellTarget,
+
+  /// `ifFrameLoaded (frame) body` statement
+  ///
+  /// The frame may be preceded by a scene: `ifFrameLoaded (scene, frame) body`.
+  NodeIfFrameLoaded,
+
+  /// Button event handler: `on (press, release) { ... }`
+  NodeOnHandler,
+
+  /// Movie clip event handler: `onClipEvent (enterFrame) { ... }`
+  NodeClipEventHandler,
+
+  /// Event of an `on` or `onClipEvent` handler: `press` or `keyPress "<Enter>"`
+  NodeEvent,
+
+  /// Class declaration: `class a.b.C extends D implements E { ... }`
+  NodeClass,
+
+  /// Interface declaration: `interface a.b.IFoo extends IBar { ... }`
+  NodeInterface,
+
+  /// `extends` clause of a class declaration
+  NodeExtends,
+
+  /// `implements` clause of a class declaration
+  NodeImplements,
+
+  /// Member variable of a class: `static var a:Number = 1;`
+  NodeField,
+
+  /// Method of a class, including accessors and the constructor
+  NodeMethod,
+
+  /// Parenthesized parameter list of a method
+  NodeParams,
+
+  /// Single parameter, with an optional type annotation: `a:Number`
+  NodeParam,
+
+  /// `import` declaration: `import a.b.C;` or `import a.b.*;`
+  NodeImport,
+
+  /// `#include` directive: `#include "lib/util.as"`
+  ///
+  /// After include expansion, the root of the included script is its last child.
+  NodeInclude,
+
+  /// Possibly dotted name of a type or package: `a.b.C`
+  ///
+  /// In `import` declarations, the last segment may be a wildcard: `a.b.*`.
+  NodeQualifiedName,
+
+  /// Metadata tag: `[Event("click")]`
+  ///
+  /// It is the first child of the class, interface or member it decorates.
+  NodeMetadata,
+
+  /// Argument of a metadata tag: `"click"` or `defaultValue="left"`
+  NodeMetadataArg,
+
+  /// Variable declaration: `var a = 1, b`
+  ///
+  /// The trailing semicolon is included when used as a statement.
+  NodeVarDecl,
+
+  /// Single variable declarator inside a `NodeVarDecl`: `a = 1`
+  NodeVarDeclarator,
+
+  /// Type annotation: `:Type`
+  NodeTypeAnnotation,
+
+  /// Binary expression: `left op right`
+  NodeBinExpr,
+
+  /// Prefix unary expression: `op arg`
+  NodeUnaryExpr,
+
+  /// Prefix or postfix increment or decrement: `++a`, `a--`
+  NodeUpdateExpr,
+
+  /// Member access: `base.key` or `base[key]`
+  NodeMember,
+
+  /// Flash 4 target path with a variable: `/clip/sub:score`
+  NodeTargetPath,
+
+  /// Decompiler register local: `_loc3_`
+  NodeRegister,
+
+  /// Decompiler stack pop: `§§pop()`
+  NodePopExpr,
+
+  /// Conditional expression: `test ? consequent : alternate`
+  NodeCondExpr,
+
+  /// Assignment expression: `target op value`
+  ///
+  /// The target is reinterpreted as a pattern: an identifier or member access.
+  NodeAssignExpr,
+
+  /// Sequence expression: `a, b, c`
+  NodeSeqExpr,
+
   /// Any expression
   NodeExpression,
 
-  /// Root node
+  /// Tokens that could not be parsed
+  ///
+  /// Error recovery wraps unexpected tokens in this node.
+  NodeError,
+
+  /// Root node of a frame script
   NodeScript,
+
+  /// Root node of a button or movie clip instance script
+  ///
+  /// Its statements are `on` or `onClipEvent` handlers.
+  NodeInstanceScript,
+
+  /// Root node of a class file
+  ///
+  /// It contains imports and a single class or interface declaration.
+  NodeClassFile,
+
+  /// Root node of a standalone expression
+  NodeExprRoot,
+
+  /// Root node of a standalone statement
+  NodeStmtRoot,
 }
 
 impl From<SyntaxKind> for rowan::SyntaxKind {
@@ -150,6 +687,8 @@ impl SyntaxKind {
       | TokenTrailingComment
       | TokenMultilineComment
       | TokenUnilineComment
+      | TokenElse
+      | TokenIf
       | TokenThrow
       | TokenThis
       | TokenTrue
@@ -158,7 +697,100 @@ impl SyntaxKind {
       | TokenStrLit
       | TokenSemicolon
       | TokenOpenParen
-      | TokenCloseParen => true,
+      | TokenCloseParen
+      | TokenOpenBrace
+      | TokenCloseBrace
+      | TokenExcl
+      | TokenAdd
+      | TokenDelete
+      | TokenDo
+      | TokenFor
+      | TokenIn
+      | TokenInstanceOf
+      | TokenTypeOf
+      | TokenVar
+      | TokenVoid
+      | TokenWhile
+      | TokenNumLit
+      | TokenComma
+      | TokenDot
+      | TokenOpenBracket
+      | TokenCloseBracket
+      | TokenExclEq
+      | TokenExclEqEq
+      | TokenTilde
+      | TokenEq
+      | TokenEqEq
+      | TokenEqEqEq
+      | TokenLt
+      | TokenLtEq
+      | TokenLtLt
+      | TokenLtLtEq
+      | TokenGt
+      | TokenGtEq
+      | TokenGtGt
+      | TokenGtGtEq
+      | TokenGtGtGt
+      | TokenGtGtGtEq
+      | TokenPlus
+      | TokenPlusEq
+      | TokenPlusPlus
+      | TokenMinus
+      | TokenMinusEq
+      | TokenMinusMinus
+      | TokenStar
+      | TokenStarEq
+      | TokenSlash
+      | TokenSlashEq
+      | TokenPercent
+      | TokenPercentEq
+      | TokenAmp
+      | TokenAmpAmp
+      | TokenAmpEq
+      | TokenPipe
+      | TokenPipePipe
+      | TokenPipeEq
+      | TokenCaret
+      | TokenCaretEq
+      | TokenCase
+      | TokenDefault
+      | TokenColon
+      | TokenSwitch
+      | TokenCatch
+      | TokenFinally
+      | TokenBreak
+      | TokenContinue
+      | TokenReturn
+      | TokenIfFrameLoaded
+      | TokenTellTarget
+      | TokenWith
+      | TokenOn
+      | TokenOnClipEvent
+      | TokenClass
+      | TokenDynamic
+      | TokenExtends
+      | TokenFunction
+      | TokenGet
+      | TokenImplements
+      | TokenIntrinsic
+      | TokenPrivate
+      | TokenPublic
+      | TokenSet
+      | TokenStatic
+      | TokenInterface
+      | TokenImport
+      | TokenInsertedSemicolon
+      | TokenQuestion
+      | TokenTargetPath
+      | TokenInclude
+      | TokenPush
+      | TokenPop
+      | TokenGoto
+      | TokenFalse
+      | TokenNewline
+      | TokenNull
+      | TokenSuper
+      | TokenUndefined => true,
       _ => false,
     }
   }
@@ -196,83 +828,1423 @@ pub type SyntaxNode = rowan::SyntaxNode<As2Lang>;
 /// Represents an AS2 syntax symbol: token (terminal) or node (non-terminal).
 pub type SyntaxSymbol = rowan::NodeOrToken<SyntaxNode, SyntaxToken>;
 
-/// Represents an identifier pattern backed by a lossless syntax node.
-#[derive(Debug, Eq, PartialEq, Clone, Hash)]
-pub struct IdentPat {
-  syntax: SyntaxNode,
+/// Declares a typed wrapper around the syntax nodes of the provided kinds.
+///
+/// The wrapper is built with `TryFrom<SyntaxNode>`, failing for other kinds,
+/// and `syntax` returns the underlying node.
+macro_rules! syntax_node {
+  ($(#[$meta:meta])* $name:ident: $($kind:ident)|+) => {
+    $(#[$meta])*
+    #[derive(Debug, Eq, PartialEq, Clone, Hash)]
+    pub struct $name {
+      syntax: SyntaxNode,
+    }
+
+    impl TryFrom<SyntaxNode> for $name {
+      type Error = ();
+
+      fn try_from(syntax: SyntaxNode) -> Result<Self, Self::Error> {
+        match syntax.kind() {
+          $(SyntaxKind::$kind)|+ => Ok($name { syntax }),
+          _ => Err(()),
+        }
+      }
+    }
+
+    impl $name {
+      pub fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+      }
+    }
+  };
 }
 
-impl TryFrom<SyntaxNode> for IdentPat {
-  type Error = ();
+/// Declares an enum over typed wrappers, dispatching on the node kind.
+macro_rules! syntax_enum {
+  ($(#[$meta:meta])* $name:ident { $($variant:ident($wrapper:ident): $($kind:ident)|+,)+ }) => {
+    $(#[$meta])*
+    #[derive(Debug, Eq, PartialEq, Clone, Hash)]
+    pub enum $name {
+      $($variant($wrapper),)+
+    }
 
-  fn try_from(syntax: SyntaxNode) -> Result<Self, Self::Error> {
-    match syntax.kind() {
-      SyntaxKind::NodeIdent => Ok(IdentPat { syntax }),
-      _ => Err(()),
+    impl TryFrom<SyntaxNode> for $name {
+      type Error = ();
+
+      // Enums may have an `Error` variant, shadowing the associated type
+      fn try_from(syntax: SyntaxNode) -> Result<Self, ()> {
+        match syntax.kind() {
+          $($(SyntaxKind::$kind)|+ => $wrapper::try_from(syntax).map($name::$variant),)+
+          _ => Err(()),
+        }
+      }
+    }
+
+    impl $name {
+      pub fn syntax(&self) -> &SyntaxNode {
+        match self {
+          $($name::$variant(node) => node.syntax(),)+
+        }
+      }
     }
+  };
+}
+
+/// Returns the first child node that can be cast to `N`.
+fn child<N: TryFrom<SyntaxNode>>(syntax: &SyntaxNode) -> Option<N> {
+  syntax.children().find_map(|node| N::try_from(node).ok())
+}
+
+/// Returns the child nodes that can be cast to `N`.
+fn children<N: TryFrom<SyntaxNode>>(syntax: &SyntaxNode) -> impl Iterator<Item = N> {
+  syntax.children().filter_map(|node| N::try_from(node).ok())
+}
+
+/// Returns the first child token with one of the provided kinds.
+fn token(syntax: &SyntaxNode, kinds: &[SyntaxKind]) -> Option<SyntaxToken> {
+  syntax
+    .children_with_tokens()
+    .filter_map(|symbol| symbol.into_token())
+    .find(|token| kinds.contains(&token.kind()))
+}
+
+/// Returns the child nodes between the `index`-th and next separator tokens.
+///
+/// For `while (test) body` split by `(` and `)`, the part `1` is the test
+/// and the part `2` is the body. Nodes before a missing separator stay in the
+/// previous part, so accessors return `None` rather than an unrelated node.
+fn part_nodes(
+  syntax: &SyntaxNode,
+  separators: &'static [SyntaxKind],
+  index: usize,
+) -> impl Iterator<Item = SyntaxNode> {
+  syntax
+    .children_with_tokens()
+    .scan(0, move |part, symbol| {
+      match symbol {
+        SyntaxSymbol::Token(ref token) if separators.contains(&token.kind()) => *part += 1,
+        _ => {}
+      }
+      Some((*part, symbol))
+    })
+    .filter_map(move |(part, symbol)| match symbol {
+      SyntaxSymbol::Node(node) if part == index => Some(node),
+      _ => None,
+    })
+}
+
+/// Returns the first node of a part if it can be cast to `N`.
+fn part<N: TryFrom<SyntaxNode>>(syntax: &SyntaxNode, separators: &'static [SyntaxKind], index: usize) -> Option<N> {
+  part_nodes(syntax, separators, index)
+    .next()
+    .and_then(|node| N::try_from(node).ok())
+}
+
+/// Returns the text of a token, or an empty string if it is missing.
+fn token_text(token: &Option<SyntaxToken>) -> &str {
+  token.as_ref().map_or("", |token| token.text().as_str())
+}
+
+const PARENS: &[SyntaxKind] = &[SyntaxKind::TokenOpenParen, SyntaxKind::TokenCloseParen];
+const PARENS_COMMA: &[SyntaxKind] = &[
+  SyntaxKind::TokenOpenParen,
+  SyntaxKind::TokenComma,
+  SyntaxKind::TokenCloseParen,
+];
+
+syntax_enum! {
+  /// Any statement, including the declarations allowed in statement position
+  Stmt {
+    Block(BlockStmt): NodeBlock,
+    Break(BreakStmt): NodeBreak,
+    Class(ClassDecl): NodeClass,
+    ClipEventHandler(ClipEventHandler): NodeClipEventHandler,
+    Continue(ContinueStmt): NodeContinue,
+    DoWhile(DoWhileStmt): NodeDoWhile,
+    Empty(EmptyStmt): NodeEmptyStmt,
+    Error(ErrorNode): NodeError,
+    Expr(ExprStmt): NodeStatement,
+    For(ForStmt): NodeFor,
+    ForIn(ForInStmt): NodeForIn,
+    Goto(GotoStmt): NodeGoto,
+    If(IfStmt): NodeIf,
+    IfFrameLoaded(IfFrameLoadedStmt): NodeIfFrameLoaded,
+    Import(ImportDecl): NodeImport,
+    Include(IncludeDirective): NodeInclude,
+    Interface(InterfaceDecl): NodeInterface,
+    Labelled(LabelledStmt): NodeLabelled,
+    OnHandler(OnHandler): NodeOnHandler,
+    Push(PushStmt): NodePush,
+    Return(ReturnStmt): NodeReturn,
+    Switch(SwitchStmt): NodeSwitch,
+    TellTarget(TellTargetStmt): NodeTellTarget,
+    Throw(ThrowStmt): NodeThrow,
+    Try(TryStmt): NodeTry,
+    VarDecl(VarDecl): NodeVarDecl,
+    While(WhileStmt): NodeWhile,
+    With(WithStmt): NodeWith,
   }
 }
 
-impl traits::IdentPat for IdentPat {
-  fn name(&self) -> &str {
-    unimplemented!()
-    // self.syntax.first_token().unwrap().text().as_str()
+syntax_enum! {
+  /// Any expression
+  Expr {
+    Assign(AssignExpr): NodeAssignExpr,
+    Bin(BinExpr): NodeBinExpr,
+    BoolLit(BoolLit): NodeBoolLit,
+    Call(CallExpr): NodeCall,
+    Cond(CondExpr): NodeCondExpr,
+    Error(ErrorNode): NodeError,
+    Expression(Expression): NodeExpression,
+    Ident(IdentExpr): NodeIdent,
+    Member(MemberExpr): NodeMember,
+    NewlineLit(NewlineLit): NodeNewlineLit,
+    NullLit(NullLit): NodeNullLit,
+    NumLit(NumLit): NodeNumLit,
+    Paren(ParenExpr): NodeParenExpr,
+    Pop(PopExpr): NodePopExpr,
+    Register(Register): NodeRegister,
+    Seq(SeqExpr): NodeSeqExpr,
+    StrLit(StrLit): NodeStrLit,
+    Super(SuperExpr): NodeSuperExpr,
+    TargetPath(TargetPath): NodeTargetPath,
+    This(ThisExpr): NodeThisExpr,
+    UndefinedLit(UndefinedLit): NodeUndefinedLit,
+    Unary(UnaryExpr): NodeUnaryExpr,
+    Update(UpdateExpr): NodeUpdateExpr,
   }
 }
 
-/// Represents a string literal backed by a lossless syntax node.
+/// Initializer of a `for` loop, or target of a `for in` loop
 #[derive(Debug, Eq, PartialEq, Clone, Hash)]
-pub struct StrLit {
-  syntax: SyntaxNode,
+pub enum ForInit {
+  VarDecl(VarDecl),
+  Expr(Expr),
 }
 
-impl TryFrom<SyntaxNode> for StrLit {
+impl TryFrom<SyntaxNode> for ForInit {
   type Error = ();
 
   fn try_from(syntax: SyntaxNode) -> Result<Self, Self::Error> {
     match syntax.kind() {
-      SyntaxKind::NodeStrLit => Ok(StrLit { syntax }),
-      _ => Err(()),
+      SyntaxKind::NodeVarDecl => VarDecl::try_from(syntax).map(ForInit::VarDecl),
+      _ => Expr::try_from(syntax).map(ForInit::Expr),
     }
   }
 }
 
-impl traits::StrLit for StrLit {
-  fn value(&self) -> Cow<str> {
-    let token = self.syntax.first_token().unwrap();
-    let text = token.text().as_str();
-    Cow::Owned(unescape_string(text).unwrap())
+impl ForInit {
+  pub fn syntax(&self) -> &SyntaxNode {
+    match self {
+      ForInit::VarDecl(node) => node.syntax(),
+      ForInit::Expr(node) => node.syntax(),
+    }
   }
 }
 
-fn unescape_string(quoted: &str) -> Option<String> {
-  let content = find_quoted_content(quoted)?;
-  let str_content = &quoted[content.range];
-  let mut unescaped: String = String::with_capacity(str_content.len());
-  let mut has_error = false;
-  // TODO: Return iterator, so we can stop at the first error
-  unescape_string_content(
-    str_content,
-    content.quotes,
-    &mut |_, unescaped_char| match unescaped_char {
-      Ok(c) => unescaped.push(c),
-      Err(_) => has_error = true,
-    },
-  );
-  if has_error {
-    None
-  } else {
-    Some(unescaped)
+syntax_enum! {
+  /// Member of a class or interface body
+  ClassMember {
+    Error(ErrorNode): NodeError,
+    Field(FieldDecl): NodeField,
+    Method(MethodDecl): NodeMethod,
   }
 }
 
-fn find_quoted_content(quoted: &str) -> Option<QuotedContent> {
-  let chars = quoted.char_indices();
-  let (first_idx, first_char) = chars.clone().next()?;
-  let (last_idx, last_char) = chars.clone().next_back()?;
-  if first_idx == last_idx {
-    // There is only one character in `quoted`
-    return None;
+// Roots
+
+syntax_node! {
+  /// Root of a script, such as a frame script or an included file
+  Script: NodeScript
+}
+
+impl Script {
+  pub fn stmts(&self) -> impl Iterator<Item = Stmt> {
+    children(&self.syntax)
+  }
+}
+
+syntax_node! {
+  /// Root of a button or movie clip instance script
+  InstanceScript: NodeInstanceScript
+}
+
+impl InstanceScript {
+  pub fn stmts(&self) -> impl Iterator<Item = Stmt> {
+    children(&self.syntax)
+  }
+}
+
+syntax_node! {
+  /// Root of a class file
+  ClassFile: NodeClassFile
+}
+
+impl ClassFile {
+  pub fn stmts(&self) -> impl Iterator<Item = Stmt> {
+    children(&self.syntax)
+  }
+}
+
+syntax_node! {
+  /// Root of a standalone expression
+  ExprRoot: NodeExprRoot
+}
+
+impl ExprRoot {
+  pub fn expr(&self) -> Option<Expr> {
+    child(&self.syntax)
+  }
+}
+
+syntax_node! {
+  /// Root of a single statement
+  StmtRoot: NodeStmtRoot
+}
+
+impl StmtRoot {
+  pub fn stmt(&self) -> Option<Stmt> {
+    child(&self.syntax)
+  }
+}
+
+syntax_node! {
+  /// Unparsed tokens, in statement or expression position
+  ErrorNode: NodeError
+}
+
+// Statements
+
+syntax_node! {
+  /// Block statement: `{ stmts }`
+  BlockStmt: NodeBlock
+}
+
+impl BlockStmt {
+  pub fn stmts(&self) -> impl Iterator<Item = Stmt> {
+    children(&self.syntax)
+  }
+}
+
+syntax_node! {
+  /// Lone semicolon
+  EmptyStmt: NodeEmptyStmt
+}
+
+syntax_node! {
+  /// Expression statement: `expr;`
+  ExprStmt: NodeStatement
+}
+
+impl ExprStmt {
+  pub fn expr(&self) -> Option<Expr> {
+    child(&self.syntax)
+  }
+}
+
+syntax_node! {
+  /// `if (test) consequent else alternate`
+  IfStmt: NodeIf
+}
+
+impl IfStmt {
+  const PARTS: &'static [SyntaxKind] = &[
+    SyntaxKind::TokenOpenParen,
+    SyntaxKind::TokenCloseParen,
+    SyntaxKind::TokenElse,
+  ];
+
+  pub fn test(&self) -> Option<Expr> {
+    part(&self.syntax, Self::PARTS, 1)
+  }
+
+  pub fn consequent(&self) -> Option<Stmt> {
+    part(&self.syntax, Self::PARTS, 2)
+  }
+
+  pub fn alternate(&self) -> Option<Stmt> {
+    part(&self.syntax, Self::PARTS, 3)
+  }
+}
+
+syntax_node! {
+  /// `while (test) body`
+  WhileStmt: NodeWhile
+}
+
+impl WhileStmt {
+  pub fn test(&self) -> Option<Expr> {
+    part(&self.syntax, PARENS, 1)
+  }
+
+  pub fn body(&self) -> Option<Stmt> {
+    part(&self.syntax, PARENS, 2)
+  }
+}
+
+syntax_node! {
+  /// `do body while (test);`
+  DoWhileStmt: NodeDoWhile
+}
+
+impl DoWhileStmt {
+  pub fn body(&self) -> Option<Stmt> {
+    part(&self.syntax, PARENS, 0)
+  }
+
+  pub fn test(&self) -> Option<Expr> {
+    part(&self.syntax, PARENS, 1)
+  }
+}
+
+syntax_node! {
+  /// `for (init; test; update) body`
+  ForStmt: NodeFor
+}
+
+impl ForStmt {
+  const PARTS: &'static [SyntaxKind] = &[
+    SyntaxKind::TokenOpenParen,
+    SyntaxKind::TokenSemicolon,
+    SyntaxKind::TokenCloseParen,
+  ];
+
+  pub fn init(&self) -> Option<ForInit> {
+    part(&self.syntax, Self::PARTS, 1)
+  }
+
+  pub fn test(&self) -> Option<Expr> {
+    part(&self.syntax, Self::PARTS, 2)
+  }
+
+  pub fn update(&self) -> Option<Expr> {
+    part(&self.syntax, Self::PARTS, 3)
+  }
+
+  pub fn body(&self) -> Option<Stmt> {
+    part(&self.syntax, Self::PARTS, 4)
+  }
+}
+
+syntax_node! {
+  /// `for (target in object) body`
+  ForInStmt: NodeForIn
+}
+
+impl ForInStmt {
+  const PARTS: &'static [SyntaxKind] = &[
+    SyntaxKind::TokenOpenParen,
+    SyntaxKind::TokenIn,
+    SyntaxKind::TokenCloseParen,
+  ];
+
+  pub fn target(&self) -> Option<ForInit> {
+    part(&self.syntax, Self::PARTS, 1)
+  }
+
+  pub fn object(&self) -> Option<Expr> {
+    part(&self.syntax, Self::PARTS, 2)
+  }
+
+  pub fn body(&self) -> Option<Stmt> {
+    part(&self.syntax, Self::PARTS, 3)
+  }
+}
+
+syntax_node! {
+  /// `switch (discriminant) { cases }`
+  SwitchStmt: NodeSwitch
+}
+
+impl SwitchStmt {
+  pub fn discriminant(&self) -> Option<Expr> {
+    part(&self.syntax, PARENS, 1)
+  }
+
+  pub fn cases(&self) -> impl Iterator<Item = CaseClause> {
+    children(&self.syntax)
+  }
+}
+
+syntax_node! {
+  /// `case test: body` or `default: body`
+  CaseClause: NodeCase
+}
+
+impl CaseClause {
+  const PARTS: &'static [SyntaxKind] = &[SyntaxKind::TokenColon];
+
+  pub fn is_default(&self) -> bool {
+    token(&self.syntax, &[SyntaxKind::TokenDefault]).is_some()
+  }
+
+  /// Returns the tested value, `None` for the `default` clause.
+  pub fn test(&self) -> Option<Expr> {
+    part(&self.syntax, Self::PARTS, 0)
+  }
+
+  pub fn body(&self) -> impl Iterator<Item = Stmt> {
+    part_nodes(&self.syntax, Self::PARTS, 1).filter_map(|node| Stmt::try_from(node).ok())
+  }
+}
+
+syntax_node! {
+  /// `try block catch (param) body finally body`
+  TryStmt: NodeTry
+}
+
+impl TryStmt {
+  pub fn block(&self) -> Option<BlockStmt> {
+    child(&self.syntax)
+  }
+
+  pub fn catch_clauses(&self) -> impl Iterator<Item = CatchClause> {
+    children(&self.syntax)
+  }
+
+  pub fn finally_clause(&self) -> Option<FinallyClause> {
+    child(&self.syntax)
+  }
+}
+
+syntax_node! {
+  /// `catch (param: Type) body`
+  CatchClause: NodeCatch
+}
+
+impl CatchClause {
+  pub fn param(&self) -> Option<IdentPat> {
+    child(&self.syntax)
+  }
+
+  pub fn type_annotation(&self) -> Option<TypeAnnotation> {
+    child(&self.syntax)
+  }
+
+  pub fn body(&self) -> Option<BlockStmt> {
+    child(&self.syntax)
+  }
+}
+
+syntax_node! {
+  /// `finally body`
+  FinallyClause: NodeFinally
+}
+
+impl FinallyClause {
+  pub fn body(&self) -> Option<BlockStmt> {
+    child(&self.syntax)
+  }
+}
+
+syntax_node! {
+  /// `throw value;`
+  ThrowStmt: NodeThrow
+}
+
+impl ThrowStmt {
+  pub fn value(&self) -> Option<Expr> {
+    child(&self.syntax)
+  }
+}
+
+syntax_node! {
+  /// `return value;`
+  ReturnStmt: NodeReturn
+}
+
+impl ReturnStmt {
+  pub fn value(&self) -> Option<Expr> {
+    child(&self.syntax)
+  }
+}
+
+syntax_node! {
+  /// `break label;`
+  BreakStmt: NodeBreak
+}
+
+impl BreakStmt {
+  pub fn label(&self) -> Option<IdentExpr> {
+    child(&self.syntax)
+  }
+}
+
+syntax_node! {
+  /// `continue label;`
+  ContinueStmt: NodeContinue
+}
+
+impl ContinueStmt {
+  pub fn label(&self) -> Option<IdentExpr> {
+    child(&self.syntax)
+  }
+}
+
+syntax_node! {
+  /// `label: body`
+  LabelledStmt: NodeLabelled
+}
+
+impl LabelledStmt {
+  const PARTS: &'static [SyntaxKind] = &[SyntaxKind::TokenColon];
+
+  pub fn label(&self) -> Option<IdentExpr> {
+    part(&self.syntax, Self::PARTS, 0)
+  }
+
+  pub fn body(&self) -> Option<Stmt> {
+    part(&self.syntax, Self::PARTS, 1)
+  }
+}
+
+syntax_node! {
+  /// Decompiler push: `§§push(value);`
+  PushStmt: NodePush
+}
+
+impl PushStmt {
+  pub fn value(&self) -> Option<Expr> {
+    part(&self.syntax, PARENS, 1)
+  }
+}
+
+syntax_node! {
+  /// Decompiler jump: `§§goto(label);`
+  GotoStmt: NodeGoto
+}
+
+impl GotoStmt {
+  pub fn label(&self) -> Option<IdentExpr> {
+    child(&self.syntax)
+  }
+}
+
+syntax_node! {
+  /// `with (object) body`
+  WithStmt: NodeWith
+}
+
+impl WithStmt {
+  pub fn object(&self) -> Option<Expr> {
+    part(&self.syntax, PARENS, 1)
+  }
+
+  pub fn body(&self) -> Option<Stmt> {
+    part(&self.syntax, PARENS, 2)
+  }
+}
+
+/// Returns `1` if `kw (scene, target) body` has a scene, shifting the parts
+/// after it.
+fn comma_offset(syntax: &SyntaxNode) -> usize {
+  // The comma of a sequence expression is nested in `NodeSeqExpr`
+  if token(syntax, &[SyntaxKind::TokenComma]).is_some() {
+    1
+  } else {
+    0
+  }
+}
+
+syntax_node! {
+  /// `tellTarget(target) body`, with an optional scene: `tellTarget(scene, target)`
+  TellTargetStmt: NodeTellTarget
+}
+
+impl TellTargetStmt {
+  pub fn scene(&self) -> Option<Expr> {
+    match comma_offset(&self.syntax) {
+      0 => None,
+      _ => part(&self.syntax, PARENS_COMMA, 1),
+    }
+  }
+
+  pub fn target(&self) -> Option<Expr> {
+    part(&self.syntax, PARENS_COMMA, 1 + comma_offset(&self.syntax))
+  }
+
+  pub fn body(&self) -> Option<Stmt> {
+    part(&self.syntax, PARENS_COMMA, 2 + comma_offset(&self.syntax))
+  }
+}
+
+syntax_node! {
+  /// `ifFrameLoaded(frame) body`, with an optional scene: `ifFrameLoaded(scene, frame)`
+  IfFrameLoadedStmt: NodeIfFrameLoaded
+}
+
+impl IfFrameLoadedStmt {
+  pub fn scene(&self) -> Option<Expr> {
+    match comma_offset(&self.syntax) {
+      0 => None,
+      _ => part(&self.syntax, PARENS_COMMA, 1),
+    }
+  }
+
+  pub fn frame(&self) -> Option<Expr> {
+    part(&self.syntax, PARENS_COMMA, 1 + comma_offset(&self.syntax))
+  }
+
+  pub fn body(&self) -> Option<Stmt> {
+    part(&self.syntax, PARENS_COMMA, 2 + comma_offset(&self.syntax))
+  }
+}
+
+syntax_node! {
+  /// Button event handler: `on (press, release) body`
+  OnHandler: NodeOnHandler
+}
+
+impl OnHandler {
+  pub fn events(&self) -> impl Iterator<Item = Event> {
+    children(&self.syntax)
+  }
+
+  pub fn body(&self) -> Option<BlockStmt> {
+    child(&self.syntax)
+  }
+}
+
+syntax_node! {
+  /// Movie clip event handler: `onClipEvent (load) body`
+  ClipEventHandler: NodeClipEventHandler
+}
+
+impl ClipEventHandler {
+  pub fn events(&self) -> impl Iterator<Item = Event> {
+    children(&self.syntax)
+  }
+
+  pub fn body(&self) -> Option<BlockStmt> {
+    child(&self.syntax)
+  }
+}
+
+syntax_node! {
+  /// Event of a handler, such as `release` or `keyPress "<Enter>"`
+  Event: NodeEvent
+}
+
+impl Event {
+  pub fn name(&self) -> Option<SyntaxToken> {
+    token(&self.syntax, &[SyntaxKind::TokenIdent])
+  }
+
+  /// Returns the quoted key of a `keyPress` event.
+  pub fn key(&self) -> Option<SyntaxToken> {
+    token(&self.syntax, &[SyntaxKind::TokenStrLit])
+  }
+}
+
+// Declarations
+
+syntax_node! {
+  /// `dynamic class Name extends Parent implements Interface { members }`
+  ClassDecl: NodeClass
+}
+
+impl ClassDecl {
+  pub fn metadata(&self) -> impl Iterator<Item = Metadata> {
+    children(&self.syntax)
+  }
+
+  pub fn is_dynamic(&self) -> bool {
+    token(&self.syntax, &[SyntaxKind::TokenDynamic]).is_some()
+  }
+
+  pub fn is_intrinsic(&self) -> bool {
+    token(&self.syntax, &[SyntaxKind::TokenIntrinsic]).is_some()
+  }
+
+  pub fn name(&self) -> Option<QualifiedName> {
+    child(&self.syntax)
+  }
+
+  pub fn extends(&self) -> Option<ExtendsClause> {
+    child(&self.syntax)
+  }
+
+  pub fn implements(&self) -> Option<ImplementsClause> {
+    child(&self.syntax)
+  }
+
+  pub fn members(&self) -> impl Iterator<Item = ClassMember> {
+    children(&self.syntax)
+  }
+}
+
+syntax_node! {
+  /// `interface Name extends Parent { members }`
+  InterfaceDecl: NodeInterface
+}
+
+impl InterfaceDecl {
+  pub fn metadata(&self) -> impl Iterator<Item = Metadata> {
+    children(&self.syntax)
+  }
+
+  pub fn name(&self) -> Option<QualifiedName> {
+    child(&self.syntax)
+  }
+
+  pub fn extends(&self) -> Option<ExtendsClause> {
+    child(&self.syntax)
+  }
+
+  pub fn members(&self) -> impl Iterator<Item = ClassMember> {
+    children(&self.syntax)
+  }
+}
+
+syntax_node! {
+  /// `extends Parent`
+  ExtendsClause: NodeExtends
+}
+
+impl ExtendsClause {
+  pub fn name(&self) -> Option<QualifiedName> {
+    child(&self.syntax)
+  }
+}
+
+syntax_node! {
+  /// `implements First, Second`
+  ImplementsClause: NodeImplements
+}
+
+impl ImplementsClause {
+  pub fn names(&self) -> impl Iterator<Item = QualifiedName> {
+    children(&self.syntax)
+  }
+}
+
+/// Returns the modifiers set by the keywords of a class member.
+fn modifiers(syntax: &SyntaxNode) -> Modifiers {
+  let mut modifiers = Modifiers::default();
+  for token in syntax.children_with_tokens().filter_map(|symbol| symbol.into_token()) {
+    lower::modifier(&mut modifiers, token.kind());
+  }
+  modifiers
+}
+
+syntax_node! {
+  /// Class field: `static private var a: Number, b;`
+  FieldDecl: NodeField
+}
+
+impl FieldDecl {
+  pub fn metadata(&self) -> impl Iterator<Item = Metadata> {
+    children(&self.syntax)
+  }
+
+  pub fn modifiers(&self) -> Modifiers {
+    modifiers(&self.syntax)
+  }
+
+  pub fn declarators(&self) -> impl Iterator<Item = VarDeclarator> {
+    children(&self.syntax)
+  }
+}
+
+syntax_node! {
+  /// Class method: `public function name(params): Type body`
+  MethodDecl: NodeMethod
+}
+
+impl MethodDecl {
+  pub fn metadata(&self) -> impl Iterator<Item = Metadata> {
+    children(&self.syntax)
+  }
+
+  pub fn modifiers(&self) -> Modifiers {
+    modifiers(&self.syntax)
+  }
+
+  /// Returns the kind of the method, a constructor being named after its class.
+  pub fn kind(&self) -> MethodKind {
+    match token(&self.syntax, &[SyntaxKind::TokenGet, SyntaxKind::TokenSet]) {
+      Some(ref token) if token.kind() == SyntaxKind::TokenGet => MethodKind::Get,
+      Some(_) => MethodKind::Set,
+      None => {
+        let class_name = self
+          .syntax
+          .parent()
+          .and_then(|parent| ClassDecl::try_from(parent).ok())
+          .and_then(|class| class.name())
+          .and_then(|name| name.segments().last());
+        let name = self.name();
+        match (class_name, name) {
+          (Some(ref class_name), Some(ref name)) if class_name.text() == traits::IdentPat::name(name) => {
+            MethodKind::Constructor
+          }
+          _ => MethodKind::Method,
+        }
+      }
+    }
+  }
+
+  pub fn name(&self) -> Option<IdentPat> {
+    child(&self.syntax)
+  }
+
+  pub fn params(&self) -> Option<ParamList> {
+    child(&self.syntax)
+  }
+
+  pub fn return_type(&self) -> Option<TypeAnnotation> {
+    child(&self.syntax)
+  }
+
+  /// Returns the body, `None` for interface and intrinsic methods.
+  pub fn body(&self) -> Option<BlockStmt> {
+    child(&self.syntax)
+  }
+}
+
+syntax_node! {
+  /// Parameter list: `(a: Number, b)`
+  ParamList: NodeParams
+}
+
+impl ParamList {
+  pub fn params(&self) -> impl Iterator<Item = Param> {
+    children(&self.syntax)
+  }
+}
+
+syntax_node! {
+  /// Parameter: `name: Type`
+  Param: NodeParam
+}
+
+impl Param {
+  pub fn name(&self) -> Option<IdentPat> {
+    child(&self.syntax)
+  }
+
+  pub fn type_annotation(&self) -> Option<TypeAnnotation> {
+    child(&self.syntax)
+  }
+}
+
+syntax_node! {
+  /// `import path.Name;` or `import path.*;`
+  ImportDecl: NodeImport
+}
+
+impl ImportDecl {
+  pub fn name(&self) -> Option<QualifiedName> {
+    child(&self.syntax)
+  }
+}
+
+syntax_node! {
+  /// `#include "path.as"`
+  IncludeDirective: NodeInclude
+}
+
+impl IncludeDirective {
+  pub fn path(&self) -> Option<StrLit> {
+    child(&self.syntax)
+  }
+
+  /// Returns the included script, once includes are expanded.
+  pub fn script(&self) -> Option<Script> {
+    child(&self.syntax)
+  }
+}
+
+syntax_node! {
+  /// Dotted name: `flash.display.BitmapData`, ending with `*` in imports
+  QualifiedName: NodeQualifiedName
+}
+
+impl QualifiedName {
+  pub fn segments(&self) -> impl Iterator<Item = SyntaxToken> {
+    self
+      .syntax
+      .children_with_tokens()
+      .filter_map(|symbol| symbol.into_token())
+      .filter(|token| token.kind() == SyntaxKind::TokenIdent || token.kind() == SyntaxKind::TokenStar)
+  }
+}
+
+syntax_node! {
+  /// Metadata tag: `[Inspectable(name = "Size", defaultValue = 10)]`
+  Metadata: NodeMetadata
+}
+
+impl Metadata {
+  pub fn name(&self) -> Option<SyntaxToken> {
+    token(&self.syntax, &[SyntaxKind::TokenIdent])
+  }
+
+  pub fn args(&self) -> impl Iterator<Item = MetadataArg> {
+    children(&self.syntax)
+  }
+}
+
+syntax_node! {
+  /// Metadata argument: `key = value` or a lone `value`
+  MetadataArg: NodeMetadataArg
+}
+
+impl MetadataArg {
+  fn tokens(&self) -> impl Iterator<Item = SyntaxToken> {
+    self
+      .syntax
+      .children_with_tokens()
+      .filter_map(|symbol| symbol.into_token())
+      .filter(|token| !token.kind().is_trivia())
+  }
+
+  fn has_key(&self) -> bool {
+    token(&self.syntax, &[SyntaxKind::TokenEq]).is_some()
+  }
+
+  pub fn key(&self) -> Option<SyntaxToken> {
+    if !self.has_key() {
+      return None;
+    }
+    self
+      .tokens()
+      .next()
+      .filter(|token| token.kind() == SyntaxKind::TokenIdent)
+  }
+
+  pub fn value(&self) -> Option<SyntaxToken> {
+    let index = if self.has_key() { 1 } else { 0 };
+    self
+      .tokens()
+      .filter(|token| token.kind() != SyntaxKind::TokenEq)
+      .nth(index)
+  }
+}
+
+syntax_node! {
+  /// `var a: Number = 1, b`
+  VarDecl: NodeVarDecl
+}
+
+impl VarDecl {
+  pub fn declarators(&self) -> impl Iterator<Item = VarDeclarator> {
+    children(&self.syntax)
+  }
+}
+
+syntax_node! {
+  /// Variable of a declaration: `name: Type = init`
+  VarDeclarator: NodeVarDeclarator
+}
+
+impl VarDeclarator {
+  const PARTS: &'static [SyntaxKind] = &[SyntaxKind::TokenEq];
+
+  pub fn name(&self) -> Option<IdentPat> {
+    part(&self.syntax, Self::PARTS, 0)
+  }
+
+  pub fn type_annotation(&self) -> Option<TypeAnnotation> {
+    child(&self.syntax)
+  }
+
+  pub fn init(&self) -> Option<Expr> {
+    part(&self.syntax, Self::PARTS, 1)
+  }
+}
+
+syntax_node! {
+  /// Type annotation: `: Type`
+  TypeAnnotation: NodeTypeAnnotation
+}
+
+impl TypeAnnotation {
+  pub fn name(&self) -> Option<QualifiedName> {
+    child(&self.syntax)
+  }
+}
+
+// Expressions
+
+/// Represents an identifier pattern backed by a lossless syntax node.
+#[derive(Debug, Eq, PartialEq, Clone, Hash)]
+pub struct IdentPat {
+  syntax: SyntaxNode,
+  // The token is kept to return its text by reference
+  ident: Option<SyntaxToken>,
+}
+
+impl TryFrom<SyntaxNode> for IdentPat {
+  type Error = ();
+
+  fn try_from(syntax: SyntaxNode) -> Result<Self, Self::Error> {
+    match syntax.kind() {
+      SyntaxKind::NodeIdent => Ok(IdentPat {
+        ident: token(&syntax, &[SyntaxKind::TokenIdent]),
+        syntax,
+      }),
+      _ => Err(()),
+    }
+  }
+}
+
+impl IdentPat {
+  pub fn syntax(&self) -> &SyntaxNode {
+    &self.syntax
+  }
+}
+
+impl traits::IdentPat for IdentPat {
+  fn name(&self) -> &str {
+    token_text(&self.ident)
+  }
+}
+
+/// Represents an identifier expression backed by a lossless syntax node.
+#[derive(Debug, Eq, PartialEq, Clone, Hash)]
+pub struct IdentExpr {
+  syntax: SyntaxNode,
+  ident: Option<SyntaxToken>,
+}
+
+impl TryFrom<SyntaxNode> for IdentExpr {
+  type Error = ();
+
+  fn try_from(syntax: SyntaxNode) -> Result<Self, Self::Error> {
+    match syntax.kind() {
+      SyntaxKind::NodeIdent => Ok(IdentExpr {
+        ident: token(&syntax, &[SyntaxKind::TokenIdent]),
+        syntax,
+      }),
+      _ => Err(()),
+    }
+  }
+}
+
+impl IdentExpr {
+  pub fn syntax(&self) -> &SyntaxNode {
+    &self.syntax
+  }
+}
+
+impl traits::IdentExpr for IdentExpr {
+  fn name(&self) -> &str {
+    token_text(&self.ident)
+  }
+}
+
+syntax_node! {
+  /// Represents a string literal backed by a lossless syntax node.
+  StrLit: NodeStrLit
+}
+
+impl traits::StrLit for StrLit {
+  fn value(&self) -> Cow<str> {
+    let value = token(&self.syntax, &[SyntaxKind::TokenStrLit]).and_then(|token| unescape_string(token.text()));
+    Cow::Owned(value.unwrap_or_default())
+  }
+}
+
+syntax_node! {
+  /// Number literal: `42`, `0x2A` or `4.2e1`
+  NumLit: NodeNumLit
+}
+
+impl traits::NumLit for NumLit {
+  fn value(&self) -> f64 {
+    token(&self.syntax, &[SyntaxKind::TokenNumLit]).map_or(std::f64::NAN, |token| lower::num_value(token.text()))
+  }
+}
+
+syntax_node! {
+  /// `true` or `false`
+  BoolLit: NodeBoolLit
+}
+
+impl traits::BoolLit for BoolLit {
+  fn value(&self) -> bool {
+    token(&self.syntax, &[SyntaxKind::TokenTrue]).is_some()
+  }
+}
+
+syntax_node! {
+  /// `null`
+  NullLit: NodeNullLit
+}
+
+impl traits::NullLit for NullLit {}
+
+syntax_node! {
+  /// `undefined`
+  UndefinedLit: NodeUndefinedLit
+}
+
+impl traits::UndefinedLit for UndefinedLit {}
+
+syntax_node! {
+  /// `newline`, the legacy constant for `"\n"`
+  NewlineLit: NodeNewlineLit
+}
+
+impl traits::NewlineLit for NewlineLit {}
+
+syntax_node! {
+  /// `this`
+  ThisExpr: NodeThisExpr
+}
+
+impl traits::ThisExpr for ThisExpr {}
+
+syntax_node! {
+  /// `super`
+  SuperExpr: NodeSuperExpr
+}
+
+impl traits::SuperExpr for SuperExpr {}
+
+syntax_node! {
+  /// `(expr)`
+  ParenExpr: NodeParenExpr
+}
+
+impl ParenExpr {
+  pub fn expr(&self) -> Option<Expr> {
+    child(&self.syntax)
+  }
+}
+
+syntax_node! {
+  /// `callee(args)`
+  CallExpr: NodeCall
+}
+
+impl CallExpr {
+  const PARTS: &'static [SyntaxKind] = &[SyntaxKind::TokenOpenParen];
+
+  pub fn callee(&self) -> Option<Expr> {
+    part(&self.syntax, Self::PARTS, 0)
+  }
+
+  pub fn args(&self) -> impl Iterator<Item = Expr> {
+    part_nodes(&self.syntax, Self::PARTS, 1).filter_map(|node| Expr::try_from(node).ok())
+  }
+}
+
+syntax_node! {
+  /// `base.key` or `base[key]`
+  MemberExpr: NodeMember
+}
+
+impl MemberExpr {
+  const PARTS: &'static [SyntaxKind] = &[SyntaxKind::TokenDot, SyntaxKind::TokenOpenBracket];
+
+  pub fn base(&self) -> Option<Expr> {
+    part(&self.syntax, Self::PARTS, 0)
+  }
+
+  pub fn is_computed(&self) -> bool {
+    token(&self.syntax, &[SyntaxKind::TokenOpenBracket]).is_some()
+  }
+
+  /// Returns the key, an `Expr::Ident` for `base.key`.
+  pub fn key(&self) -> Option<Expr> {
+    part(&self.syntax, Self::PARTS, 1)
+  }
+}
+
+syntax_node! {
+  /// Unary expression: `!arg`, `typeof arg`, ...
+  UnaryExpr: NodeUnaryExpr
+}
+
+impl UnaryExpr {
+  pub fn op(&self) -> Option<UnaryOp> {
+    self.syntax.children_with_tokens().find_map(|symbol| match symbol {
+      SyntaxSymbol::Token(token) => lower::unary_op(token.kind()),
+      SyntaxSymbol::Node(_) => None,
+    })
+  }
+
+  pub fn arg(&self) -> Option<Expr> {
+    child(&self.syntax)
+  }
+}
+
+syntax_node! {
+  /// Update expression: `++arg` or `arg--`
+  UpdateExpr: NodeUpdateExpr
+}
+
+impl UpdateExpr {
+  pub fn op(&self) -> Option<UpdateOp> {
+    let token = token(&self.syntax, &[SyntaxKind::TokenPlusPlus, SyntaxKind::TokenMinusMinus])?;
+    match token.kind() {
+      SyntaxKind::TokenPlusPlus => Some(UpdateOp::Increment),
+      _ => Some(UpdateOp::Decrement),
+    }
+  }
+
+  pub fn is_prefix(&self) -> bool {
+    self
+      .syntax
+      .children_with_tokens()
+      .find(|symbol| !symbol.kind().is_trivia())
+      .and_then(|symbol| symbol.into_token())
+      .is_some()
+  }
+
+  pub fn arg(&self) -> Option<Expr> {
+    child(&self.syntax)
+  }
+}
+
+syntax_node! {
+  /// Binary expression: `left + right`
+  BinExpr: NodeBinExpr
+}
+
+impl BinExpr {
+  pub fn left(&self) -> Option<Expr> {
+    self.syntax.children().next().and_then(|node| Expr::try_from(node).ok())
+  }
+
+  pub fn op(&self) -> Option<BinOp> {
+    self.syntax.children_with_tokens().find_map(|symbol| match symbol {
+      SyntaxSymbol::Token(token) => lower::bin_op(token.kind()),
+      SyntaxSymbol::Node(_) => None,
+    })
+  }
+
+  pub fn right(&self) -> Option<Expr> {
+    self.syntax.children().nth(1).and_then(|node| Expr::try_from(node).ok())
+  }
+}
+
+syntax_node! {
+  /// Assignment: `target = value`, `target += value`, ...
+  AssignExpr: NodeAssignExpr
+}
+
+impl AssignExpr {
+  /// Returns the assigned pattern, an expression in the lossless tree.
+  pub fn target(&self) -> Option<Expr> {
+    self.syntax.children().next().and_then(|node| Expr::try_from(node).ok())
+  }
+
+  pub fn op(&self) -> Option<AssignOp> {
+    self.syntax.children_with_tokens().find_map(|symbol| match symbol {
+      SyntaxSymbol::Token(token) => lower::assign_op(token.kind()),
+      SyntaxSymbol::Node(_) => None,
+    })
+  }
+
+  pub fn value(&self) -> Option<Expr> {
+    self.syntax.children().nth(1).and_then(|node| Expr::try_from(node).ok())
+  }
+}
+
+syntax_node! {
+  /// Conditional expression: `test ? consequent : alternate`
+  CondExpr: NodeCondExpr
+}
+
+impl CondExpr {
+  const PARTS: &'static [SyntaxKind] = &[SyntaxKind::TokenQuestion, SyntaxKind::TokenColon];
+
+  pub fn test(&self) -> Option<Expr> {
+    part(&self.syntax, Self::PARTS, 0)
+  }
+
+  pub fn consequent(&self) -> Option<Expr> {
+    part(&self.syntax, Self::PARTS, 1)
+  }
+
+  pub fn alternate(&self) -> Option<Expr> {
+    part(&self.syntax, Self::PARTS, 2)
+  }
+}
+
+syntax_node! {
+  /// Sequence expression: `a, b, c`
+  SeqExpr: NodeSeqExpr
+}
+
+impl SeqExpr {
+  pub fn exprs(&self) -> impl Iterator<Item = Expr> {
+    children(&self.syntax)
+  }
+}
+
+syntax_node! {
+  /// Flash 4 target path with a variable: `/clip/sub:score`
+  TargetPath: NodeTargetPath
+}
+
+impl TargetPath {
+  pub fn path(&self) -> Option<SyntaxToken> {
+    token(&self.syntax, &[SyntaxKind::TokenTargetPath])
+  }
+}
+
+syntax_node! {
+  /// Decompiler register local: `_loc3_`
+  Register: NodeRegister
+}
+
+impl traits::Register for Register {
+  fn index(&self) -> u8 {
+    token(&self.syntax, &[SyntaxKind::TokenIdent])
+      .and_then(|token| register_index(&token.text().to_ascii_lowercase()))
+      .unwrap_or_default()
+  }
+}
+
+syntax_node! {
+  /// Decompiler stack pop: `§§pop()`
+  PopExpr: NodePopExpr
+}
+
+impl traits::PopExpr for PopExpr {}
+
+syntax_node! {
+  /// Generic expression node, wrapping another expression
+  Expression: NodeExpression
+}
+
+impl Expression {
+  pub fn expr(&self) -> Option<Expr> {
+    child(&self.syntax)
+  }
+}
+
+pub(crate) fn unescape_string(quoted: &str) -> Option<String> {
+  let content = find_quoted_content(quoted)?;
+  let str_content = &quoted[content.range];
+  let mut unescaped: String = String::with_capacity(str_content.len());
+  let mut has_error = false;
+  // TODO: Return iterator, so we can stop at the first error
+  unescape_string_content(
+    str_content,
+    content.quotes,
+    &mut |_, unescaped_char| match unescaped_char {
+      Ok(c) => unescaped.push(c),
+      Err(_) => has_error = true,
+    },
+  );
+  if has_error {
+    None
+  } else {
+    Some(unescaped)
+  }
+}
+
+fn find_quoted_content(quoted: &str) -> Option<QuotedContent> {
+  let chars = quoted.char_indices();
+  let (first_idx, first_char) = chars.clone().next()?;
+  let (last_idx, last_char) = chars.clone().next_back()?;
+  if first_idx == last_idx {
+    // There is only one character in `quoted`
+    return None;
   }
   debug_assert!(first_idx < last_idx);
   match (first_char, last_char) {
@@ -336,29 +2308,357 @@ fn unescape_char(first_char: char, chars: &mut Chars, quotes: QuoteKind) -> Resu
       Some('r') => Ok('\r'),
       Some('t') => Ok('\t'),
       Some('v') => Ok('\x0b'),
-      Some('x') => unimplemented!("UnescapeHexSequence"),
-      Some('u') => unimplemented!("UnescapeUnicodeSequence"),
-      Some('0') => unimplemented!("UnescapeNulOrOctal"),
-      Some(_) => unimplemented!("UnescapeNonEscapeChar"),
+      Some('x') => hex_value(chars, 2)
+        .map(|value| char::from(value as u8))
+        .ok_or(UnescapeError::InvalidHexEscape),
+      Some('u') => unescape_unicode(chars),
+      Some(first_digit @ '0'..='7') => Ok(unescape_octal(first_digit, chars)),
+      // Other escaped characters stand for themselves: `\q` is `q`
+      Some(c) => Ok(c),
     },
     _ => Ok(first_char),
   }
 }
 
+/// Reads exactly `len` hex digits.
+fn hex_value(chars: &mut Chars, len: usize) -> Option<u32> {
+  let mut value: u32 = 0;
+  for _ in 0..len {
+    let digit = chars.clone().next()?.to_digit(16)?;
+    chars.next();
+    value = value * 16 + digit;
+  }
+  Some(value)
+}
+
+/// Unescapes the UTF-16 code unit of `\uXXXX`, after the `u`.
+///
+/// A high surrogate is combined with an escaped low surrogate immediately
+/// following it. Lone surrogates can't be represented in a Rust string and
+/// are replaced with `U+FFFD`.
+fn unescape_unicode(chars: &mut Chars) -> Result<char, UnescapeError> {
+  let unit = hex_value(chars, 4).ok_or(UnescapeError::InvalidUnicodeEscape)?;
+  if (0xd800..0xdc00).contains(&unit) {
+    let mut low_chars = chars.clone();
+    if low_chars.next() == Some('\\') && low_chars.next() == Some('u') {
+      if let Some(low @ 0xdc00..=0xdfff) = hex_value(&mut low_chars, 4) {
+        *chars = low_chars;
+        let code_point = 0x10000 + ((unit - 0xd800) << 10) + (low - 0xdc00);
+        return Ok(std::char::from_u32(code_point).unwrap_or(std::char::REPLACEMENT_CHARACTER));
+      }
+    }
+  }
+  Ok(std::char::from_u32(unit).unwrap_or(std::char::REPLACEMENT_CHARACTER))
+}
+
+/// Unescapes a NUL or legacy octal escape such as `\0` or `\101`.
+///
+/// The value is at most `\377`: `\400` is `\40` followed by `0`.
+fn unescape_octal(first_digit: char, chars: &mut Chars) -> char {
+  let mut value: u32 = first_digit.to_digit(8).unwrap_or(0);
+  let max_len = if value <= 3 { 3 } else { 2 };
+  for _ in 1..max_len {
+    match chars.clone().next().and_then(|c| c.to_digit(8)) {
+      Some(digit) => {
+        chars.next();
+        value = value * 8 + digit;
+      }
+      None => break,
+    }
+  }
+  char::from(value as u8)
+}
+
 #[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd, Hash)]
 enum UnescapeError {
   LoneSlash,
   /// Found a non-escaped character that can only appear as escaped
   /// For example `"` must always be escaped inside a double-quoted string literal
   EscapeOnlyChar,
+  /// `\x` not followed by two hex digits
+  InvalidHexEscape,
+  /// `\u` not followed by four hex digits
+  InvalidUnicodeEscape,
 }
 
 #[cfg(test)]
 mod tests {
-  use super::SyntaxKind;
+  use super::*;
+  use crate::parser::{
+    parse, parse_class_file, parse_expr, parse_flash4, parse_instance_script, parse_stmt, ParseOptions,
+  };
+  use crate::types::ast::traits::{IdentExpr as _, IdentPat as _, NumLit as _, Register as _, StrLit as _};
+  use std::collections::BTreeSet;
 
   #[test]
   fn test_syntax_kind_variant_count() {
-    assert_eq!(SyntaxKind::VARIANT_COUNT, 22);
+    assert_eq!(SyntaxKind::VARIANT_COUNT, 180);
+  }
+
+  fn is_typed(node: &SyntaxNode) -> bool {
+    let node = || node.clone();
+    Stmt::try_from(node()).is_ok()
+      || Expr::try_from(node()).is_ok()
+      || Script::try_from(node()).is_ok()
+      || InstanceScript::try_from(node()).is_ok()
+      || ClassFile::try_from(node()).is_ok()
+      || ExprRoot::try_from(node()).is_ok()
+      || StmtRoot::try_from(node()).is_ok()
+      || CaseClause::try_from(node()).is_ok()
+      || CatchClause::try_from(node()).is_ok()
+      || FinallyClause::try_from(node()).is_ok()
+      || Event::try_from(node()).is_ok()
+      || ExtendsClause::try_from(node()).is_ok()
+      || ImplementsClause::try_from(node()).is_ok()
+      || ClassMember::try_from(node()).is_ok()
+      || ParamList::try_from(node()).is_ok()
+      || Param::try_from(node()).is_ok()
+      || QualifiedName::try_from(node()).is_ok()
+      || Metadata::try_from(node()).is_ok()
+      || MetadataArg::try_from(node()).is_ok()
+      || VarDeclarator::try_from(node()).is_ok()
+      || TypeAnnotation::try_from(node()).is_ok()
+  }
+
+  #[test]
+  fn test_every_node_kind_is_typed() {
+    let roots = vec![
+      parse(
+        concat!(
+          "#include \"lib.as\"\n",
+          "import flash.display.*;\n",
+          "var a: Number = 0x10, b;\n",
+          "label: while (a < 10) { a++; if (!a) break label; else continue; }\n",
+          "do { --a; } while (a);\n",
+          "for (var i = 0; i < 3; i += 1) {}\n",
+          "for (k in this) ;\n",
+          "switch (a) { case 1: trace(\"one\"); default: }\n",
+          "try { throw null; } catch (e: Error) {} finally { return undefined; }\n",
+          "with (super.x) tellTarget(\"/clip\") ifFrameLoaded(\"scene\", 5) a = true ? newline : (f(1, 2), c[0]);\n",
+          "}\n",
+        ),
+        &ParseOptions::default(),
+      )
+      .syntax(),
+      parse_class_file(
+        concat!(
+          "class a.B extends C implements D, E {\n",
+          "  [Inspectable(name = \"x\", 1)]\n",
+          "  static private var f: Number;\n",
+          "  function B(v: Number) {}\n",
+          "  public function get g(): Number { return 1; }\n",
+          "}\n",
+        ),
+        &ParseOptions::default(),
+      )
+      .syntax(),
+      parse_class_file(
+        "interface I extends J { function f(): Void; }",
+        &ParseOptions::default(),
+      )
+      .syntax(),
+      parse_instance_script(
+        "on (press, keyPress \"<Enter>\") {} onClipEvent (load) {}",
+        &ParseOptions::default(),
+      )
+      .syntax(),
+      parse_flash4("/clip/sub:score = 1;").syntax(),
+      parse(
+        "§§push(_loc1_); §§goto(lbl); lbl: _loc2_ = §§pop();",
+        &ParseOptions::decompiler(),
+      )
+      .syntax(),
+      parse_expr("1", &ParseOptions::default()).syntax(),
+      parse_stmt(";", &ParseOptions::default()).syntax(),
+    ];
+
+    let mut kinds: BTreeSet<SyntaxKind> = BTreeSet::new();
+    for root in roots {
+      for node in root.descendants() {
+        assert!(is_typed(&node), "untyped node: {:?}", node);
+        kinds.insert(node.kind());
+      }
+    }
+    // `NodeExpression` is never built by the parser
+    let expected: BTreeSet<SyntaxKind> = (0..SyntaxKind::VARIANT_COUNT as u16)
+      .filter_map(|raw| SyntaxKind::try_from(raw).ok())
+      .filter(|kind| !kind.is_token() && *kind != SyntaxKind::NodeExpression)
+      .collect();
+    assert_eq!(kinds, expected);
+  }
+
+  #[test]
+  fn test_typed_accessors() {
+    let root = Script::try_from(
+      parse(
+        "var a: Number = 0x10, b; for (var i = 0; i < a; i++) { trace(i, \"x\"); }",
+        &ParseOptions::default(),
+      )
+      .syntax(),
+    )
+    .unwrap();
+    let stmts: Vec<Stmt> = root.stmts().collect();
+    assert_eq!(stmts.len(), 2);
+
+    let decl = match &stmts[0] {
+      Stmt::VarDecl(decl) => decl,
+      stmt => panic!("expected a variable declaration: {:?}", stmt),
+    };
+    let declarators: Vec<VarDeclarator> = decl.declarators().collect();
+    assert_eq!(declarators.len(), 2);
+    assert_eq!(declarators[0].name().unwrap().name(), "a");
+    let type_name = declarators[0]
+      .type_annotation()
+      .and_then(|annotation| annotation.name())
+      .unwrap();
+    let segments: Vec<String> = type_name.segments().map(|token| token.text().to_string()).collect();
+    assert_eq!(segments, vec!["Number"]);
+    match declarators[0].init() {
+      Some(Expr::NumLit(lit)) => assert_eq!(lit.value(), 16.0),
+      init => panic!("expected a number literal: {:?}", init),
+    }
+    assert_eq!(declarators[1].name().unwrap().name(), "b");
+    assert!(declarators[1].init().is_none());
+
+    let for_stmt = match &stmts[1] {
+      Stmt::For(for_stmt) => for_stmt,
+      stmt => panic!("expected a for loop: {:?}", stmt),
+    };
+    assert!(matches!(for_stmt.init(), Some(ForInit::VarDecl(_))));
+    match for_stmt.test() {
+      Some(Expr::Bin(test)) => {
+        assert_eq!(test.op(), Some(BinOp::Less));
+        assert!(matches!(test.right(), Some(Expr::Ident(ref ident)) if ident.name() == "a"));
+      }
+      test => panic!("expected a binary expression: {:?}", test),
+    }
+    match for_stmt.update() {
+      Some(Expr::Update(update)) => {
+        assert_eq!(update.op(), Some(UpdateOp::Increment));
+        assert!(!update.is_prefix());
+      }
+      update => panic!("expected an update expression: {:?}", update),
+    }
+    let body = match for_stmt.body() {
+      Some(Stmt::Block(body)) => body,
+      body => panic!("expected a block: {:?}", body),
+    };
+    let call = match body.stmts().next().and_then(|stmt| match stmt {
+      Stmt::Expr(stmt) => stmt.expr(),
+      _ => None,
+    }) {
+      Some(Expr::Call(call)) => call,
+      expr => panic!("expected a call: {:?}", expr),
+    };
+    assert!(matches!(call.callee(), Some(Expr::Ident(ref ident)) if ident.name() == "trace"));
+    let args: Vec<Expr> = call.args().collect();
+    assert_eq!(args.len(), 2);
+    assert!(matches!(&args[1], Expr::StrLit(lit) if lit.value() == "x"));
+    assert_eq!(
+      call.syntax().parent().map(|parent| parent.kind()),
+      Some(SyntaxKind::NodeStatement)
+    );
+  }
+
+  #[test]
+  fn test_typed_class_members() {
+    let text = "class a.B { static private var f; function B() {} function set g(v) {} function h() {} }";
+    let root = ClassFile::try_from(parse_class_file(text, &ParseOptions::default()).syntax()).unwrap();
+    let class = match root.stmts().next() {
+      Some(Stmt::Class(class)) => class,
+      stmt => panic!("expected a class: {:?}", stmt),
+    };
+    let members: Vec<ClassMember> = class.members().collect();
+    let field = match &members[0] {
+      ClassMember::Field(field) => field,
+      member => panic!("expected a field: {:?}", member),
+    };
+    assert_eq!(
+      field.modifiers(),
+      Modifiers {
+        is_static: true,
+        access: Some(traits::Access::Private)
+      }
+    );
+    let kinds: Vec<MethodKind> = members
+      .iter()
+      .filter_map(|member| match member {
+        ClassMember::Method(method) => Some(method.kind()),
+        _ => None,
+      })
+      .collect();
+    assert_eq!(
+      kinds,
+      vec![MethodKind::Constructor, MethodKind::Set, MethodKind::Method]
+    );
+  }
+
+  #[test]
+  fn test_typed_tell_target_scene() {
+    let root =
+      StmtRoot::try_from(parse_stmt("ifFrameLoaded(\"scene\", 5) {}", &ParseOptions::default()).syntax()).unwrap();
+    match root.stmt() {
+      Some(Stmt::IfFrameLoaded(stmt)) => {
+        assert!(matches!(stmt.scene(), Some(Expr::StrLit(_))));
+        assert!(matches!(stmt.frame(), Some(Expr::NumLit(_))));
+        assert!(matches!(stmt.body(), Some(Stmt::Block(_))));
+      }
+      stmt => panic!("expected ifFrameLoaded: {:?}", stmt),
+    }
+    let root = StmtRoot::try_from(parse_stmt("tellTarget(\"/clip\") {}", &ParseOptions::default()).syntax()).unwrap();
+    match root.stmt() {
+      Some(Stmt::TellTarget(stmt)) => {
+        assert!(stmt.scene().is_none());
+        assert!(matches!(stmt.target(), Some(Expr::StrLit(_))));
+        assert!(matches!(stmt.body(), Some(Stmt::Block(_))));
+      }
+      stmt => panic!("expected tellTarget: {:?}", stmt),
+    }
+  }
+
+  #[test]
+  fn test_typed_decompiler_register() {
+    let parsed = parse("_loc3_ = §§pop();", &ParseOptions::decompiler());
+    let root = Script::try_from(parsed.syntax()).unwrap();
+    let assign = match root.stmts().next() {
+      Some(Stmt::Expr(stmt)) => match stmt.expr() {
+        Some(Expr::Assign(assign)) => assign,
+        expr => panic!("expected an assignment: {:?}", expr),
+      },
+      stmt => panic!("expected an expression statement: {:?}", stmt),
+    };
+    assert!(matches!(assign.target(), Some(Expr::Register(ref register)) if register.index() == 3));
+    assert_eq!(assign.op(), Some(AssignOp::Assign));
+    assert!(matches!(assign.value(), Some(Expr::Pop(_))));
+  }
+
+  #[test]
+  fn test_typed_malformed() {
+    let root = StmtRoot::try_from(parse_stmt("if (", &ParseOptions::default()).syntax()).unwrap();
+    match root.stmt() {
+      Some(Stmt::If(stmt)) => {
+        assert!(stmt.consequent().is_none());
+        assert!(stmt.alternate().is_none());
+      }
+      stmt => panic!("expected an if statement: {:?}", stmt),
+    }
+    let root = Script::try_from(parse("var = 1; for (;;) a.; x = ;", &ParseOptions::default()).syntax()).unwrap();
+    for node in root.syntax().descendants() {
+      if let Ok(declarator) = VarDeclarator::try_from(node.clone()) {
+        assert!(declarator.name().is_none());
+      }
+      if let Ok(stmt) = ForStmt::try_from(node.clone()) {
+        assert!(stmt.init().is_none());
+        assert!(stmt.test().is_none());
+        assert!(stmt.update().is_none());
+      }
+      if let Ok(member) = MemberExpr::try_from(node.clone()) {
+        assert!(member.key().is_none());
+      }
+      if let Ok(assign) = AssignExpr::try_from(node) {
+        assert!(assign.value().is_none());
+      }
+    }
+    assert!(Script::try_from(parse_expr("1", &ParseOptions::default()).syntax()).is_err());
   }
 }